@@ -0,0 +1,434 @@
+//! Command implementations for `bq25730-cli`.
+//!
+//! Everything here is generic over `embedded_hal::i2c::I2c` and `DelayNs`, so the
+//! commands can be run against a mock bus in tests; `main.rs` wires them to
+//! `linux-embedded-hal`.
+
+use std::{fmt, io, path::PathBuf};
+
+use bq25730_async_rs::{
+    BQ25731_DEVICE_ID, Bq25730, ChipVariant, Config, ConfigChanges, Error, MilliAmps, MilliVolts,
+    RegisterAccess, SenseResistorValue,
+    data_types::{
+        AdcMeasurements, AdcOption, ChargeCurrentSetting, ChargeVoltageSetting, IinHostSetting,
+        InputVoltageSetting, OtgCurrentSetting, OtgVoltageSetting, VsysMinSetting,
+    },
+    registers::{AdcOptionFlags, AdcOptionMsbFlags, ChargeOption3MsbFlags, Register},
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Value of the ManufacturerID register (0x2E).
+pub const MANUFACTURER_ID: u8 = bq25730_async_rs::BQ25730_MANUFACTURER_ID;
+/// Value of the DeviceID register (0x2F) on the BQ25730.
+pub const DEVICE_ID: u8 = bq25730_async_rs::BQ25730_DEVICE_ID;
+
+/// Delay between polls of ADC_START while a one-shot conversion runs.
+const ADC_POLL_INTERVAL_MS: u32 = 10;
+/// Number of polls before a one-shot conversion is considered stuck.
+const ADC_POLL_ATTEMPTS: usize = 50;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "bq25730-cli",
+    version,
+    about = "Inspect and configure a BQ25730 over Linux i2c-dev"
+)]
+pub struct Cli {
+    /// I2C bus device.
+    #[arg(short, long, default_value = "/dev/i2c-1")]
+    pub bus: PathBuf,
+    /// 7-bit I2C address, decimal or 0x-prefixed hex.
+    #[arg(short, long, default_value = "0x6b", value_parser = parse_u8)]
+    pub address: u8,
+    /// Battery cell count (1-5) used for the default configuration.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=5))]
+    pub cells: u8,
+    /// Battery path sense resistor.
+    #[arg(long, value_enum, default_value_t = Rsns::R5m)]
+    pub rsns_bat: Rsns,
+    /// Input path sense resistor.
+    #[arg(long, value_enum, default_value_t = Rsns::R5m)]
+    pub rsns_ac: Rsns,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+impl Cli {
+    /// Configuration the driver is created with before any command runs.
+    ///
+    /// Only the sense resistors matter for reads; nothing is written unless the
+    /// command asks for it.
+    pub fn config(&self) -> Config {
+        Config::new(self.cells, self.rsns_bat.into(), self.rsns_ac.into())
+    }
+}
+
+/// Sense resistor value as accepted on the command line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Rsns {
+    #[value(name = "5m")]
+    R5m,
+    #[value(name = "10m")]
+    R10m,
+}
+
+impl From<Rsns> for SenseResistorValue {
+    fn from(value: Rsns) -> Self {
+        match value {
+            Rsns::R5m => SenseResistorValue::R5mOhm,
+            Rsns::R10m => SenseResistorValue::R10mOhm,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Read the ManufacturerID and DeviceID registers.
+    Id,
+    /// Read and decode every register.
+    Dump,
+    /// Show the ChargerStatus and ProchotStatus flags.
+    Status,
+    /// Run a one-shot conversion on every ADC channel and print the results.
+    Adc,
+    /// Print the configuration registers as TOML, in the format `apply` accepts.
+    Config,
+    /// Write a single setting.
+    Set {
+        #[command(subcommand)]
+        setting: Setting,
+    },
+    /// Turn the OTG output on or off.
+    Otg {
+        #[command(subcommand)]
+        action: OtgAction,
+    },
+    /// Run the ADC continuously and print a table row per interval.
+    Watch {
+        /// Time between rows in milliseconds.
+        #[arg(short, long, default_value_t = 1000)]
+        interval_ms: u32,
+        /// Stop after this many rows instead of running until interrupted.
+        #[arg(short = 'n', long)]
+        count: Option<u32>,
+    },
+    /// Write the configuration from a TOML file, touching only registers that differ.
+    Apply {
+        /// Path to a TOML file, e.g. the output of `config`.
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Subcommand)]
+pub enum Setting {
+    /// ChargeCurrent in mA.
+    ChargeCurrent { milliamps: u16 },
+    /// ChargeVoltage in mV.
+    ChargeVoltage { millivolts: u16 },
+    /// IIN_HOST input current limit in mA.
+    InputCurrent { milliamps: u16 },
+    /// InputVoltage (VINDPM) in mV.
+    InputVoltage { millivolts: u16 },
+    /// VSYS_MIN in mV.
+    VsysMin { millivolts: u16 },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Subcommand)]
+pub enum OtgAction {
+    /// Program OTGVoltage and OTGCurrent, then set EN_OTG.
+    On { millivolts: u16, milliamps: u16 },
+    /// Clear EN_OTG.
+    Off,
+}
+
+/// Errors reported by the CLI.
+#[derive(Debug)]
+pub enum CliError<E> {
+    /// The driver returned an error.
+    Driver(Error<E>),
+    /// Writing output or reading a file failed.
+    Io(io::Error),
+    /// The configuration file could not be parsed.
+    Config(toml::de::Error),
+    /// The configuration could not be printed as TOML.
+    Format(toml::ser::Error),
+    /// ID registers did not match a BQ25730.
+    UnexpectedId { manufacturer: u8, device: u8 },
+    /// A one-shot ADC conversion did not finish.
+    AdcTimeout,
+}
+
+impl<E> From<Error<E>> for CliError<E> {
+    fn from(e: Error<E>) -> Self {
+        CliError::Driver(e)
+    }
+}
+
+impl<E> From<io::Error> for CliError<E> {
+    fn from(e: io::Error) -> Self {
+        CliError::Io(e)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for CliError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Driver(e) => write!(f, "driver error: {e:?}"),
+            CliError::Io(e) => write!(f, "I/O error: {e}"),
+            CliError::Config(e) => write!(f, "invalid configuration: {e}"),
+            CliError::Format(e) => write!(f, "cannot format configuration: {e}"),
+            CliError::UnexpectedId {
+                manufacturer,
+                device,
+            } => write!(
+                f,
+                "unexpected IDs: manufacturer 0x{manufacturer:02X} (expected 0x{MANUFACTURER_ID:02X}), \
+                 device 0x{device:02X} (expected 0x{DEVICE_ID:02X} or 0x{BQ25731_DEVICE_ID:02X})"
+            ),
+            CliError::AdcTimeout => write!(f, "ADC conversion did not complete"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for CliError<E> {}
+
+/// Runs `command`, writing human-readable output to `out`.
+///
+/// `Apply` reads its file here; use `apply_toml` to apply a configuration held in memory.
+pub fn run<I2C, D, W>(
+    charger: &mut Bq25730<I2C>,
+    delay: &mut D,
+    command: &Command,
+    out: &mut W,
+) -> Result<(), CliError<I2C::Error>>
+where
+    I2C: I2c + Send + 'static,
+    D: DelayNs,
+    W: io::Write,
+{
+    match command {
+        Command::Id => {
+            let (manufacturer, device) = read_id(charger)?;
+            writeln!(out, "ManufacturerID: 0x{manufacturer:02X}")?;
+            writeln!(out, "DeviceID:       0x{device:02X}")?;
+            if ChipVariant::from_ids(manufacturer, device).is_none() {
+                return Err(CliError::UnexpectedId {
+                    manufacturer,
+                    device,
+                });
+            }
+        }
+        Command::Dump => {
+            let dump = charger.dump_registers()?;
+            write!(out, "{dump}")?;
+        }
+        Command::Status => {
+            let status = charger.read_charger_status()?;
+            let prochot = charger.read_prochot_status()?;
+            writeln!(out, "Charger status: {:?}", status.status_flags)?;
+            writeln!(out, "Charger faults: {:?}", status.fault_flags)?;
+            writeln!(out, "PROCHOT status: {:?}", prochot.lsb_flags)?;
+            writeln!(
+                out,
+                "PROCHOT control: {:?} (width {})",
+                prochot.msb_flags, prochot.prochot_width
+            )?;
+        }
+        Command::Adc => {
+            let adc = read_adc_once(charger, delay)?;
+            write_adc(out, &adc)?;
+        }
+        Command::Config => {
+            let config = charger.read_config()?;
+            let text = toml::to_string(&config).map_err(CliError::Format)?;
+            write!(out, "{text}")?;
+        }
+        Command::Set { setting } => {
+            set(charger, *setting)?;
+            writeln!(out, "ok")?;
+        }
+        Command::Otg { action } => {
+            otg(charger, *action)?;
+            writeln!(out, "ok")?;
+        }
+        Command::Watch { interval_ms, count } => {
+            watch(charger, delay, *interval_ms, *count, out)?;
+        }
+        Command::Apply { path } => {
+            let text = std::fs::read_to_string(path)?;
+            let changes = apply_toml(charger, &text)?;
+            writeln!(out, "Written: {changes:?}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the ManufacturerID and DeviceID registers.
+pub fn read_id<I2C>(charger: &mut Bq25730<I2C>) -> Result<(u8, u8), Error<I2C::Error>>
+where
+    I2C: I2c + Send + 'static,
+{
+    let manufacturer = charger.read_register(Register::ManufacturerID)?;
+    let device = charger.read_register(Register::DeviceID)?;
+    Ok((manufacturer, device))
+}
+
+/// Writes a single setting, converted with the configured sense resistors.
+pub fn set<I2C>(charger: &mut Bq25730<I2C>, setting: Setting) -> Result<(), Error<I2C::Error>>
+where
+    I2C: I2c + Send + 'static,
+{
+    let rsns_bat = charger.config().rsns_bat;
+    match setting {
+        Setting::ChargeCurrent { milliamps } => charger.set_charge_current_setting(
+            ChargeCurrentSetting::from_milliamps(MilliAmps(milliamps), rsns_bat),
+        ),
+        Setting::ChargeVoltage { millivolts } => charger.set_charge_voltage_setting(
+            ChargeVoltageSetting::from_millivolts(MilliVolts(millivolts)),
+        ),
+        Setting::InputCurrent { milliamps } => {
+            charger.set_iin_host_setting(IinHostSetting::from_milliamps(MilliAmps(milliamps)))
+        }
+        Setting::InputVoltage { millivolts } => charger.set_input_voltage_setting(
+            InputVoltageSetting::from_millivolts(MilliVolts(millivolts)),
+        ),
+        Setting::VsysMin { millivolts } => {
+            charger.set_vsys_min_setting(VsysMinSetting::from_millivolts(MilliVolts(millivolts)))
+        }
+    }
+}
+
+/// Programs and enables, or disables, the OTG output.
+pub fn otg<I2C>(charger: &mut Bq25730<I2C>, action: OtgAction) -> Result<(), Error<I2C::Error>>
+where
+    I2C: I2c + Send + 'static,
+{
+    let mut option3 = charger.read_charge_option3()?;
+    match action {
+        OtgAction::On {
+            millivolts,
+            milliamps,
+        } => {
+            let rsns_bat = charger.config().rsns_bat;
+            charger.set_otg_voltage_setting(OtgVoltageSetting::from_millivolts(MilliVolts(
+                millivolts,
+            )))?;
+            charger.set_otg_current_setting(OtgCurrentSetting::from_milliamps(
+                MilliAmps(milliamps),
+                rsns_bat,
+            ))?;
+            option3.msb_flags.insert(ChargeOption3MsbFlags::EN_OTG);
+        }
+        OtgAction::Off => option3.msb_flags.remove(ChargeOption3MsbFlags::EN_OTG),
+    }
+    charger.set_charge_option3(option3)
+}
+
+/// Starts a one-shot conversion on every channel, waits for ADC_START to clear
+/// and reads the results.
+pub fn read_adc_once<I2C, D>(
+    charger: &mut Bq25730<I2C>,
+    delay: &mut D,
+) -> Result<AdcMeasurements, CliError<I2C::Error>>
+where
+    I2C: I2c + Send + 'static,
+    D: DelayNs,
+{
+    charger.set_adc_option(AdcOption {
+        msb_flags: AdcOptionMsbFlags::ADC_START | AdcOptionMsbFlags::ADC_FULLSCALE,
+        lsb_flags: AdcOptionFlags::all(),
+    })?;
+    for _ in 0..ADC_POLL_ATTEMPTS {
+        delay.delay_ms(ADC_POLL_INTERVAL_MS);
+        let option = charger.read_adc_option()?;
+        if !option.msb_flags.contains(AdcOptionMsbFlags::ADC_START) {
+            return Ok(charger.read_adc_measurements()?);
+        }
+    }
+    Err(CliError::AdcTimeout)
+}
+
+/// Enables continuous conversion and prints one table row every `interval_ms`,
+/// `count` times or forever.
+pub fn watch<I2C, D, W>(
+    charger: &mut Bq25730<I2C>,
+    delay: &mut D,
+    interval_ms: u32,
+    count: Option<u32>,
+    out: &mut W,
+) -> Result<(), CliError<I2C::Error>>
+where
+    I2C: I2c + Send + 'static,
+    D: DelayNs,
+    W: io::Write,
+{
+    charger.set_adc_option(AdcOption {
+        msb_flags: AdcOptionMsbFlags::ADC_CONV
+            | AdcOptionMsbFlags::ADC_START
+            | AdcOptionMsbFlags::ADC_FULLSCALE,
+        lsb_flags: AdcOptionFlags::all(),
+    })?;
+    writeln!(
+        out,
+        "{:>8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}  status",
+        "VBUS", "VSYS", "VBAT", "ICHG", "IDCHG", "IIN", "PSYS", "CMPIN"
+    )?;
+    let mut rows = 0;
+    while count.is_none_or(|count| rows < count) {
+        delay.delay_ms(interval_ms);
+        let adc = charger.read_adc_measurements()?;
+        let status = charger.read_charger_status()?;
+        writeln!(
+            out,
+            "{:>8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}  {:?}",
+            adc.vbus.0.0,
+            adc.vsys.0.0,
+            adc.vbat.0.0,
+            adc.ichg.milliamps.0,
+            adc.idchg.milliamps.0,
+            adc.iin.milliamps.0,
+            adc.psys.0.0,
+            adc.cmpin.0.0,
+            status.status_flags
+        )?;
+        out.flush()?;
+        rows += 1;
+    }
+    Ok(())
+}
+
+/// Parses `text` as a `Config` and applies it, writing only the registers that
+/// differ from what the chip currently holds.
+pub fn apply_toml<I2C>(
+    charger: &mut Bq25730<I2C>,
+    text: &str,
+) -> Result<ConfigChanges, CliError<I2C::Error>>
+where
+    I2C: I2c + Send + 'static,
+{
+    let config: Config = toml::from_str(text).map_err(CliError::Config)?;
+    // The driver was created with a default config; diff against the chip instead.
+    let current = charger.read_config()?;
+    *charger.config_mut() = current;
+    Ok(charger.apply_config(&config)?)
+}
+
+fn write_adc<W: io::Write>(out: &mut W, adc: &AdcMeasurements) -> io::Result<()> {
+    writeln!(out, "VBUS:  {}", adc.vbus.0)?;
+    writeln!(out, "VSYS:  {}", adc.vsys.0)?;
+    writeln!(out, "VBAT:  {}", adc.vbat.0)?;
+    writeln!(out, "ICHG:  {}", adc.ichg.milliamps)?;
+    writeln!(out, "IDCHG: {}", adc.idchg.milliamps)?;
+    writeln!(out, "IIN:   {}", adc.iin.milliamps)?;
+    writeln!(out, "PSYS:  {}", adc.psys.0)?;
+    writeln!(out, "CMPIN: {}", adc.cmpin.0)
+}
+
+fn parse_u8(s: &str) -> Result<u8, String> {
+    let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => s.parse(),
+    };
+    parsed.map_err(|e| format!("invalid address `{s}`: {e}"))
+}
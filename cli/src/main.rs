@@ -0,0 +1,32 @@
+use std::{io, process::ExitCode};
+
+use bq25730_async_rs::Bq25730;
+use bq25730_cli::{Cli, run};
+use clap::Parser;
+use linux_embedded_hal::{Delay, I2cdev};
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let i2c = match I2cdev::new(&cli.bus) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            eprintln!("cannot open {}: {e}", cli.bus.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut charger = Bq25730::new(i2c, cli.address, cli.config());
+
+    match run(
+        &mut charger,
+        &mut Delay,
+        &cli.command,
+        &mut io::stdout().lock(),
+    ) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, Config, ConfigChanges, MilliAmps, registers::Register,
+};
+use bq25730_cli::{Cli, CliError, apply_toml, run};
+use clap::Parser;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+};
+
+fn read_8(reg: Register, value: u8) -> I2cTransaction {
+    I2cTransaction::write_read(BQ25730_I2C_ADDRESS, vec![reg as u8], vec![value])
+}
+
+fn read_16(reg: Register, lsb: u8, msb: u8) -> I2cTransaction {
+    I2cTransaction::write_read(BQ25730_I2C_ADDRESS, vec![reg as u8], vec![lsb, msb])
+}
+
+fn write_16(reg: Register, lsb: u8, msb: u8) -> I2cTransaction {
+    I2cTransaction::write(BQ25730_I2C_ADDRESS, vec![reg as u8, lsb, msb])
+}
+
+/// Register blocks (0x00-0x0F, 0x30-0x3F) holding `config`, as `read_config` reads them.
+fn config_reads(config: &Config) -> [I2cTransaction; 2] {
+    let mut low = vec![0u8; 16];
+    let mut high = vec![0u8; 16];
+    for (reg, lsb, msb) in config.register_values() {
+        let (block, index) = match reg as u8 {
+            addr @ 0x00..=0x0F => (&mut low, addr as usize),
+            addr => (&mut high, (addr - 0x30) as usize),
+        };
+        block[index] = lsb;
+        block[index + 1] = msb;
+    }
+    [
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption0 as u8],
+            low,
+        ),
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption1 as u8],
+            high,
+        ),
+    ]
+}
+
+/// Parses `args` as a command line and runs it against `expectations`, returning the output.
+fn run_args(
+    args: &[&str],
+    expectations: &[I2cTransaction],
+) -> Result<String, CliError<embedded_hal::i2c::ErrorKind>> {
+    let cli =
+        Cli::try_parse_from(std::iter::once("bq25730-cli").chain(args.iter().copied())).unwrap();
+    let mut charger = Bq25730::new(I2cMock::new(expectations), cli.address, cli.config());
+    let mut out = Vec::new();
+    let result = run(&mut charger, &mut NoopDelay, &cli.command, &mut out);
+    charger.i2c.done();
+    result.map(|()| String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn test_cli_parses_global_options() {
+    let cli = Cli::try_parse_from([
+        "bq25730-cli",
+        "--bus",
+        "/dev/i2c-3",
+        "--address",
+        "0x6A",
+        "--cells",
+        "2",
+        "--rsns-bat",
+        "10m",
+        "otg",
+        "on",
+        "5000",
+        "3000",
+    ])
+    .unwrap();
+    assert_eq!(cli.bus.to_str(), Some("/dev/i2c-3"));
+    assert_eq!(cli.address, 0x6A);
+    assert_eq!(
+        cli.config(),
+        Config::new(
+            2,
+            bq25730_async_rs::SenseResistorValue::R10mOhm,
+            bq25730_async_rs::SenseResistorValue::R5mOhm
+        )
+    );
+
+    assert!(Cli::try_parse_from(["bq25730-cli", "--cells", "6", "id"]).is_err());
+    assert!(Cli::try_parse_from(["bq25730-cli", "--address", "0x1FF", "id"]).is_err());
+}
+
+#[test]
+fn test_id() {
+    let output = run_args(
+        &["id"],
+        &[
+            read_8(Register::ManufacturerID, 0x40),
+            read_8(Register::DeviceID, 0xD5),
+        ],
+    )
+    .unwrap();
+    assert_eq!(output, "ManufacturerID: 0x40\nDeviceID:       0xD5\n");
+}
+
+#[test]
+fn test_id_rejects_other_devices() {
+    let result = run_args(
+        &["id"],
+        &[
+            read_8(Register::ManufacturerID, 0x40),
+            read_8(Register::DeviceID, 0x89),
+        ],
+    );
+    assert!(matches!(
+        result,
+        Err(CliError::UnexpectedId {
+            manufacturer: 0x40,
+            device: 0x89
+        })
+    ));
+}
+
+#[test]
+fn test_set_charge_current() {
+    // 2048 mA at 5 mOhm is 16 steps of 128 mA in bits 12:6.
+    let output = run_args(
+        &["set", "charge-current", "2048"],
+        &[write_16(Register::ChargeCurrent, 0x00, 0x04)],
+    )
+    .unwrap();
+    assert_eq!(output, "ok\n");
+}
+
+#[test]
+fn test_otg_on_and_off() {
+    run_args(
+        &["otg", "on", "5000", "3000"],
+        &[
+            read_16(Register::ChargeOption3, 0x34, 0x04),
+            write_16(Register::OTGVoltage, 0xC4, 0x09),
+            read_8(Register::OTGCurrentMsb, 0x00),
+            write_16(Register::OTGCurrent, 0x00, 0x1E),
+            write_16(Register::ChargeOption3, 0x34, 0x14),
+        ],
+    )
+    .unwrap();
+
+    run_args(
+        &["otg", "off"],
+        &[
+            read_16(Register::ChargeOption3, 0x34, 0x14),
+            write_16(Register::ChargeOption3, 0x34, 0x04),
+        ],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_adc_one_shot() {
+    let output = run_args(
+        &["adc"],
+        &[
+            // ADC_START | ADC_FULLSCALE, all channels
+            write_16(Register::ADCOption, 0xFF, 0x60),
+            read_16(Register::ADCOption, 0xFF, 0x60),
+            read_16(Register::ADCOption, 0xFF, 0x20),
+            I2cTransaction::write_read(
+                BQ25730_I2C_ADDRESS,
+                vec![Register::ADCPSYS as u8],
+                vec![0, 0, 0, 0, 0, 0, 0, 0],
+            ),
+        ],
+    )
+    .unwrap();
+    assert!(output.starts_with("VBUS:  0 mV\n"));
+    assert!(output.contains("IIN:   0 mA\n"));
+}
+
+#[test]
+fn test_watch_prints_requested_rows() {
+    let adc_block = I2cTransaction::write_read(
+        BQ25730_I2C_ADDRESS,
+        vec![Register::ADCPSYS as u8],
+        vec![0, 0, 0, 0, 0, 0, 0, 0],
+    );
+    let output = run_args(
+        &["watch", "--interval-ms", "10", "-n", "2"],
+        &[
+            write_16(Register::ADCOption, 0xFF, 0xE0),
+            adc_block.clone(),
+            read_16(Register::ChargerStatus, 0x00, 0x80),
+            adc_block,
+            read_16(Register::ChargerStatus, 0x00, 0x84),
+        ],
+    )
+    .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("    VBUS"));
+    assert!(lines[1].ends_with("ChargerStatusFlags(STAT_AC)"));
+    assert!(lines[2].ends_with("ChargerStatusFlags(STAT_AC | IN_FCHRG)"));
+}
+
+#[test]
+fn test_config_output_round_trips() {
+    let config = Config::default();
+    let output = run_args(&["config"], &config_reads(&config)).unwrap();
+    let parsed: Config = toml::from_str(&output).unwrap();
+    assert_eq!(parsed, config);
+}
+
+#[test]
+fn test_apply_toml_writes_only_changed_registers() {
+    let current = Config::default();
+    let mut wanted = current;
+    wanted.charge_current.milliamps = MilliAmps(1024);
+    let text = toml::to_string(&wanted).unwrap();
+
+    let mut expectations = config_reads(&current).to_vec();
+    expectations.push(write_16(Register::ChargeCurrent, 0x00, 0x02));
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    let changes = apply_toml(&mut charger, &text).unwrap();
+    assert_eq!(changes, ConfigChanges::CHARGE_CURRENT);
+    assert_eq!(charger.config().charge_current.milliamps, MilliAmps(1024));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_apply_toml_rejects_invalid_file() {
+    let mut charger = Bq25730::new(I2cMock::new(&[]), BQ25730_I2C_ADDRESS, Config::default());
+    assert!(matches!(
+        apply_toml(&mut charger, "charge_current = 5"),
+        Err(CliError::Config(_))
+    ));
+    charger.i2c.done();
+}
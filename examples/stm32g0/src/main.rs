@@ -14,7 +14,7 @@ use embassy_time::{Duration, Timer};
 use {defmt_rtt as _, panic_probe as _};
 
 use bq25730_async_rs::{
-    Bq25730,
+    Bq25730, MilliAmps, MilliVolts,
     data_types::{AdcMeasurements, ChargeCurrentSetting, ChargeVoltageSetting, SenseResistorValue},
     registers::{ChargeOption0MsbFlags, ChargeOption1MsbFlags, WatchdogTimerAdjust},
 };
@@ -92,19 +92,19 @@ async fn main(_spawner: Spawner) {
     // 2. 充电控制示例
     info!("--- Charging Control Example ---");
     // 设置充电电流为 512 mA (4 * 128mA LSB)
-    let charge_current = ChargeCurrentSetting::from_milliamps(512, bq.config().rsns_bat);
+    let charge_current = ChargeCurrentSetting::from_milliamps(MilliAmps(512), bq.config().rsns_bat);
     if let Err(e) = bq.set_charge_current_setting(charge_current).await {
         error!("Failed to set charge current: {:?}", e);
     } else {
-        info!("Charge current set to {} mA.", charge_current.to_milliamps());
+        info!("Charge current set to {}.", charge_current.to_milliamps());
     }
 
     // 设置充电电压为 18000 mV (5 节磷酸铁锂电池，每节 3.6V)
-    let charge_voltage = ChargeVoltageSetting::from_millivolts(18000);
+    let charge_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(18000));
     if let Err(e) = bq.set_charge_voltage_setting(charge_voltage).await {
         error!("Failed to set charge voltage: {:?}", e);
     } else {
-        info!("Charge voltage set to {} mV.", charge_voltage.to_millivolts());
+        info!("Charge voltage set to {}.", charge_voltage.to_millivolts());
     }
     info!("Charging control example complete.");
 
@@ -166,14 +166,14 @@ async fn main(_spawner: Spawner) {
         match bq.read_adc_measurements().await {
             Ok(adc_measurements) => {
                 info!("ADC Measurements:");
-                info!("  VSYS: {} mV", adc_measurements.vsys.0);
-                info!("  VBUS: {} mV", adc_measurements.vbus.0);
-                info!("  ICHG: {} mA", adc_measurements.ichg.milliamps);
-                info!("  IDCHG: {} mA", adc_measurements.idchg.milliamps);
-                info!("  CMPIN: {} mV", adc_measurements.cmpin.0);
-                info!("  IIN: {} mA", adc_measurements.iin.milliamps);
-                info!("  VBAT: {} mV", adc_measurements.vbat.0);
-                info!("  PSYS: {} mW", adc_measurements.psys.0);
+                info!("  VSYS: {}", adc_measurements.vsys.0);
+                info!("  VBUS: {}", adc_measurements.vbus.0);
+                info!("  ICHG: {}", adc_measurements.ichg.milliamps);
+                info!("  IDCHG: {}", adc_measurements.idchg.milliamps);
+                info!("  CMPIN: {}", adc_measurements.cmpin.0);
+                info!("  IIN: {}", adc_measurements.iin.milliamps);
+                info!("  VBAT: {}", adc_measurements.vbat.0);
+                info!("  PSYS: {}", adc_measurements.psys.0);
             }
             Err(e) => {
                 error!("Failed to read ADC measurements: {:?}", e);
@@ -0,0 +1,213 @@
+//! Host-side coulomb counting from the ICHG/IDCHG ADC readings.
+//!
+//! For boards without a gauge IC. [`CoulombCounter`] integrates the net
+//! battery current (ICHG minus IDCHG) of timestamped [`AdcMeasurements`] with
+//! the trapezoidal rule, and while the battery rests corrects the state of
+//! charge from VBAT through an open-circuit-voltage table.
+//!
+//! The ADC resolves ICHG and IDCHG only in steps of [`AdcIchg::lsb`] and
+//! [`AdcIdchg::lsb`], which depend on the battery sense resistor. Smaller
+//! currents read as zero and are not counted, so the count drifts over time;
+//! the OCV correction bounds that drift.
+
+use core::time::Duration;
+
+use crate::data_types::{AdcIchg, AdcIdchg, AdcMeasurements};
+use crate::units::{MilliAmps, MilliVolts};
+
+/// mA x us in one mAh.
+const MA_US_PER_MAH: i64 = 3_600_000_000;
+
+/// One entry of an open-circuit-voltage table.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OcvPoint {
+    /// Rested pack voltage, as read by the VBAT ADC.
+    pub vbat: MilliVolts,
+    pub soc_percent: u8,
+}
+
+/// Returns the state of charge for a rested `vbat`, interpolating linearly
+/// between the points of `table` (sorted by ascending `vbat`).
+///
+/// Voltages outside the table map to its first or last point. Returns
+/// `None` for an empty table.
+pub fn soc_from_ocv(table: &[OcvPoint], vbat: MilliVolts) -> Option<u8> {
+    let first = table.first()?;
+    if vbat <= first.vbat {
+        return Some(first.soc_percent);
+    }
+    for pair in table.windows(2) {
+        let (low, high) = (pair[0], pair[1]);
+        if vbat <= high.vbat {
+            let span = u32::from(high.vbat.0 - low.vbat.0);
+            let offset = u32::from(vbat.0 - low.vbat.0);
+            let low_soc = u32::from(low.soc_percent);
+            let high_soc = u32::from(high.soc_percent);
+            // Tables are monotonic, but don't underflow on one that isn't.
+            let soc = if high_soc >= low_soc {
+                low_soc + (high_soc - low_soc) * offset / span
+            } else {
+                low_soc - (low_soc - high_soc) * offset / span
+            };
+            return Some(soc as u8);
+        }
+    }
+    table.last().map(|p| p.soc_percent)
+}
+
+/// Settings of a [`CoulombCounter`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoulombCounterConfig<'a> {
+    /// Usable capacity of the battery.
+    pub capacity_mah: u32,
+    /// Rested VBAT to SoC table, sorted by ascending voltage. Empty disables
+    /// the voltage-based correction.
+    pub ocv_table: &'a [OcvPoint],
+    /// Net current at or below which the battery counts as resting, in
+    /// addition to readings below one ADC step.
+    pub rest_current: MilliAmps,
+    /// Time the battery must rest before VBAT is taken as its OCV.
+    pub rest_time: Duration,
+}
+
+impl CoulombCounterConfig<'_> {
+    /// Settings for a `capacity_mah` battery, without OCV correction.
+    pub const fn new(capacity_mah: u32) -> Self {
+        Self {
+            capacity_mah,
+            ocv_table: &[],
+            rest_current: MilliAmps(0),
+            rest_time: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+/// Net-charge integrator; see the module documentation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoulombCounter<'a> {
+    config: CoulombCounterConfig<'a>,
+    /// Remaining charge in mA x us.
+    charge: i64,
+    /// Timestamp and net current (mA, positive while charging) of the last update.
+    last: Option<(Duration, i32)>,
+    rested: Duration,
+}
+
+impl<'a> CoulombCounter<'a> {
+    /// Creates a counter starting at `initial_soc_percent` (clamped to 100).
+    pub fn new(config: CoulombCounterConfig<'a>, initial_soc_percent: u8) -> Self {
+        let mut counter = Self {
+            config,
+            charge: 0,
+            last: None,
+            rested: Duration::ZERO,
+        };
+        counter.set_soc(initial_soc_percent);
+        counter
+    }
+
+    /// Returns the counter settings.
+    pub fn config(&self) -> &CoulombCounterConfig<'a> {
+        &self.config
+    }
+
+    /// Overrides the state of charge, e.g. from a gauge reading or a full charge.
+    pub fn set_soc(&mut self, soc_percent: u8) {
+        self.charge = self.full_charge() * i64::from(soc_percent.min(100)) / 100;
+    }
+
+    /// Integrates the net current since the previous update and applies the
+    /// OCV correction once the battery has rested for `rest_time`.
+    ///
+    /// `timestamp` is any monotonic time base. An update that does not move
+    /// forward in time restarts the integration from this sample.
+    pub fn update(&mut self, timestamp: Duration, adc: &AdcMeasurements) {
+        let net = i32::from(adc.ichg.milliamps.0) - i32::from(adc.idchg.milliamps.0);
+        let resting = (adc.ichg.milliamps < AdcIchg::lsb(adc.ichg.rsns_bat)
+            && adc.idchg.milliamps < AdcIdchg::lsb(adc.idchg.rsns_bat))
+            || net.unsigned_abs() <= u32::from(self.config.rest_current.0);
+
+        match self.last {
+            Some((last_time, last_net)) if timestamp > last_time => {
+                let dt = timestamp - last_time;
+                let dt_us = i64::try_from(dt.as_micros()).unwrap_or(i64::MAX);
+                let average = (i64::from(last_net) + i64::from(net)) / 2;
+                self.charge = self
+                    .charge
+                    .saturating_add(average.saturating_mul(dt_us))
+                    .clamp(0, self.full_charge());
+                self.rested = if resting {
+                    self.rested.saturating_add(dt)
+                } else {
+                    Duration::ZERO
+                };
+            }
+            _ => self.rested = Duration::ZERO,
+        }
+        self.last = Some((timestamp, net));
+
+        let ocv_soc = if self.is_rested() {
+            soc_from_ocv(self.config.ocv_table, adc.vbat.0)
+        } else {
+            None
+        };
+        if let Some(soc) = ocv_soc {
+            self.set_soc(soc);
+        }
+    }
+
+    /// State of charge, rounded to the nearest percent.
+    pub fn soc_percent(&self) -> u8 {
+        let full = self.full_charge();
+        if full == 0 {
+            return 0;
+        }
+        ((self.charge * 100 + full / 2) / full) as u8
+    }
+
+    /// Remaining charge, rounded down.
+    pub fn remaining_capacity_mah(&self) -> u32 {
+        (self.charge / MA_US_PER_MAH) as u32
+    }
+
+    /// Net battery current of the last update in mA, positive while charging.
+    pub fn net_current_ma(&self) -> Option<i32> {
+        self.last.map(|(_, net)| net)
+    }
+
+    /// Returns `true` once the battery has rested for the configured `rest_time`.
+    pub fn is_rested(&self) -> bool {
+        self.rested >= self.config.rest_time
+    }
+
+    /// Time until empty at the last net current, if discharging.
+    pub fn time_to_empty(&self) -> Option<Duration> {
+        match self.net_current_ma()? {
+            net if net < 0 => Some(Self::time_for(self.charge, net.unsigned_abs())),
+            _ => None,
+        }
+    }
+
+    /// Time until full at the last net current, if charging.
+    pub fn time_to_full(&self) -> Option<Duration> {
+        match self.net_current_ma()? {
+            net if net > 0 => Some(Self::time_for(
+                self.full_charge() - self.charge,
+                net.unsigned_abs(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn time_for(charge: i64, current_ma: u32) -> Duration {
+        Duration::from_micros(charge as u64 / u64::from(current_ma))
+    }
+
+    fn full_charge(&self) -> i64 {
+        i64::from(self.config.capacity_mah) * MA_US_PER_MAH
+    }
+}
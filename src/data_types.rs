@@ -1,5 +1,9 @@
 #![allow(clippy::approx_constant)]
 
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use bitflags::bitflags;
 #[cfg(feature = "defmt")]
 use defmt::Format;
 
@@ -7,36 +11,37 @@ use crate::registers::{
     AdcOptionFlags, AdcOptionMsbFlags, ChargeOption0Flags, ChargeOption0MsbFlags,
     ChargeOption1Flags, ChargeOption1MsbFlags, ChargeOption2Flags, ChargeOption2MsbFlags,
     ChargeOption3Flags, ChargeOption3MsbFlags, ChargeOption4Flags, ChargeOption4MsbFlags,
-    ChargerStatusFaultFlags, ChargerStatusFlags, ProchotOption0Flags, ProchotOption0MsbFlags,
-    ProchotOption1Flags, ProchotOption1MsbFlags, ProchotStatusFlags, ProchotStatusMsbFlags,
-    VminActiveProtectionFlags, VminActiveProtectionMsbFlags,
+    ChargerStatusFaultFlags, ChargerStatusFlags, DitherSetting, ProchotOption0Flags,
+    ProchotOption0MsbFlags, ProchotOption1Flags, ProchotOption1MsbFlags, ProchotStatusFlags,
+    ProchotStatusMsbFlags, PsysRatio, Register, VminActiveProtectionFlags,
+    VminActiveProtectionMsbFlags,
 };
+use crate::units::{MilliAmps, MilliVolts, MilliWatts};
 #[cfg(feature = "binrw")]
 use binrw::{BinRead, BinWrite};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Enum to represent the sense resistor value.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(repr = u8))] // Assuming u8 representation for the enum
 #[cfg_attr(feature = "binrw", bw(repr = u8))] // Assuming u8 representation for the enum
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SenseResistorValue {
-    R5mOhm,  // 5mΩ sense resistor
+    #[default]
+    R5mOhm, // 5mΩ sense resistor
     R10mOhm, // 10mΩ sense resistor
 }
 
-impl Default for SenseResistorValue {
-    fn default() -> Self {
-        SenseResistorValue::R5mOhm // Default to 5mOhm
-    }
-}
-
 /// Represents the status of the BQ25730 charger.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ChargerStatus::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargerStatus {
     pub status_flags: ChargerStatusFlags,
     pub fault_flags: ChargerStatusFaultFlags,
@@ -63,9 +68,18 @@ impl defmt::Format for ChargerStatus {
     }
 }
 
+impl fmt::Display for ChargerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ChargerStatus {{ status_flags: {}, fault_flags: {} }}",
+            self.status_flags, self.fault_flags
+        )
+    }
+}
+
 impl ChargerStatus {
-    #[cfg(feature = "binrw")]
-    fn from_u16(value: u16) -> Self {
+    pub fn from_u16(value: u16) -> Self {
         Self {
             status_flags: ChargerStatusFlags::from_bits_truncate((value >> 8) as u8),
             fault_flags: ChargerStatusFaultFlags::from_bits_truncate(value as u8),
@@ -84,6 +98,19 @@ impl ChargerStatus {
     }
 }
 
+/// Writes set flag names as `NAME | NAME`, or `(empty)`, matching the `defmt` output.
+fn write_flag_names<F: bitflags::Flags>(f: &mut fmt::Formatter<'_>, flags: &F) -> fmt::Result {
+    if flags.is_empty() {
+        return f.write_str("(empty)");
+    }
+    let mut sep = "";
+    for (name, _) in flags.iter_names() {
+        write!(f, "{sep}{name}")?;
+        sep = " | ";
+    }
+    Ok(())
+}
+
 #[cfg(feature = "defmt")]
 impl defmt::Format for ChargerStatusFlags {
     fn format(&self, fmt: defmt::Formatter) {
@@ -151,6 +178,12 @@ impl defmt::Format for ChargerStatusFlags {
     }
 }
 
+impl fmt::Display for ChargerStatusFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flag_names(f, self)
+    }
+}
+
 #[cfg(feature = "defmt")]
 impl defmt::Format for ChargerStatusFaultFlags {
     fn format(&self, fmt: defmt::Formatter) {
@@ -218,6 +251,12 @@ impl defmt::Format for ChargerStatusFaultFlags {
     }
 }
 
+impl fmt::Display for ChargerStatusFaultFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flag_names(f, self)
+    }
+}
+
 #[cfg(feature = "defmt")]
 impl defmt::Format for ProchotStatusMsbFlags {
     fn format(&self, fmt: defmt::Formatter) {
@@ -271,6 +310,35 @@ impl defmt::Format for ProchotStatusMsbFlags {
     }
 }
 
+impl fmt::Display for ProchotStatusMsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("(empty)");
+        }
+        let mut sep = "";
+        if self.contains(ProchotStatusMsbFlags::EN_PROCHOT_EXT) {
+            f.write_str("EN_PROCHOT_EXT")?;
+            sep = " | ";
+        }
+        let width_val = (self.bits() & ProchotStatusMsbFlags::PROCHOT_WIDTH.bits()) >> 4;
+        if width_val != 0 {
+            write!(f, "{sep}PROCHOT_WIDTH_VAL={width_val}")?;
+            sep = " | ";
+        }
+        for (flag, name) in [
+            (ProchotStatusMsbFlags::PROCHOT_CLEAR, "PROCHOT_CLEAR"),
+            (ProchotStatusMsbFlags::STAT_VAP_FAIL, "STAT_VAP_FAIL"),
+            (ProchotStatusMsbFlags::STAT_EXIT_VAP, "STAT_EXIT_VAP"),
+        ] {
+            if self.contains(flag) {
+                write!(f, "{sep}{name}")?;
+                sep = " | ";
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(feature = "defmt")]
 impl defmt::Format for ProchotStatusFlags {
     fn format(&self, fmt: defmt::Formatter) {
@@ -338,11 +406,18 @@ impl defmt::Format for ProchotStatusFlags {
     }
 }
 
+impl fmt::Display for ProchotStatusFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flag_names(f, self)
+    }
+}
+
 /// Represents the PROCHOT status of the BQ25730.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ProchotStatus::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProchotStatus {
     pub msb_flags: ProchotStatusMsbFlags,
     pub lsb_flags: ProchotStatusFlags,
@@ -360,8 +435,7 @@ impl Default for ProchotStatus {
 }
 
 impl ProchotStatus {
-    #[cfg(feature = "binrw")]
-    fn from_u16(value: u16) -> Self {
+    pub fn from_u16(value: u16) -> Self {
         Self {
             // Mask out bits 13:12 (prochot_width) from the MSB when extracting msb_flags
             msb_flags: ProchotStatusMsbFlags::from_bits_truncate(((value >> 8) & !0x30) as u8),
@@ -399,36 +473,48 @@ impl defmt::Format for ProchotStatus {
     }
 }
 
+impl fmt::Display for ProchotStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ProchotStatus {{ msb_flags: {:b}, lsb_flags: {:b}, prochot_width: {} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits(),
+            self.prochot_width
+        )
+    }
+}
+
 /// Represents the Charge Current setting.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(little))] // Assuming LSB first for raw u16
 #[cfg_attr(feature = "binrw", bw(little))] // Assuming LSB first for raw u16
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargeCurrentSetting {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
     pub rsns_bat: SenseResistorValue,
 }
 
-impl Default for ChargeCurrentSetting {
-    fn default() -> Self {
-        // Datasheet reset for REG0x03/02h is 0000h, which corresponds to 0mA.
-        Self {
-            milliamps: 0,
-            rsns_bat: SenseResistorValue::default(),
-        }
+impl ChargeCurrentSetting {
+    /// Currents the 7-bit register field can hold without clamping.
+    pub fn valid_range(rsns_bat: SenseResistorValue) -> RangeInclusive<MilliAmps> {
+        let lsb_ma = match rsns_bat {
+            SenseResistorValue::R5mOhm => 128,
+            SenseResistorValue::R10mOhm => 64,
+        };
+        MilliAmps(0)..=MilliAmps(0x7F * lsb_ma)
     }
-}
 
-impl ChargeCurrentSetting {
-    pub fn from_milliamps(milliamps: u16, rsns_bat: SenseResistorValue) -> Self {
+    pub fn from_milliamps(milliamps: MilliAmps, rsns_bat: SenseResistorValue) -> Self {
         Self {
             milliamps,
             rsns_bat,
         }
     }
 
-    pub fn to_milliamps(&self) -> u16 {
+    pub fn to_milliamps(&self) -> MilliAmps {
         self.milliamps
     }
 
@@ -446,7 +532,7 @@ impl ChargeCurrentSetting {
             SenseResistorValue::R10mOhm => 64,
         };
         Self {
-            milliamps: (raw_7bit as u16) * lsb_ma_val,
+            milliamps: MilliAmps((raw_7bit as u16) * lsb_ma_val),
             rsns_bat,
         }
     }
@@ -458,8 +544,9 @@ impl ChargeCurrentSetting {
             SenseResistorValue::R5mOhm => 128,
             SenseResistorValue::R10mOhm => 64,
         };
-        let mut raw_7bit_val = self.milliamps / lsb_ma_val;
+        let mut raw_7bit_val = self.milliamps.0 / lsb_ma_val;
         if raw_7bit_val > 0x7F {
+            warn!("Charge current {} out of range, clamped", self.milliamps);
             raw_7bit_val = 0x7F; // Clamp to max 7-bit value
         }
         let raw_7bit = raw_7bit_val as u8;
@@ -488,24 +575,30 @@ impl ChargeCurrentSetting {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = |x: u16| ChargeVoltageSetting::from_raw(x, None) ))]
 #[cfg_attr(feature = "binrw", bw(map = |s: &ChargeVoltageSetting| s.to_raw() ))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargeVoltageSetting {
-    pub millivolts: u16,
+    pub millivolts: MilliVolts,
 }
 
 impl Default for ChargeVoltageSetting {
     fn default() -> Self {
         // Default for 4S battery: 16.8V (raw 0x41A0)
         // This will be overridden by Config::new based on cell_count
-        Self::from_millivolts(16800)
+        Self::from_millivolts(MilliVolts(16800))
     }
 }
 
 impl ChargeVoltageSetting {
-    pub fn from_millivolts(millivolts: u16) -> Self {
+    /// Charge voltages the chip regulates to (VBAT_RNG, 1.024 V to 23 V).
+    pub fn valid_range() -> RangeInclusive<MilliVolts> {
+        MilliVolts(1024)..=MilliVolts(23000)
+    }
+
+    pub fn from_millivolts(millivolts: MilliVolts) -> Self {
         Self { millivolts }
     }
 
-    pub fn to_millivolts(&self) -> u16 {
+    pub fn to_millivolts(&self) -> MilliVolts {
         self.millivolts
     }
 
@@ -521,16 +614,17 @@ impl ChargeVoltageSetting {
 
         let combined_12bit = ((d11_d5 as u16) << 5) | (d4_d0 as u16);
         Self {
-            millivolts: combined_12bit * 8,
+            millivolts: MilliVolts(combined_12bit * 8),
         }
     }
 
     /// Converts the ChargeVoltageSetting to a raw 16-bit register value (LSB first).
     /// LSB (04h): D4-D0 in bits 7:3. MSB (05h): D11-D5 in bits 6:0.
     pub fn to_raw(&self) -> u16 {
-        let mut combined_12bit = self.millivolts / 8;
+        let mut combined_12bit = self.millivolts.0 / 8;
         if combined_12bit > 0xFFF {
             // Clamp to max 12-bit value
+            warn!("Charge voltage {} out of range, clamped", self.millivolts);
             combined_12bit = 0xFFF;
         }
 
@@ -558,8 +652,9 @@ impl ChargeVoltageSetting {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = |x: u16| OtgVoltageSetting::from_raw(x) ))]
 #[cfg_attr(feature = "binrw", bw(map = |s: &OtgVoltageSetting| s.to_raw() ))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OtgVoltageSetting {
-    pub millivolts: u16,
+    pub millivolts: MilliVolts,
 }
 
 impl Default for OtgVoltageSetting {
@@ -574,11 +669,16 @@ impl OtgVoltageSetting {
     /// LSB value for OTG Voltage in mV.
     pub const LSB_MV: u16 = 8;
 
-    pub fn from_millivolts(millivolts: u16) -> Self {
+    /// Voltages the 12-bit register field can hold without clamping.
+    pub fn valid_range() -> RangeInclusive<MilliVolts> {
+        MilliVolts(0)..=MilliVolts(0xFFF * Self::LSB_MV)
+    }
+
+    pub fn from_millivolts(millivolts: MilliVolts) -> Self {
         Self { millivolts }
     }
 
-    pub fn to_millivolts(&self) -> u16 {
+    pub fn to_millivolts(&self) -> MilliVolts {
         self.millivolts
     }
 
@@ -600,7 +700,7 @@ impl OtgVoltageSetting {
         // Combine D11..D6, D5, D4..D0 to form the 12-bit value
         let combined_12bit = (d11_d6 << 6) | (d5 << 5) | d4_d0;
         Self {
-            millivolts: combined_12bit * Self::LSB_MV,
+            millivolts: MilliVolts(combined_12bit * Self::LSB_MV),
         }
     }
 
@@ -609,9 +709,10 @@ impl OtgVoltageSetting {
     /// MSB (REG0x07): D11-D6 in bits 5:0. Bit 7 is reserved, Bit 6 is unused by D11-D6.
     /// LSB (REG0x06): D5 in bit 7, D4-D0 in bits 6:2. Bits 1:0 are reserved.
     pub fn to_raw(&self) -> u16 {
-        let mut raw_12bit = self.millivolts / Self::LSB_MV;
+        let mut raw_12bit = self.millivolts.0 / Self::LSB_MV;
         if raw_12bit > 0xFFF {
             // Clamp to max 12-bit value (4095)
+            warn!("OTG voltage {} out of range, clamped", self.millivolts);
             raw_12bit = 0xFFF;
         }
 
@@ -648,8 +749,9 @@ impl OtgVoltageSetting {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = |x: u16| OtgCurrentSetting::from_raw((x >> 8) as u8, SenseResistorValue::default()) ))] // Reads MSB for raw value
 #[cfg_attr(feature = "binrw", bw(map = |s: &OtgCurrentSetting| (s.to_raw() as u16) << 8 ))] // Writes raw value to MSB
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OtgCurrentSetting {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
     pub rsns_bat: SenseResistorValue,
 }
 
@@ -662,14 +764,23 @@ impl Default for OtgCurrentSetting {
 }
 
 impl OtgCurrentSetting {
-    pub fn from_milliamps(milliamps: u16, rsns_bat: SenseResistorValue) -> Self {
+    /// Currents the 7-bit register field can hold without clamping.
+    pub fn valid_range(rsns_bat: SenseResistorValue) -> RangeInclusive<MilliAmps> {
+        let lsb_ma = match rsns_bat {
+            SenseResistorValue::R5mOhm => 100,
+            SenseResistorValue::R10mOhm => 50,
+        };
+        MilliAmps(0)..=MilliAmps(0x7F * lsb_ma)
+    }
+
+    pub fn from_milliamps(milliamps: MilliAmps, rsns_bat: SenseResistorValue) -> Self {
         Self {
             milliamps,
             rsns_bat,
         }
     }
 
-    pub fn to_milliamps(&self) -> u16 {
+    pub fn to_milliamps(&self) -> MilliAmps {
         self.milliamps
     }
 
@@ -681,7 +792,7 @@ impl OtgCurrentSetting {
         };
         // Raw value is 7-bit (0-127)
         Self {
-            milliamps: (raw_7bit as u16) * lsb_ma,
+            milliamps: MilliAmps((raw_7bit as u16) * lsb_ma),
             rsns_bat,
         }
     }
@@ -693,8 +804,9 @@ impl OtgCurrentSetting {
             SenseResistorValue::R10mOhm => 50,
         };
         // Ensure the result fits in 7 bits (0-127)
-        let raw_value = self.milliamps / lsb_ma;
+        let raw_value = self.milliamps.0 / lsb_ma;
         if raw_value > 0x7F {
+            warn!("OTG current {} out of range, clamped", self.milliamps);
             0x7F // Clamp to max 7-bit value
         } else {
             raw_value as u8
@@ -716,8 +828,9 @@ impl OtgCurrentSetting {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = |x: u16| InputVoltageSetting::from_raw(x) ))]
 #[cfg_attr(feature = "binrw", bw(map = |s: &InputVoltageSetting| s.to_raw() ))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InputVoltageSetting {
-    pub millivolts: u16,
+    pub millivolts: MilliVolts,
 }
 
 impl Default for InputVoltageSetting {
@@ -735,11 +848,16 @@ impl InputVoltageSetting {
     /// Offset value for Input Voltage in mV.
     pub const OFFSET_MV: u16 = 3200; // 3200mV offset
 
-    pub fn from_millivolts(millivolts: u16) -> Self {
+    /// Voltages the 9-bit register field can hold without clamping.
+    pub fn valid_range() -> RangeInclusive<MilliVolts> {
+        MilliVolts(Self::OFFSET_MV)..=MilliVolts(Self::OFFSET_MV + 0x1FF * Self::LSB_MV)
+    }
+
+    pub fn from_millivolts(millivolts: MilliVolts) -> Self {
         Self { millivolts }
     }
 
-    pub fn to_millivolts(&self) -> u16 {
+    pub fn to_millivolts(&self) -> MilliVolts {
         self.millivolts
     }
 
@@ -758,7 +876,7 @@ impl InputVoltageSetting {
 
         let combined_9bit = ((d8 as u16) << 8) | (d7_d0 as u16);
         Self {
-            millivolts: combined_9bit * Self::LSB_MV + Self::OFFSET_MV,
+            millivolts: MilliVolts(combined_9bit * Self::LSB_MV + Self::OFFSET_MV),
         }
     }
 
@@ -767,14 +885,16 @@ impl InputVoltageSetting {
     /// MSB (0x0B): D8 in bit 5
     /// LSB (0x0A): D7-D0 in bits 7:0
     pub fn to_raw(&self) -> u16 {
-        let mut raw_9bit = if self.millivolts >= Self::OFFSET_MV {
-            (self.millivolts - Self::OFFSET_MV) / Self::LSB_MV
+        let mut raw_9bit = if self.millivolts.0 >= Self::OFFSET_MV {
+            (self.millivolts.0 - Self::OFFSET_MV) / Self::LSB_MV
         } else {
+            warn!("Input voltage {} below range, clamped", self.millivolts);
             0 // Clamp to the minimum register value
         };
 
         if raw_9bit > 0x1FF {
             // Clamp to max 9-bit value
+            warn!("Input voltage {} out of range, clamped", self.millivolts);
             raw_9bit = 0x1FF;
         }
 
@@ -804,8 +924,9 @@ impl InputVoltageSetting {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = |x: u16| VsysMinSetting::from_raw(x) ))]
 #[cfg_attr(feature = "binrw", bw(map = |s: &VsysMinSetting| s.to_raw() ))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VsysMinSetting {
-    pub millivolts: u16,
+    pub millivolts: MilliVolts,
 }
 
 impl Default for VsysMinSetting {
@@ -821,11 +942,16 @@ impl VsysMinSetting {
     /// LSB value for Minimum System Voltage in mV.
     pub const LSB_MV: u16 = 100;
 
-    pub fn from_millivolts(millivolts: u16) -> Self {
+    /// Voltages the 8-bit register field can hold without clamping.
+    pub fn valid_range() -> RangeInclusive<MilliVolts> {
+        MilliVolts(0)..=MilliVolts(0xFF * Self::LSB_MV)
+    }
+
+    pub fn from_millivolts(millivolts: MilliVolts) -> Self {
         Self { millivolts }
     }
 
-    pub fn to_millivolts(&self) -> u16 {
+    pub fn to_millivolts(&self) -> MilliVolts {
         self.millivolts
     }
 
@@ -834,19 +960,20 @@ impl VsysMinSetting {
     pub fn from_raw(raw_value: u16) -> Self {
         let msb_byte = ((raw_value >> 8) & 0xFF) as u8; // Extract MSB
         Self {
-            millivolts: (msb_byte as u16) * Self::LSB_MV,
+            millivolts: MilliVolts((msb_byte as u16) * Self::LSB_MV),
         }
     }
 
     /// Converts the VsysMinSetting to a raw 16-bit register value (LSB first).
     /// MSB (0x0D) contains the 8-bit value. LSB (0x0C) is 0x00.
     pub fn to_raw(&self) -> u16 {
-        let mut msb_val = self.millivolts / Self::LSB_MV;
+        let mut msb_val = self.millivolts.0 / Self::LSB_MV;
         if msb_val > 0xFF {
             // Clamp to max 8-bit value
+            warn!("VSYS_MIN {} out of range, clamped", self.millivolts);
             msb_val = 0xFF;
         }
-        (msb_val as u16) << 8 // LSB is 0x00
+        msb_val << 8 // LSB is 0x00
     }
 
     /// Converts to MSB and LSB bytes for register writing.
@@ -860,27 +987,36 @@ impl VsysMinSetting {
 
 /// Represents the Input Current Limit Set by Host (IIN_HOST) setting.
 /// REG0x0F/0Eh: MSB (0Fh) D6-D0, LSB (0Eh) is reserved (0x00).
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
-#[cfg_attr(feature = "binrw", br(map = |x: u16| IinHostSetting::from_milliamps(x) ))]
-#[cfg_attr(feature = "binrw", bw(map = |s: &IinHostSetting| s.to_milliamps() ))]
+#[cfg_attr(feature = "binrw", br(map = |x: u16| IinHostSetting::from_milliamps(MilliAmps(x)) ))]
+#[cfg_attr(feature = "binrw", bw(map = |s: &IinHostSetting| s.to_milliamps().0 ))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IinHostSetting {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
 }
 
-impl Default for IinHostSetting {
-    fn default() -> Self {
-        Self { milliamps: 0 } // Default to 0mA, specific value set in Config::new
-    }
+/// IIN_HOST and IIN_DPM share the same 7-bit encoding with an offset of one LSB.
+fn input_current_range(rsns_ac: SenseResistorValue) -> RangeInclusive<MilliAmps> {
+    let lsb_ma = match rsns_ac {
+        SenseResistorValue::R5mOhm => 100,
+        SenseResistorValue::R10mOhm => 50,
+    };
+    MilliAmps(lsb_ma)..=MilliAmps(lsb_ma + 0x7F * lsb_ma)
 }
 
 impl IinHostSetting {
-    pub fn from_milliamps(milliamps: u16) -> Self {
+    /// Currents the 7-bit register field can hold without clamping.
+    pub fn valid_range(rsns_ac: SenseResistorValue) -> RangeInclusive<MilliAmps> {
+        input_current_range(rsns_ac)
+    }
+
+    pub fn from_milliamps(milliamps: MilliAmps) -> Self {
         Self { milliamps }
     }
 
-    pub fn to_milliamps(&self) -> u16 {
+    pub fn to_milliamps(&self) -> MilliAmps {
         self.milliamps
     }
 
@@ -892,7 +1028,7 @@ impl IinHostSetting {
             SenseResistorValue::R5mOhm => (100, 100), // LSB 100mA, Offset 100mA
             SenseResistorValue::R10mOhm => (50, 50),  // LSB 50mA, Offset 50mA
         };
-        let milliamps = (raw_7bit as u16) * lsb_ma + offset_ma;
+        let milliamps = MilliAmps((raw_7bit as u16) * lsb_ma + offset_ma);
         Self { milliamps }
     }
 
@@ -904,14 +1040,15 @@ impl IinHostSetting {
             SenseResistorValue::R10mOhm => (50, 50),
         };
         let mut raw_7bit_val = 0;
-        if self.milliamps >= offset_ma {
-            raw_7bit_val = (self.milliamps - offset_ma) / lsb_ma;
+        if self.milliamps.0 >= offset_ma {
+            raw_7bit_val = (self.milliamps.0 - offset_ma) / lsb_ma;
         }
         if raw_7bit_val > 0x7F {
             // Clamp to max 7-bit value (127)
+            warn!("IIN_HOST {} out of range, clamped", self.milliamps);
             raw_7bit_val = 0x7F;
         }
-        (raw_7bit_val as u16) << 8 // Place 7-bit code in MSB, LSB is 0x00
+        raw_7bit_val << 8 // Place 7-bit code in MSB, LSB is 0x00
     }
 
     /// Converts to MSB and LSB bytes for register writing, using RSNS_AC.
@@ -929,8 +1066,9 @@ impl IinHostSetting {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(little))]
 #[cfg_attr(feature = "binrw", bw(little))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IinHost {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
     pub rsns_ac: SenseResistorValue,
 }
 
@@ -943,7 +1081,7 @@ impl Default for IinHost {
             SenseResistorValue::R10mOhm => (50, 50),
         };
         Self {
-            milliamps: offset_ma,
+            milliamps: MilliAmps(offset_ma),
             rsns_ac,
         }
     }
@@ -958,7 +1096,7 @@ impl IinHost {
         };
         // Raw value is 7-bit (0-127)
         Self {
-            milliamps: (raw_7bit as u16) * lsb_ma + offset_ma,
+            milliamps: MilliAmps((raw_7bit as u16) * lsb_ma + offset_ma),
             rsns_ac,
         }
     }
@@ -969,8 +1107,8 @@ impl IinHost {
             SenseResistorValue::R5mOhm => (100, 100),
             SenseResistorValue::R10mOhm => (50, 50),
         };
-        let raw_value = if self.milliamps >= offset_ma {
-            (self.milliamps - offset_ma) / lsb_ma
+        let raw_value = if self.milliamps.0 >= offset_ma {
+            (self.milliamps.0 - offset_ma) / lsb_ma
         } else {
             0 // Clamp to 0 if milliamps is less than offset
         };
@@ -989,8 +1127,9 @@ impl IinHost {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(little))]
 #[cfg_attr(feature = "binrw", bw(little))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IinDpm {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
     pub rsns_ac: SenseResistorValue,
 }
 
@@ -1002,13 +1141,18 @@ impl Default for IinDpm {
             SenseResistorValue::R10mOhm => (50, 50),
         };
         Self {
-            milliamps: offset_ma,
+            milliamps: MilliAmps(offset_ma),
             rsns_ac,
         }
     }
 }
 
 impl IinDpm {
+    /// Currents the 7-bit register field can hold without clamping.
+    pub fn valid_range(rsns_ac: SenseResistorValue) -> RangeInclusive<MilliAmps> {
+        input_current_range(rsns_ac)
+    }
+
     /// Creates a new IinDpm from a raw 7-bit register value and RSNS setting.
     pub fn from_raw(raw_7bit: u8, rsns_ac: SenseResistorValue) -> Self {
         let (lsb_ma, offset_ma) = match rsns_ac {
@@ -1017,7 +1161,7 @@ impl IinDpm {
         };
         // Raw value is 7-bit (0-127)
         Self {
-            milliamps: (raw_7bit as u16) * lsb_ma + offset_ma,
+            milliamps: MilliAmps((raw_7bit as u16) * lsb_ma + offset_ma),
             rsns_ac,
         }
     }
@@ -1028,8 +1172,8 @@ impl IinDpm {
             SenseResistorValue::R5mOhm => (100, 100),
             SenseResistorValue::R10mOhm => (50, 50),
         };
-        let raw_value = if self.milliamps >= offset_ma {
-            (self.milliamps - offset_ma) / lsb_ma
+        let raw_value = if self.milliamps.0 >= offset_ma {
+            (self.milliamps.0 - offset_ma) / lsb_ma
         } else {
             0 // Clamp to 0 if milliamps is less than offset
         };
@@ -1042,11 +1186,15 @@ impl IinDpm {
     }
 }
 
+/// Offset added to the VBAT and VSYS ADC codes.
+pub(crate) const ADC_OFFSET_MV: u16 = 2880;
+
 /// Represents the ADC measurements.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(little))] // Specify Little Endian for reading
 #[cfg_attr(feature = "binrw", bw(little))] // Specify Little Endian for writing
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AdcMeasurements {
     pub vbat: AdcVbat,
     pub vsys: AdcVsys,
@@ -1058,17 +1206,28 @@ pub struct AdcMeasurements {
     pub cmpin: AdcCmpin,
 }
 
-impl Default for AdcMeasurements {
-    fn default() -> Self {
+impl AdcMeasurements {
+    /// Decodes the ADC result block (ADCPSYS 0x26 through ADCVSYS 0x2D) as
+    /// returned by a single 8-byte read.
+    pub fn from_adc_block(
+        raw: &[u8; 8],
+        rsns_bat: SenseResistorValue,
+        rsns_ac: SenseResistorValue,
+        offset_mv: u16,
+    ) -> Self {
         Self {
-            vbat: AdcVbat::default(),
-            vsys: AdcVsys::default(),
-            ichg: AdcIchg::default(),
-            idchg: AdcIdchg::default(),
-            iin: AdcIin::default(),
-            psys: AdcPsys::default(),
-            vbus: AdcVbus::default(),
-            cmpin: AdcCmpin::default(),
+            vbat: AdcVbat::from_register_value(
+                raw[6], // LSB for ADCVBAT is 0x2C
+                raw[7], // MSB for ADCVBAT is 0x2D
+                offset_mv,
+            ),
+            psys: AdcPsys::from_u8(raw[0]), // ADCPSYS at 0x26
+            vbus: AdcVbus::from_u8(raw[1]), // ADCVBUS at 0x27
+            idchg: AdcIdchg::from_raw(raw[2], rsns_bat), // ADCIDCHG at 0x28
+            ichg: AdcIchg::from_raw(raw[3], rsns_bat), // ADCICHG at 0x29
+            cmpin: AdcCmpin::from_u8(raw[4]), // ADCCMPIN at 0x2A
+            iin: AdcIin::from_raw(raw[5], rsns_ac), // ADCIIN at 0x2B
+            vsys: AdcVsys::from_register_value(0, raw[7], offset_mv), // ADCVSYS at 0x2D
         }
     }
 }
@@ -1091,19 +1250,31 @@ impl defmt::Format for AdcMeasurements {
     }
 }
 
+impl fmt::Display for AdcMeasurements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AdcMeasurements {{ vbat: {}, vsys: {}, ichg: {}, idchg: {}, iin: {}, psys: {}, vbus: {}, cmpin: {} }}",
+            self.vbat.0,
+            self.vsys.0,
+            self.ichg.milliamps,
+            self.idchg.milliamps,
+            self.iin.milliamps,
+            self.psys.0,
+            self.vbus.0,
+            self.cmpin.0
+        )
+    }
+}
+
 /// Represents the ADCCMPIN register value.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = AdcCmpin::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
-pub struct AdcCmpin(pub u16);
-
-impl Default for AdcCmpin {
-    fn default() -> Self {
-        Self(0)
-    }
-}
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdcCmpin(pub MilliVolts);
 
 impl AdcCmpin {
     /// LSB value for ADCCMPIN in mV (with ADC_FULLSCALE=1b).
@@ -1112,70 +1283,64 @@ impl AdcCmpin {
     /// Creates a new AdcCmpin from a 16-bit raw register value.
     pub fn from_u16(value: u16) -> Self {
         // Extract the 8-bit raw value from the MSB before scaling
-        AdcCmpin((value >> 8) * Self::LSB_MV)
+        AdcCmpin(MilliVolts((value >> 8) * Self::LSB_MV))
     }
 
     /// Creates a new AdcCmpin from an 8-bit raw register value.
     /// Converts the 8-bit raw value to a scaled value based on LSB.
     pub fn from_u8(raw_value: u8) -> Self {
         // ADCCMPIN is an 8-bit value (0-255)
-        AdcCmpin((raw_value as u16) * Self::LSB_MV) // Scale by 12mV LSB
+        AdcCmpin(MilliVolts((raw_value as u16) * Self::LSB_MV)) // Scale by 12mV LSB
     }
 
     /// Converts the AdcCmpin to a 16-bit raw register value.
     pub fn to_u16(&self) -> u16 {
-        (self.0 / Self::LSB_MV) << 8 // Convert mV back to raw 8-bit value in MSB
+        (self.0.0 / Self::LSB_MV) << 8 // Convert mV back to raw 8-bit value in MSB
     }
 
     /// Converts the AdcCmpin to raw MSB and LSB register values.
     /// Since ADCCMPIN is an 8-bit register, LSB will be 0.
     pub fn to_msb_lsb_bytes(&self) -> (u8, u8) {
-        (0x00, (self.0 / Self::LSB_MV) as u8) // Convert mV back to raw 8-bit value
+        (0x00, (self.0.0 / Self::LSB_MV) as u8) // Convert mV back to raw 8-bit value
     }
 }
 
 /// Represents the ADCICHG register value in mA.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(little))]
 #[cfg_attr(feature = "binrw", bw(little))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AdcIchg {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
     pub rsns_bat: SenseResistorValue,
 }
 
-impl Default for AdcIchg {
-    fn default() -> Self {
-        Self {
-            milliamps: 0,
-            rsns_bat: SenseResistorValue::default(),
+impl AdcIchg {
+    /// Current of one ADC step: 128 mA at 5 mOhm, 64 mA at 10 mOhm.
+    pub const fn lsb(rsns_bat: SenseResistorValue) -> MilliAmps {
+        match rsns_bat {
+            SenseResistorValue::R5mOhm => MilliAmps(128),
+            SenseResistorValue::R10mOhm => MilliAmps(64),
         }
     }
-}
 
-impl AdcIchg {
     /// Creates a new AdcIchg from a raw 7-bit ADC value and RSNS setting.
     pub fn from_raw(raw_7bit_adc: u8, rsns_bat: SenseResistorValue) -> Self {
-        let lsb_ma = match rsns_bat {
-            SenseResistorValue::R5mOhm => 128, // 128mA/LSB for 5mΩ
-            SenseResistorValue::R10mOhm => 64, // 64mA/LSB for 10mΩ
-        };
+        let lsb_ma = Self::lsb(rsns_bat).0;
         // ADCICHG is a 7-bit value (0-127)
         Self {
-            milliamps: (raw_7bit_adc as u16) * lsb_ma,
+            milliamps: MilliAmps((raw_7bit_adc as u16) * lsb_ma),
             rsns_bat,
         }
     }
 
     /// Converts the AdcIchg to a raw 7-bit ADC value.
     pub fn to_raw(&self) -> u8 {
-        let lsb_ma = match self.rsns_bat {
-            SenseResistorValue::R5mOhm => 128,
-            SenseResistorValue::R10mOhm => 64,
-        };
+        let lsb_ma = Self::lsb(self.rsns_bat).0;
         // Ensure the result fits in 7 bits (0-127)
-        let raw_value = self.milliamps / lsb_ma;
+        let raw_value = self.milliamps.0 / lsb_ma;
         if raw_value > 0x7F {
             0x7F // Clamp to max 7-bit value
         } else {
@@ -1185,47 +1350,41 @@ impl AdcIchg {
 }
 
 /// Represents the ADCIDCHG register value in mA.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(little))]
 #[cfg_attr(feature = "binrw", bw(little))]
 #[cfg_attr(feature = "defmt", derive(Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AdcIdchg {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
     pub rsns_bat: SenseResistorValue,
 }
 
-impl Default for AdcIdchg {
-    fn default() -> Self {
-        Self {
-            milliamps: 0,
-            rsns_bat: SenseResistorValue::default(),
+impl AdcIdchg {
+    /// Current of one ADC step: 512 mA at 5 mOhm, 256 mA at 10 mOhm.
+    pub const fn lsb(rsns_bat: SenseResistorValue) -> MilliAmps {
+        match rsns_bat {
+            SenseResistorValue::R5mOhm => MilliAmps(512),
+            SenseResistorValue::R10mOhm => MilliAmps(256),
         }
     }
-}
 
-impl AdcIdchg {
     /// Creates a new AdcIdchg from a raw 7-bit ADC value and RSNS setting.
     pub fn from_raw(raw_7bit_adc: u8, rsns_bat: SenseResistorValue) -> Self {
-        let lsb_ma = match rsns_bat {
-            SenseResistorValue::R5mOhm => 512,  // 512mA/LSB for 5mΩ
-            SenseResistorValue::R10mOhm => 256, // 256mA/LSB for 10mΩ
-        };
+        let lsb_ma = Self::lsb(rsns_bat).0;
         // ADCIDCHG is a 7-bit value (0-127)
         Self {
-            milliamps: (raw_7bit_adc as u16) * lsb_ma,
+            milliamps: MilliAmps((raw_7bit_adc as u16) * lsb_ma),
             rsns_bat,
         }
     }
 
     /// Converts the AdcIdchg to a raw 7-bit ADC value.
     pub fn to_raw(&self) -> u8 {
-        let lsb_ma = match self.rsns_bat {
-            SenseResistorValue::R5mOhm => 512,
-            SenseResistorValue::R10mOhm => 256,
-        };
+        let lsb_ma = Self::lsb(self.rsns_bat).0;
         // Ensure the result fits in 7 bits (0-127)
-        let raw_value = self.milliamps / lsb_ma;
+        let raw_value = self.milliamps.0 / lsb_ma;
         if raw_value > 0x7F {
             0x7F // Clamp to max 7-bit value
         } else {
@@ -1235,25 +1394,17 @@ impl AdcIdchg {
 }
 
 /// Represents the ADCIIN register value in mA.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(little))]
 #[cfg_attr(feature = "binrw", bw(little))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AdcIin {
-    pub milliamps: u16,
+    pub milliamps: MilliAmps,
     pub rsns_ac: SenseResistorValue,
 }
 
-impl Default for AdcIin {
-    fn default() -> Self {
-        Self {
-            milliamps: 0,
-            rsns_ac: SenseResistorValue::default(),
-        }
-    }
-}
-
 impl AdcIin {
     /// Creates a new AdcIin from an 8-bit raw ADC value and RSNS setting.
     pub fn from_raw(raw_8bit_adc: u8, rsns_ac: SenseResistorValue) -> Self {
@@ -1263,7 +1414,7 @@ impl AdcIin {
         };
         // ADCIIN is an 8-bit value (0-255)
         Self {
-            milliamps: (raw_8bit_adc as u16) * lsb_ma,
+            milliamps: MilliAmps((raw_8bit_adc as u16) * lsb_ma),
             rsns_ac,
         }
     }
@@ -1275,7 +1426,7 @@ impl AdcIin {
             SenseResistorValue::R10mOhm => 50,
         };
         // Ensure the result fits in 8 bits (0-255)
-        let raw_value = self.milliamps / lsb_ma;
+        let raw_value = self.milliamps.0 / lsb_ma;
         if raw_value > 0xFF {
             0xFF // Clamp to max 8-bit value
         } else {
@@ -1285,18 +1436,13 @@ impl AdcIin {
 }
 
 /// Represents the ADCPSYS register value (related to system power).
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = AdcPsys::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
-pub struct AdcPsys(pub u16);
-
-impl Default for AdcPsys {
-    fn default() -> Self {
-        Self(0)
-    }
-}
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdcPsys(pub MilliVolts);
 
 impl AdcPsys {
     /// LSB value for ADCPSYS (assuming 12mV/LSB when ADC_FULLSCALE=1b).
@@ -1305,41 +1451,36 @@ impl AdcPsys {
 
     /// Creates a new AdcPsys from a 16-bit raw register value.
     pub fn from_u16(value: u16) -> Self {
-        AdcPsys(value) // Assuming value is already scaled
+        AdcPsys(MilliVolts(value)) // Assuming value is already scaled
     }
 
     /// Creates a new AdcPsys from an 8-bit raw register value.
     /// Converts the 8-bit raw value to a scaled value based on LSB.
     pub fn from_u8(raw_value: u8) -> Self {
         // ADCPSYS is an 8-bit value (0-255)
-        AdcPsys((raw_value as u16) * Self::LSB_MV) // Scale by 12mV LSB
+        AdcPsys(MilliVolts((raw_value as u16) * Self::LSB_MV)) // Scale by 12mV LSB
     }
 
     /// Converts the AdcPsys to a 16-bit raw register value.
     pub fn to_u16(&self) -> u16 {
-        self.0 // Assuming self.0 is already scaled
+        self.0.0 // Assuming self.0 is already scaled
     }
 
     /// Converts the AdcPsys to raw MSB and LSB register values.
     /// Since ADCPSYS is an 8-bit register, LSB will be 0.
     pub fn to_msb_lsb_bytes(&self) -> (u8, u8) {
-        (0x00, (self.0 / Self::LSB_MV) as u8) // Convert scaled value back to raw 8-bit value
+        (0x00, (self.0.0 / Self::LSB_MV) as u8) // Convert scaled value back to raw 8-bit value
     }
 }
 
 /// Represents the ADCVBUS register value in mV.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = AdcVbus::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
-pub struct AdcVbus(pub u16);
-
-impl Default for AdcVbus {
-    fn default() -> Self {
-        Self(0)
-    }
-}
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdcVbus(pub MilliVolts);
 
 impl AdcVbus {
     /// LSB value for ADCVBUS in mV.
@@ -1348,19 +1489,19 @@ impl AdcVbus {
     /// Creates a new AdcVbus from a 16-bit raw register value.
     pub fn from_u16(value: u16) -> Self {
         let raw_value = (value >> 8) as u8;
-        AdcVbus((raw_value as u16) * Self::LSB_MV)
+        AdcVbus(MilliVolts((raw_value as u16) * Self::LSB_MV))
     }
 
     /// Creates a new AdcVbus from an 8-bit raw register value.
     /// Converts the 8-bit raw value to mV based on LSB.
     pub fn from_u8(raw_value: u8) -> Self {
         // ADCVBUS is an 8-bit value (0-255)
-        AdcVbus((raw_value as u16) * Self::LSB_MV)
+        AdcVbus(MilliVolts((raw_value as u16) * Self::LSB_MV))
     }
 
     /// Converts the AdcVbus to a 16-bit raw register value.
     pub fn to_u16(&self) -> u16 {
-        let raw_value = self.0 / Self::LSB_MV;
+        let raw_value = self.0.0 / Self::LSB_MV;
         raw_value << 8
     }
 
@@ -1372,14 +1513,9 @@ impl AdcVbus {
 }
 
 /// Represents the ADCVBAT register value in mV.
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct AdcVbat(pub u16);
-
-impl Default for AdcVbat {
-    fn default() -> Self {
-        Self(0)
-    }
-}
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdcVbat(pub MilliVolts);
 
 impl AdcVbat {
     /// LSB value for ADCVBAT in mV.
@@ -1391,25 +1527,25 @@ impl AdcVbat {
     /// The 8-bit value is in the MSB (0x2D).
     pub fn from_u16(value: u16, offset_mv: u16) -> Self {
         let raw_value = (value >> 8) as u8;
-        AdcVbat((raw_value as u16) * Self::LSB_MV + offset_mv)
+        AdcVbat(MilliVolts((raw_value as u16) * Self::LSB_MV + offset_mv))
     }
 
     /// Creates a new AdcVbat from raw LSB and MSB register values.
     pub fn from_register_value(_lsb: u8, msb: u8, offset_mv: u16) -> Self {
-        AdcVbat((msb as u16) * Self::LSB_MV + offset_mv)
+        AdcVbat(MilliVolts((msb as u16) * Self::LSB_MV + offset_mv))
     }
 
     /// Converts the AdcVbat to a 16-bit raw register value.
     /// The 8-bit value is in the MSB (0x2D).
     pub fn to_u16(&self) -> u16 {
-        let raw_value = (self.0 - self.0 % Self::LSB_MV) / Self::LSB_MV; // Convert mV back to raw 8-bit value
+        let raw_value = (self.0.0 - self.0.0 % Self::LSB_MV) / Self::LSB_MV; // Convert mV back to raw 8-bit value
         raw_value << 8
     }
 
     /// Converts the AdcVbat to raw MSB and LSB register values.
     /// Since ADCVBAT is an 8-bit register, LSB will be 0.
     pub fn to_msb_lsb_bytes(&self) -> (u8, u8) {
-        (0x00, (self.0 / Self::LSB_MV) as u8) // Convert mV back to raw 8-bit value
+        (0x00, (self.0.0 / Self::LSB_MV) as u8) // Convert mV back to raw 8-bit value
     }
 }
 
@@ -1420,6 +1556,12 @@ impl defmt::Format for AdcVbat {
     }
 }
 
+impl fmt::Display for AdcVbat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AdcVbat({})", self.0)
+    }
+}
+
 #[cfg(feature = "binrw")]
 impl BinRead for AdcVbat {
     type Args<'a> = ();
@@ -1452,14 +1594,9 @@ impl BinWrite for AdcVbat {
 }
 
 /// Represents the ADCVSYS register value in mV.
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct AdcVsys(pub u16);
-
-impl Default for AdcVsys {
-    fn default() -> Self {
-        Self(0)
-    }
-}
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdcVsys(pub MilliVolts);
 
 impl AdcVsys {
     /// LSB value for ADCVSYS in mV.
@@ -1471,25 +1608,25 @@ impl AdcVsys {
     /// The 8-bit value is in the MSB (0x2D).
     pub fn from_u16(value: u16, offset_mv: u16) -> Self {
         let raw_value = (value >> 8) as u8;
-        AdcVsys((raw_value as u16) * Self::LSB_MV + offset_mv)
+        AdcVsys(MilliVolts((raw_value as u16) * Self::LSB_MV + offset_mv))
     }
 
     /// Creates a new AdcVsys from raw LSB and MSB register values.
     pub fn from_register_value(_lsb: u8, msb: u8, offset_mv: u16) -> Self {
-        AdcVsys((msb as u16) * Self::LSB_MV + offset_mv)
+        AdcVsys(MilliVolts((msb as u16) * Self::LSB_MV + offset_mv))
     }
 
     /// Converts the AdcVsys to a 16-bit raw register value.
     /// The 8-bit value is in the MSB (0x2D).
     pub fn to_u16(&self) -> u16 {
-        let raw_value = (self.0 - self.0 % Self::LSB_MV) / Self::LSB_MV; // Convert mV back to raw 8-bit value
+        let raw_value = (self.0.0 - self.0.0 % Self::LSB_MV) / Self::LSB_MV; // Convert mV back to raw 8-bit value
         raw_value << 8
     }
 
     /// Converts the AdcVsys to raw MSB and LSB register values.
     /// Since ADCVSYS is an 8-bit register, LSB will be 0.
     pub fn to_msb_lsb_bytes(&self) -> (u8, u8) {
-        (0x00, (self.0 / Self::LSB_MV) as u8) // Convert mV back to raw 8-bit value
+        (0x00, (self.0.0 / Self::LSB_MV) as u8) // Convert mV back to raw 8-bit value
     }
 }
 
@@ -1500,6 +1637,12 @@ impl defmt::Format for AdcVsys {
     }
 }
 
+impl fmt::Display for AdcVsys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AdcVsys({})", self.0)
+    }
+}
+
 #[cfg(feature = "binrw")]
 impl BinRead for AdcVsys {
     type Args<'a> = ();
@@ -1536,6 +1679,7 @@ impl BinWrite for AdcVsys {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = AdcOption::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AdcOption {
     pub msb_flags: AdcOptionMsbFlags,
     pub lsb_flags: AdcOptionFlags,
@@ -1553,6 +1697,17 @@ impl defmt::Format for AdcOption {
     }
 }
 
+impl fmt::Display for AdcOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AdcOption {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl AdcOption {
     pub fn from_u16(value: u16) -> Self {
         Self {
@@ -1579,6 +1734,7 @@ impl AdcOption {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ChargeOption0::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargeOption0 {
     pub msb_flags: ChargeOption0MsbFlags,
     pub lsb_flags: ChargeOption0Flags,
@@ -1608,6 +1764,17 @@ impl defmt::Format for ChargeOption0 {
     }
 }
 
+impl fmt::Display for ChargeOption0 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ChargeOption0 {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl ChargeOption0 {
     pub fn from_u16(value: u16) -> Self {
         Self {
@@ -1634,6 +1801,7 @@ impl ChargeOption0 {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ChargeOption1::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargeOption1 {
     pub msb_flags: ChargeOption1MsbFlags,
     pub lsb_flags: ChargeOption1Flags,
@@ -1663,6 +1831,17 @@ impl defmt::Format for ChargeOption1 {
     }
 }
 
+impl fmt::Display for ChargeOption1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ChargeOption1 {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl ChargeOption1 {
     pub fn from_u16(value: u16) -> Self {
         Self {
@@ -1689,6 +1868,7 @@ impl ChargeOption1 {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ChargeOption2::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargeOption2 {
     pub msb_flags: ChargeOption2MsbFlags,
     pub lsb_flags: ChargeOption2Flags,
@@ -1706,6 +1886,17 @@ impl defmt::Format for ChargeOption2 {
     }
 }
 
+impl fmt::Display for ChargeOption2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ChargeOption2 {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl ChargeOption2 {
     pub fn from_u16(value: u16) -> Self {
         Self {
@@ -1732,6 +1923,7 @@ impl ChargeOption2 {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ChargeOption3::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargeOption3 {
     pub msb_flags: ChargeOption3MsbFlags,
     pub lsb_flags: ChargeOption3Flags,
@@ -1749,6 +1941,17 @@ impl defmt::Format for ChargeOption3 {
     }
 }
 
+impl fmt::Display for ChargeOption3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ChargeOption3 {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl Default for ChargeOption3 {
     fn default() -> Self {
         Self {
@@ -1784,6 +1987,7 @@ impl ChargeOption3 {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ChargeOption4::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChargeOption4 {
     pub msb_flags: ChargeOption4MsbFlags,
     pub lsb_flags: ChargeOption4Flags,
@@ -1810,7 +2014,25 @@ impl defmt::Format for ChargeOption4 {
     }
 }
 
+impl fmt::Display for ChargeOption4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ChargeOption4 {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl ChargeOption4 {
+    /// IDCHG_TH2 in percent of IDCHG_TH1.
+    pub fn idchg_th2_percent(&self) -> u16 {
+        const PERCENT: [u16; 8] = [125, 150, 175, 200, 250, 300, 350, 400];
+        let code = (self.lsb_flags & ChargeOption4Flags::IDCHG_TH2).bits() >> 3;
+        PERCENT[usize::from(code)]
+    }
+
     pub fn from_u16(value: u16) -> Self {
         Self {
             msb_flags: ChargeOption4MsbFlags::from_bits_truncate(((value >> 8) & 0xFF) as u8),
@@ -1836,6 +2058,7 @@ impl ChargeOption4 {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = VminActiveProtection::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VminActiveProtection {
     pub msb_flags: VminActiveProtectionMsbFlags,
     pub lsb_flags: VminActiveProtectionFlags,
@@ -1868,6 +2091,16 @@ impl defmt::Format for VminActiveProtection {
     }
 }
 
+impl fmt::Display for VminActiveProtection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "VminActiveProtection {{ msb_flags: {}, lsb_flags: {} }}",
+            self.msb_flags, self.lsb_flags
+        )
+    }
+}
+
 impl VminActiveProtection {
     pub fn from_u16(value: u16) -> Self {
         Self {
@@ -1892,14 +2125,14 @@ impl VminActiveProtection {
 
     /// Converts the raw VBUS_VAP_TH value to voltage in mV.
     /// Formula: 3200mV + raw_value * 100mV
-    pub fn vbus_vap_th_mv(&self) -> u16 {
-        3200 + (self.msb_flags.get_vbus_vap_th() as u16) * 100
+    pub fn vbus_vap_th_mv(&self) -> MilliVolts {
+        MilliVolts(3200 + (self.msb_flags.get_vbus_vap_th() as u16) * 100)
     }
 
     /// Sets the VBUS_VAP_TH value from voltage in mV.
     /// Clamps the value to the valid range (3200mV to 15900mV).
-    pub fn set_vbus_vap_th_mv(&mut self, voltage_mv: u16) {
-        let clamped_voltage = voltage_mv.clamp(3200, 15900);
+    pub fn set_vbus_vap_th_mv(&mut self, voltage_mv: MilliVolts) {
+        let clamped_voltage = voltage_mv.0.clamp(3200, 15900);
         let raw_value = ((clamped_voltage - 3200) / 100) as u8;
         self.msb_flags.set_vbus_vap_th(raw_value);
     }
@@ -1907,15 +2140,15 @@ impl VminActiveProtection {
     /// Converts the raw VSYS_TH2 value to voltage in mV (assuming 2s-5s mode).
     /// Formula: 3200mV + raw_value * 100mV
     /// Note: This conversion might differ for 1S mode. Refer to datasheet for details.
-    pub fn vsys_th2_mv(&self) -> u16 {
-        3200 + (self.lsb_flags.get_vsys_th2() as u16) * 100
+    pub fn vsys_th2_mv(&self) -> MilliVolts {
+        MilliVolts(3200 + (self.lsb_flags.get_vsys_th2() as u16) * 100)
     }
 
     /// Sets the VSYS_TH2 value from voltage in mV (assuming 2s-5s mode).
     /// Clamps the value to the valid range (3200mV to 9500mV for 2s-5s).
     /// Note: This conversion might differ for 1S mode. Refer to datasheet for details.
-    pub fn set_vsys_th2_mv(&mut self, voltage_mv: u16) {
-        let clamped_voltage = voltage_mv.clamp(3200, 9500); // Assuming 2s-5s range
+    pub fn set_vsys_th2_mv(&mut self, voltage_mv: MilliVolts) {
+        let clamped_voltage = voltage_mv.0.clamp(3200, 9500); // Assuming 2s-5s range
         let raw_value = ((clamped_voltage - 3200) / 100) as u8;
         self.lsb_flags.set_vsys_th2(raw_value);
     }
@@ -1946,6 +2179,7 @@ impl VminActiveProtection {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ProchotOption0::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProchotOption0 {
     pub msb_flags: ProchotOption0MsbFlags,
     pub lsb_flags: ProchotOption0Flags,
@@ -1963,7 +2197,41 @@ impl defmt::Format for ProchotOption0 {
     }
 }
 
+impl fmt::Display for ProchotOption0 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ProchotOption0 {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl ProchotOption0 {
+    /// ILIM2_VTH codes that select an ILIM2 threshold; 0 and 31 are reserved.
+    pub const ILIM2_VTH_CODES: RangeInclusive<u8> = 1..=30;
+
+    /// The ILIM2_VTH field (MSB bits 7-3).
+    pub fn ilim2_vth_code(&self) -> u8 {
+        (self.msb_flags & ProchotOption0MsbFlags::ILIM2_VTH).bits() >> 3
+    }
+
+    /// ILIM2 in percent of IIN_DPM, or `None` for a reserved ILIM2_VTH code.
+    pub fn ilim2_percent(&self) -> Option<u16> {
+        let code = self.ilim2_vth_code();
+        if !Self::ILIM2_VTH_CODES.contains(&code) {
+            return None;
+        }
+        let code = u16::from(code);
+        // 105% to 225% in 5% steps, then 250% to 450% in 50% steps.
+        Some(if code <= 25 {
+            105 + 5 * code
+        } else {
+            250 + 50 * (code - 26)
+        })
+    }
+
     pub fn from_u16(value: u16) -> Self {
         Self {
             msb_flags: ProchotOption0MsbFlags::from_bits_truncate(((value >> 8) & 0xFF) as u8),
@@ -1989,6 +2257,7 @@ impl ProchotOption0 {
 #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
 #[cfg_attr(feature = "binrw", br(map = ProchotOption1::from_u16))]
 #[cfg_attr(feature = "binrw", bw(map = |&s: &Self| s.to_u16()))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProchotOption1 {
     pub msb_flags: ProchotOption1MsbFlags,
     pub lsb_flags: ProchotOption1Flags,
@@ -2006,7 +2275,28 @@ impl defmt::Format for ProchotOption1 {
     }
 }
 
+impl fmt::Display for ProchotOption1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ProchotOption1 {{ msb_flags: {:b}, lsb_flags: {:b} }}",
+            self.msb_flags.bits(),
+            self.lsb_flags.bits()
+        )
+    }
+}
+
 impl ProchotOption1 {
+    /// IDCHG_TH1, the PROCHOT battery discharge current threshold.
+    pub fn idchg_th1(&self, rsns_bat: SenseResistorValue) -> MilliAmps {
+        let code = u16::from((self.msb_flags & ProchotOption1MsbFlags::IDCHG_TH1).bits() >> 2);
+        let lsb_ma = match rsns_bat {
+            SenseResistorValue::R5mOhm => 512,
+            SenseResistorValue::R10mOhm => 256,
+        };
+        MilliAmps(code * lsb_ma)
+    }
+
     pub fn from_u16(value: u16) -> Self {
         Self {
             msb_flags: ProchotOption1MsbFlags::from_bits_truncate(((value >> 8) & 0xFF) as u8),
@@ -2027,9 +2317,192 @@ impl ProchotOption1 {
     }
 }
 
+/// Board-level PSYS calibration.
+///
+/// The PSYS pin sources a current proportional to system power
+/// (PSYS_RATIO, in µA/W) into an external resistor, so the ADC reading
+/// can only be turned into watts once that resistor is known.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PsysConfig {
+    /// Resistor from PSYS to ground in ohms, or `None` if PSYS is not used.
+    pub resistor_ohms: Option<u32>,
+}
+
+impl PsysConfig {
+    /// Creates a PsysConfig for the given PSYS resistor in ohms.
+    pub const fn new(resistor_ohms: u32) -> Self {
+        Self {
+            resistor_ohms: Some(resistor_ohms),
+        }
+    }
+
+    /// Converts a PSYS pin voltage into system power.
+    ///
+    /// V_PSYS = R_PSYS * K_PSYS * P, so P = V_PSYS / (R_PSYS * K_PSYS).
+    /// Returns `None` if no (or a zero) resistor has been configured.
+    pub fn power_from_voltage(&self, vpsys: MilliVolts, ratio: PsysRatio) -> Option<MilliWatts> {
+        let resistor_ohms = self.resistor_ohms.filter(|&r| r != 0)? as u64;
+        // mV / (Ω * 0.25 µA/W) = 4e6 mW per (mV / (Ω * quarter-µA/W))
+        let milliwatts = vpsys.0 as u64 * 4_000_000
+            / (resistor_ohms * ratio.quarter_microamps_per_watt() as u64);
+        Some(MilliWatts(milliwatts.min(u32::MAX as u64) as u32))
+    }
+}
+
+/// Converter switching frequency (PWM_FREQ).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SwitchingFrequency {
+    Khz400,
+    Khz800,
+}
+
+impl SwitchingFrequency {
+    pub fn khz(self) -> u16 {
+        match self {
+            SwitchingFrequency::Khz400 => 400,
+            SwitchingFrequency::Khz800 => 800,
+        }
+    }
+}
+
+/// Inductor fitted on the board, as declared by the IADPT pin resistor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Inductor {
+    Uh2_2,
+    Uh3_3,
+    Uh4_7,
+}
+
+impl Inductor {
+    /// Switching frequencies the inductor is rated for: 2.2 µH needs
+    /// 800 kHz, 4.7 µH needs 400 kHz and 3.3 µH works with either.
+    pub fn supports(self, frequency: SwitchingFrequency) -> bool {
+        !matches!(
+            (self, frequency),
+            (Inductor::Uh2_2, SwitchingFrequency::Khz400)
+                | (Inductor::Uh4_7, SwitchingFrequency::Khz800)
+        )
+    }
+}
+
+/// Inductor average current clamp (IL_AVG).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InductorCurrentLimit {
+    A6 = 0b00,
+    A10 = 0b01,
+    A15 = 0b10,
+    Disabled = 0b11,
+}
+
+impl InductorCurrentLimit {
+    /// The clamp, or `None` when disabled.
+    pub fn milliamps(self) -> Option<MilliAmps> {
+        match self {
+            InductorCurrentLimit::A6 => Some(MilliAmps(6000)),
+            InductorCurrentLimit::A10 => Some(MilliAmps(10000)),
+            InductorCurrentLimit::A15 => Some(MilliAmps(15000)),
+            InductorCurrentLimit::Disabled => None,
+        }
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        match (bits >> 3) & 0b11 {
+            0b00 => InductorCurrentLimit::A6,
+            0b01 => InductorCurrentLimit::A10,
+            0b10 => InductorCurrentLimit::A15,
+            _ => InductorCurrentLimit::Disabled,
+        }
+    }
+}
+
+/// Switching frequency, dithering and EMI settings.
+///
+/// Held in ChargeOption0 (PWM_FREQ, EN_OOA, LOW_PTM_RIPPLE), ChargeOption3
+/// (IL_AVG) and ChargeOption4 (EN_DITHER); when set as `Config::converter`
+/// these settings take precedence over the same bits in its option registers.
+/// The default is the power-on state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConverterConfig {
+    pub frequency: SwitchingFrequency,
+    /// Frequency dither span, for spreading EMI.
+    pub dither: DitherSetting,
+    /// Keep the switching frequency above the audio band at light load (EN_OOA).
+    pub out_of_audio: bool,
+    /// Lower VBUS ripple in Pass Through Mode (LOW_PTM_RIPPLE).
+    pub low_ptm_ripple: bool,
+    pub il_avg: InductorCurrentLimit,
+    /// Inductor fitted, checked against `frequency`; `None` skips the check.
+    /// Not stored in any register.
+    pub inductor: Option<Inductor>,
+}
+
+impl Default for ConverterConfig {
+    fn default() -> Self {
+        Self::from_options(
+            &ChargeOption0::default(),
+            &ChargeOption3::default(),
+            &ChargeOption4::default(),
+        )
+    }
+}
+
+impl ConverterConfig {
+    /// Decodes the settings from the option registers; `inductor` is `None`.
+    pub fn from_options(
+        option0: &ChargeOption0,
+        option3: &ChargeOption3,
+        option4: &ChargeOption4,
+    ) -> Self {
+        let msb0 = option0.msb_flags;
+        Self {
+            frequency: if msb0.contains(ChargeOption0MsbFlags::PWM_FREQ) {
+                SwitchingFrequency::Khz400
+            } else {
+                SwitchingFrequency::Khz800
+            },
+            dither: DitherSetting::from_bits(option4.msb_flags.bits()),
+            out_of_audio: msb0.contains(ChargeOption0MsbFlags::EN_OOA),
+            low_ptm_ripple: msb0.contains(ChargeOption0MsbFlags::LOW_PTM_RIPPLE),
+            il_avg: InductorCurrentLimit::from_bits(option3.lsb_flags.bits()),
+            inductor: None,
+        }
+    }
+
+    /// Writes the settings into the option registers, leaving their other
+    /// bits unchanged.
+    pub fn apply_to(
+        &self,
+        option0: &mut ChargeOption0,
+        option3: &mut ChargeOption3,
+        option4: &mut ChargeOption4,
+    ) {
+        let msb0 = &mut option0.msb_flags;
+        msb0.set(
+            ChargeOption0MsbFlags::PWM_FREQ,
+            self.frequency == SwitchingFrequency::Khz400,
+        );
+        msb0.set(ChargeOption0MsbFlags::EN_OOA, self.out_of_audio);
+        msb0.set(ChargeOption0MsbFlags::LOW_PTM_RIPPLE, self.low_ptm_ripple);
+        option3.lsb_flags.remove(ChargeOption3Flags::IL_AVG);
+        option3.lsb_flags |= ChargeOption3Flags::from_bits_truncate((self.il_avg as u8) << 3);
+        option4.msb_flags.set_en_dither(self.dither);
+    }
+}
+
 /// Configuration for the BQ25730 charger.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Config {
     pub rsns_bat: SenseResistorValue,
     pub rsns_ac: SenseResistorValue,
@@ -2045,6 +2518,13 @@ pub struct Config {
     pub vsys_min: VsysMinSetting,
     pub iin_host: IinHostSetting,
     pub vmin_active_protection: VminActiveProtection,
+    /// PSYS resistor; not stored in any register.
+    pub psys: PsysConfig,
+    /// Switching frequency, dithering and EMI settings. `None` writes
+    /// PWM_FREQ, EN_OOA, LOW_PTM_RIPPLE, IL_AVG and EN_DITHER as they are in
+    /// `charge_option0`, `charge_option3` and `charge_option4`; `Some`
+    /// overrides those bits.
+    pub converter: Option<ConverterConfig>,
     // TODO: Add other configurable registers as needed
 }
 
@@ -2114,6 +2594,8 @@ impl Config {
                 rsns_ac,
             ),
             vmin_active_protection: VminActiveProtection::default(), // Uses datasheet reset (e.g. 0x006C for 2s-5s)
+            psys: PsysConfig::default(),
+            converter: None,
         }
     }
 }
@@ -2127,3 +2609,212 @@ impl Default for Config {
         )
     }
 }
+
+/// What `Bq25730::reset_registers` does once RESET_REG has completed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PostResetAction {
+    /// Re-run `init()` to write the cached configuration back to the chip.
+    Reinitialize,
+    /// Leave the chip at its reset values and mark the cached configuration stale.
+    InvalidateConfig,
+}
+
+bitflags! {
+    /// Set of configuration registers touched by `Config::diff` / `Bq25730::apply_config`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ConfigChanges: u16 {
+        const CHARGE_OPTION0 = 1 << 0;
+        const CHARGE_CURRENT = 1 << 1;
+        const CHARGE_VOLTAGE = 1 << 2;
+        const OTG_VOLTAGE = 1 << 3;
+        const OTG_CURRENT = 1 << 4;
+        const INPUT_VOLTAGE = 1 << 5;
+        const VSYS_MIN = 1 << 6;
+        const IIN_HOST = 1 << 7;
+        const CHARGE_OPTION1 = 1 << 8;
+        const CHARGE_OPTION3 = 1 << 9;
+        const CHARGE_OPTION4 = 1 << 10;
+        const VMIN_ACTIVE_PROTECTION = 1 << 11;
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ConfigChanges {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "{=u16:b}", self.bits());
+    }
+}
+
+impl fmt::Display for ConfigChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
+impl ConfigChanges {
+    /// Returns the flag for a configuration register, or an empty set if the
+    /// register is not owned by `Config`.
+    pub fn from_register(reg: Register) -> Self {
+        match reg {
+            Register::ChargeOption0 => Self::CHARGE_OPTION0,
+            Register::ChargeCurrent => Self::CHARGE_CURRENT,
+            Register::ChargeVoltage => Self::CHARGE_VOLTAGE,
+            Register::OTGVoltage => Self::OTG_VOLTAGE,
+            Register::OTGCurrent => Self::OTG_CURRENT,
+            Register::InputVoltage => Self::INPUT_VOLTAGE,
+            Register::VsysMin => Self::VSYS_MIN,
+            Register::IinHost => Self::IIN_HOST,
+            Register::ChargeOption1 => Self::CHARGE_OPTION1,
+            Register::ChargeOption3 => Self::CHARGE_OPTION3,
+            Register::ChargeOption4 => Self::CHARGE_OPTION4,
+            Register::VMINActiveProtection => Self::VMIN_ACTIVE_PROTECTION,
+            _ => Self::empty(),
+        }
+    }
+}
+
+impl Config {
+    /// Number of 16-bit registers owned by `Config`.
+    pub const REGISTER_COUNT: usize = 12;
+
+    /// Encodes every configuration register as `(register, lsb, msb)`.
+    /// The encoding matches what `Bq25730::init()` writes; option registers come first.
+    pub fn register_values(&self) -> [(Register, u8, u8); Self::REGISTER_COUNT] {
+        let (option0, option3, option4) = self.option_registers();
+        let co0 = option0.to_msb_lsb_bytes();
+        let cc = self.charge_current.to_msb_lsb_bytes();
+        let cv = self.charge_voltage.to_msb_lsb_bytes();
+        let otg_v = self.otg_voltage.to_msb_lsb_bytes();
+        let otg_c = self.otg_current.to_msb_lsb_bytes();
+        let iv = self.input_voltage.to_msb_lsb_bytes();
+        let vm = self.vsys_min.to_msb_lsb_bytes();
+        let ih = self.iin_host.to_msb_lsb_bytes(self.rsns_ac);
+        let co1 = self.charge_option1.to_msb_lsb_bytes();
+        let co3 = option3.to_msb_lsb_bytes();
+        let co4 = option4.to_msb_lsb_bytes();
+        let vmin = self.vmin_active_protection.to_msb_lsb_bytes();
+        [
+            (Register::ChargeOption0, co0.0, co0.1),
+            (Register::ChargeOption1, co1.0, co1.1),
+            (Register::ChargeOption3, co3.0, co3.1),
+            (Register::ChargeOption4, co4.0, co4.1),
+            (Register::ChargeCurrent, cc.0, cc.1),
+            (Register::ChargeVoltage, cv.0, cv.1),
+            (Register::OTGVoltage, otg_v.0, otg_v.1),
+            (Register::OTGCurrent, otg_c.0, otg_c.1),
+            (Register::InputVoltage, iv.0, iv.1),
+            (Register::VsysMin, vm.0, vm.1),
+            (Register::IinHost, ih.0, ih.1),
+            (Register::VMINActiveProtection, vmin.0, vmin.1),
+        ]
+    }
+
+    /// Returns ChargeOption0, ChargeOption3 and ChargeOption4 with
+    /// `converter` applied, if set, as written to the chip.
+    pub fn option_registers(&self) -> (ChargeOption0, ChargeOption3, ChargeOption4) {
+        let mut options = (
+            self.charge_option0,
+            self.charge_option3,
+            self.charge_option4,
+        );
+        if let Some(converter) = &self.converter {
+            converter.apply_to(&mut options.0, &mut options.1, &mut options.2);
+        }
+        options
+    }
+
+    /// Returns the converter settings that will be written: `converter` if
+    /// set, otherwise the ones decoded from the option registers.
+    pub fn converter_config(&self) -> ConverterConfig {
+        self.converter.unwrap_or_else(|| {
+            ConverterConfig::from_options(
+                &self.charge_option0,
+                &self.charge_option3,
+                &self.charge_option4,
+            )
+        })
+    }
+
+    /// Decodes a `Config` from raw register blocks, as read back from the chip.
+    ///
+    /// `low` holds registers 0x00-0x0F and `high` holds 0x30-0x3F. The sense
+    /// resistor values are recovered from RSNS_RSR/RSNS_RAC in ChargeOption1
+    /// and then used to scale the current settings. `psys` is not stored on
+    /// the chip and is left at its default; `converter` is `None`, as the
+    /// option registers already hold its bits.
+    pub fn from_registers(low: &[u8; 16], high: &[u8; 16]) -> Self {
+        let word = |block: &[u8; 16], reg: Register, base: Register| {
+            let index = (reg as u8 - base as u8) as usize;
+            u16::from_le_bytes([block[index], block[index + 1]])
+        };
+        let low_word = |reg| word(low, reg, Register::ChargeOption0);
+        let high_word = |reg| word(high, reg, Register::ChargeOption1);
+
+        let charge_option1 = ChargeOption1::from_u16(high_word(Register::ChargeOption1));
+        let rsns_bat = if charge_option1
+            .msb_flags
+            .contains(ChargeOption1MsbFlags::RSNS_RSR)
+        {
+            SenseResistorValue::R5mOhm
+        } else {
+            SenseResistorValue::R10mOhm
+        };
+        let rsns_ac = if charge_option1
+            .msb_flags
+            .contains(ChargeOption1MsbFlags::RSNS_RAC)
+        {
+            SenseResistorValue::R5mOhm
+        } else {
+            SenseResistorValue::R10mOhm
+        };
+
+        let charge_option0 = ChargeOption0::from_u16(low_word(Register::ChargeOption0));
+        let charge_option3 = ChargeOption3::from_u16(high_word(Register::ChargeOption3));
+        let charge_option4 = ChargeOption4::from_u16(high_word(Register::ChargeOption4));
+
+        Self {
+            rsns_bat,
+            rsns_ac,
+            charge_option0,
+            charge_option1,
+            charge_option3,
+            charge_option4,
+            charge_current: ChargeCurrentSetting::from_raw(
+                low_word(Register::ChargeCurrent),
+                rsns_bat,
+            ),
+            charge_voltage: ChargeVoltageSetting::from_raw(low_word(Register::ChargeVoltage), None),
+            otg_voltage: OtgVoltageSetting::from_raw(low_word(Register::OTGVoltage)),
+            otg_current: OtgCurrentSetting::from_raw(
+                (low_word(Register::OTGCurrent) >> 8) as u8 & 0x7F,
+                rsns_bat,
+            ),
+            input_voltage: InputVoltageSetting::from_raw(low_word(Register::InputVoltage)),
+            vsys_min: VsysMinSetting::from_raw(low_word(Register::VsysMin)),
+            iin_host: IinHostSetting::from_raw(low_word(Register::IinHost), rsns_ac),
+            vmin_active_protection: VminActiveProtection::from_u16(high_word(
+                Register::VMINActiveProtection,
+            )),
+            psys: PsysConfig::default(),
+            converter: None,
+        }
+    }
+
+    /// Returns the registers whose encoded value differs between `self` and `other`.
+    pub fn diff(&self, other: &Config) -> ConfigChanges {
+        let mut changes = ConfigChanges::empty();
+        for (old, new) in self
+            .register_values()
+            .iter()
+            .zip(other.register_values().iter())
+        {
+            if old != new {
+                changes |= ConfigChanges::from_register(new.0);
+            }
+        }
+        changes
+    }
+}
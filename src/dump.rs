@@ -0,0 +1,644 @@
+//! Full register dump and human-readable decoder.
+//!
+//! # Dump file formats
+//!
+//! A [`RegisterDump`] can be written and read back in two formats.
+//!
+//! **Text** (`RegisterDump::text()` / `str::parse`): one `addr=value` pair per
+//! line, both in hex with an optional `0x` prefix. Everything after `#` is a
+//! comment and blank lines are ignored:
+//!
+//! ```text
+//! 0x00=0x0e  # ChargeOption0
+//! 0x01=0xe7  # ChargeOption0Msb
+//! ```
+//!
+//! The parser also accepts the table printed by `i2cdump -y <bus> 0x6b`
+//! (`00: 0e e7 ...` rows). Cells for addresses that are not a register, and
+//! `XX` cells, are skipped. Every register must be present exactly once or
+//! parsing fails with [`DumpParseError::MissingRegister`].
+//!
+//! **Binary** (`binrw` feature): the 8-byte magic `BQ25730D` followed by the
+//! 48 register values in `Register::ALL` order.
+
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "binrw")]
+use binrw::{BinRead, BinWrite};
+use bitflags::Flags;
+use bitflags::parser::WriteHex;
+
+use crate::data_types::{
+    ADC_OFFSET_MV, AdcMeasurements, AdcOption, ChargeOption2, ChargerStatus, Config, IinDpm,
+    ProchotOption0, ProchotOption1, ProchotStatus,
+};
+use crate::registers::{
+    ChargeOption0Flags, ChargeOption3MsbFlags, ChargerStatusFaultFlags, ChargerStatusFlags,
+    Register,
+};
+use crate::units::MilliVolts;
+use crate::variant::ChipVariant;
+
+/// Raw snapshot of every register defined in [`Register`].
+///
+/// Bytes are stored in `Register::ALL` order, so two dumps can be compared
+/// address by address with [`RegisterDump::diff`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "binrw",
+    derive(BinRead, BinWrite),
+    brw(little, magic = b"BQ25730D")
+)]
+pub struct RegisterDump {
+    raw: [u8; RegisterDump::LEN],
+}
+
+impl RegisterDump {
+    /// Number of register addresses in a dump.
+    pub const LEN: usize = Register::ALL.len();
+
+    /// Leading bytes of the binary dump format.
+    pub const MAGIC: &'static [u8; 8] = b"BQ25730D";
+
+    /// Creates a dump from bytes in `Register::ALL` order.
+    pub fn from_bytes(raw: [u8; Self::LEN]) -> Self {
+        Self { raw }
+    }
+
+    /// Creates a dump from the three contiguous register blocks:
+    /// 0x00-0x0F, 0x20-0x2F and 0x30-0x3F.
+    pub fn from_blocks(low: &[u8; 16], status: &[u8; 16], high: &[u8; 16]) -> Self {
+        let mut raw = [0u8; Self::LEN];
+        raw[..16].copy_from_slice(low);
+        raw[16..32].copy_from_slice(status);
+        raw[32..].copy_from_slice(high);
+        Self { raw }
+    }
+
+    /// Returns the raw bytes in `Register::ALL` order.
+    pub fn as_bytes(&self) -> &[u8; Self::LEN] {
+        &self.raw
+    }
+
+    /// Returns the value of a single register.
+    pub fn get(&self, reg: Register) -> u8 {
+        self.raw[reg.index()]
+    }
+
+    /// Returns the 16-bit value of the register pair starting at `reg` (LSB).
+    /// The MSB reads as 0 past the last defined address.
+    pub fn word(&self, reg: Register) -> u16 {
+        let index = reg.index();
+        u16::from_le_bytes([
+            self.raw[index],
+            self.raw.get(index + 1).copied().unwrap_or(0),
+        ])
+    }
+
+    /// Iterates over every register and its value.
+    pub fn iter(&self) -> impl Iterator<Item = (Register, u8)> + '_ {
+        Register::ALL.iter().copied().zip(self.raw.iter().copied())
+    }
+
+    /// Iterates over the registers whose value differs between `self` and `other`.
+    pub fn diff<'a>(&'a self, other: &'a RegisterDump) -> impl Iterator<Item = RegisterDiff> + 'a {
+        self.iter()
+            .zip(other.raw.iter().copied())
+            .filter(|((_, before), after)| before != after)
+            .map(|((register, before), after)| RegisterDiff {
+                register,
+                before,
+                after,
+            })
+    }
+
+    /// Decodes the dump using the existing register types.
+    ///
+    /// Sense resistor values are taken from ChargeOption1 in the dump itself.
+    /// ADC readings are scaled for `variant()`, or for a BQ25730 if the IDs
+    /// are not recognised.
+    pub fn decode(&self) -> DecodedRegisters {
+        let mut low = [0u8; 16];
+        low.copy_from_slice(&self.raw[..16]);
+        let mut high = [0u8; 16];
+        high.copy_from_slice(&self.raw[32..]);
+        let config = Config::from_registers(&low, &high);
+
+        let mut adc_block = [0u8; 8];
+        let adc_start = Register::ADCPSYS.index();
+        adc_block.copy_from_slice(&self.raw[adc_start..adc_start + 8]);
+
+        DecodedRegisters {
+            charge_option2: ChargeOption2::from_u16(self.word(Register::ChargeOption2)),
+            prochot_option0: ProchotOption0::from_u16(self.word(Register::ProchotOption0)),
+            prochot_option1: ProchotOption1::from_u16(self.word(Register::ProchotOption1)),
+            adc_option: AdcOption::from_u16(self.word(Register::ADCOption)),
+            charger_status: ChargerStatus::from_u16(self.word(Register::ChargerStatus)),
+            prochot_status: ProchotStatus::from_u16(self.word(Register::ProchotStatus)),
+            iin_dpm: IinDpm::from_raw(self.get(Register::IinDpmMsb) & 0x7F, config.rsns_ac),
+            adc: AdcMeasurements::from_adc_block(
+                &adc_block,
+                config.rsns_bat,
+                config.rsns_ac,
+                ADC_OFFSET_MV,
+            ),
+            manufacturer_id: self.get(Register::ManufacturerID),
+            device_id: self.get(Register::DeviceID),
+            config,
+        }
+    }
+
+    /// Returns the chip variant identified by the dumped ID registers.
+    pub fn variant(&self) -> Option<ChipVariant> {
+        ChipVariant::from_ids(
+            self.get(Register::ManufacturerID),
+            self.get(Register::DeviceID),
+        )
+    }
+
+    /// Returns a `Display` adapter that writes the dump in the text format.
+    pub fn text(&self) -> DumpText<'_> {
+        DumpText(self)
+    }
+
+    /// Checks the decoded registers for combinations that usually point at a
+    /// misconfiguration or a fault.
+    pub fn warnings(&self) -> impl Iterator<Item = DumpWarning> {
+        let d = self.decode();
+        let c = &d.config;
+        let status = d.charger_status.status_flags;
+        let faults = d.charger_status.fault_flags;
+        let adapter = status.contains(ChargerStatusFlags::STAT_AC);
+        let charging =
+            status.intersects(ChargerStatusFlags::IN_FCHRG | ChargerStatusFlags::IN_PCHRG);
+        let vbat = d.adc.vbat.0;
+        let vbat_limit = c
+            .charge_voltage
+            .millivolts
+            .saturating_add(c.charge_voltage.millivolts / 50);
+
+        [
+            (
+                self.variant().is_none(),
+                DumpWarning::UnexpectedId {
+                    manufacturer: d.manufacturer_id,
+                    device: d.device_id,
+                },
+            ),
+            (!faults.is_empty(), DumpWarning::Faults(faults)),
+            (
+                adapter
+                    && c.charge_option3
+                        .msb_flags
+                        .contains(ChargeOption3MsbFlags::EN_OTG),
+                DumpWarning::OtgWithAdapter,
+            ),
+            (
+                charging && status.contains(ChargerStatusFlags::IN_OTG),
+                DumpWarning::ChargingInOtg,
+            ),
+            (
+                adapter
+                    && c.charge_current.milliamps > 0
+                    && c.charge_option0
+                        .lsb_flags
+                        .contains(ChargeOption0Flags::CHRG_INHIBIT),
+                DumpWarning::ChargeInhibited,
+            ),
+            (
+                c.charge_voltage.millivolts < c.vsys_min.millivolts,
+                DumpWarning::ChargeVoltageBelowVsysMin {
+                    charge_voltage: c.charge_voltage.millivolts,
+                    vsys_min: c.vsys_min.millivolts,
+                },
+            ),
+            (
+                vbat > MilliVolts(ADC_OFFSET_MV) && vbat > vbat_limit,
+                DumpWarning::VbatAboveChargeVoltage {
+                    vbat,
+                    charge_voltage: c.charge_voltage.millivolts,
+                },
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(suspicious, warning)| suspicious.then_some(warning))
+    }
+}
+
+/// Registers of a [`RegisterDump`] decoded into their typed representations.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DecodedRegisters {
+    /// Configuration registers (0x00-0x0F and the options in 0x30-0x3F).
+    pub config: Config,
+    pub charge_option2: ChargeOption2,
+    pub prochot_option0: ProchotOption0,
+    pub prochot_option1: ProchotOption1,
+    pub adc_option: AdcOption,
+    pub charger_status: ChargerStatus,
+    pub prochot_status: ProchotStatus,
+    pub iin_dpm: IinDpm,
+    pub adc: AdcMeasurements,
+    pub manufacturer_id: u8,
+    pub device_id: u8,
+}
+
+/// A single register that differs between two dumps.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterDiff {
+    pub register: Register,
+    pub before: u8,
+    pub after: u8,
+}
+
+impl fmt::Display for RegisterDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:#04x} {:?}: {:#04x} -> {:#04x}",
+            self.register as u8, self.register, self.before, self.after
+        )
+    }
+}
+
+/// Writes a [`RegisterDump`] in the text format, one `addr=value` line per register.
+pub struct DumpText<'a>(&'a RegisterDump);
+
+impl fmt::Display for DumpText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (register, value) in self.0.iter() {
+            writeln!(
+                f,
+                "{:#04x}={:#04x}  # {:?}",
+                register as u8, value, register
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Suspicious register combination found by [`RegisterDump::warnings`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DumpWarning {
+    /// ManufacturerID/DeviceID do not identify a supported `ChipVariant`.
+    UnexpectedId { manufacturer: u8, device: u8 },
+    /// ChargerStatus reports latched faults.
+    Faults(ChargerStatusFaultFlags),
+    /// EN_OTG is set while an adapter is present; OTG only starts once it is removed.
+    OtgWithAdapter,
+    /// ChargerStatus reports charging and OTG at the same time.
+    ChargingInOtg,
+    /// An adapter is present and ChargeCurrent is set, but CHRG_INHIBIT blocks charging.
+    ChargeInhibited,
+    /// ChargeVoltage is below VSYS_MIN.
+    ChargeVoltageBelowVsysMin {
+        charge_voltage: MilliVolts,
+        vsys_min: MilliVolts,
+    },
+    /// The VBAT ADC reading is more than 2% above ChargeVoltage.
+    VbatAboveChargeVoltage {
+        vbat: MilliVolts,
+        charge_voltage: MilliVolts,
+    },
+}
+
+impl fmt::Display for DumpWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpWarning::UnexpectedId {
+                manufacturer,
+                device,
+            } => write!(
+                f,
+                "ManufacturerID {manufacturer:#04x} / DeviceID {device:#04x} is not a supported BQ2573x"
+            ),
+            DumpWarning::Faults(faults) => write!(f, "faults latched: {}", Names(*faults)),
+            DumpWarning::OtgWithAdapter => f.write_str("EN_OTG is set while an adapter is present"),
+            DumpWarning::ChargingInOtg => f.write_str("charging and OTG reported at the same time"),
+            DumpWarning::ChargeInhibited => {
+                f.write_str("adapter present and ChargeCurrent set, but CHRG_INHIBIT is set")
+            }
+            DumpWarning::ChargeVoltageBelowVsysMin {
+                charge_voltage,
+                vsys_min,
+            } => write!(
+                f,
+                "ChargeVoltage {charge_voltage} is below VSYS_MIN {vsys_min}"
+            ),
+            DumpWarning::VbatAboveChargeVoltage {
+                vbat,
+                charge_voltage,
+            } => write!(f, "VBAT {vbat} is above ChargeVoltage {charge_voltage}"),
+        }
+    }
+}
+
+/// Error returned when parsing a register dump.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DumpParseError {
+    /// The line (1-based) is neither `addr=value`, an `i2cdump` row nor a comment.
+    Syntax { line: usize },
+    /// An `addr=value` line names an address that is not a register.
+    UnknownRegister { line: usize, addr: u8 },
+    /// The dump does not contain a value for this register.
+    MissingRegister(Register),
+    /// The binary dump is truncated or does not start with `RegisterDump::MAGIC`.
+    Binary,
+}
+
+impl fmt::Display for DumpParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpParseError::Syntax { line } => write!(f, "line {line}: invalid syntax"),
+            DumpParseError::UnknownRegister { line, addr } => {
+                write!(f, "line {line}: {addr:#04x} is not a register")
+            }
+            DumpParseError::MissingRegister(register) => {
+                write!(f, "missing {:#04x} {:?}", *register as u8, register)
+            }
+            DumpParseError::Binary => f.write_str("invalid binary dump"),
+        }
+    }
+}
+
+impl FromStr for RegisterDump {
+    type Err = DumpParseError;
+
+    /// Parses the text format or `i2cdump` output; see the module documentation.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut raw = [0u8; Self::LEN];
+        let mut seen = [false; Self::LEN];
+
+        for (index, line) in s.lines().enumerate() {
+            let line_no = index + 1;
+            let syntax = DumpParseError::Syntax { line: line_no };
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() || is_i2cdump_header(line) {
+                continue;
+            }
+
+            if let Some((addr, value)) = line.split_once('=') {
+                let addr = parse_hex(addr).ok_or(syntax)?;
+                let value = parse_hex(value).ok_or(syntax)?;
+                let register =
+                    Register::try_from(addr).map_err(|addr| DumpParseError::UnknownRegister {
+                        line: line_no,
+                        addr,
+                    })?;
+                raw[register.index()] = value;
+                seen[register.index()] = true;
+            } else if let Some((base, cells)) = line.split_once(':') {
+                let base = parse_hex(base).filter(|b| b & 0x0F == 0).ok_or(syntax)?;
+                // i2cdump prints 16 cells followed by an ASCII column.
+                for (offset, cell) in cells.split_whitespace().take(16).enumerate() {
+                    if cell.eq_ignore_ascii_case("xx") {
+                        continue;
+                    }
+                    let value = parse_hex(cell).ok_or(syntax)?;
+                    if let Ok(register) = Register::try_from(base + offset as u8) {
+                        raw[register.index()] = value;
+                        seen[register.index()] = true;
+                    }
+                }
+            } else {
+                return Err(syntax);
+            }
+        }
+
+        match seen.iter().position(|&seen| !seen) {
+            Some(index) => Err(DumpParseError::MissingRegister(Register::ALL[index])),
+            None => Ok(Self::from_bytes(raw)),
+        }
+    }
+}
+
+/// Parses a hex byte with an optional `0x` prefix.
+fn parse_hex(s: &str) -> Option<u8> {
+    let s = s.trim();
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    u8::from_str_radix(digits, 16).ok()
+}
+
+/// Matches the `0  1  2 ... f    0123456789abcdef` column header printed by `i2cdump`.
+fn is_i2cdump_header(line: &str) -> bool {
+    let mut tokens = line.split_whitespace();
+    "0123456789abcdef".chars().all(|digit| {
+        tokens
+            .next()
+            .is_some_and(|t| t.len() == 1 && t.starts_with(digit))
+    })
+}
+
+/// Displays bitflags as `NAME | NAME`, or `-` when no flag is set.
+struct Names<F>(F);
+
+impl<F: Flags> fmt::Display for Names<F>
+where
+    F::Bits: WriteHex,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            f.write_str("-")
+        } else {
+            bitflags::parser::to_writer(&self.0, f)
+        }
+    }
+}
+
+macro_rules! row16 {
+    ($f:expr, $dump:expr, $reg:ident, $($arg:tt)*) => {
+        writeln!(
+            $f,
+            "{:#04x} {:<22} {:#06x}  {}",
+            Register::$reg as u8,
+            stringify!($reg),
+            $dump.word(Register::$reg),
+            format_args!($($arg)*)
+        )
+    };
+}
+
+macro_rules! row8 {
+    ($f:expr, $dump:expr, $reg:ident, $($arg:tt)*) => {
+        writeln!(
+            $f,
+            "{:#04x} {:<22} {:#04x}    {}",
+            Register::$reg as u8,
+            stringify!($reg),
+            $dump.get(Register::$reg),
+            format_args!($($arg)*)
+        )
+    };
+}
+
+impl fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = self.decode();
+        let c = &d.config;
+
+        row16!(
+            f,
+            self,
+            ChargeOption0,
+            "{} | {}",
+            Names(c.charge_option0.msb_flags),
+            Names(c.charge_option0.lsb_flags)
+        )?;
+        row16!(f, self, ChargeCurrent, "{}", c.charge_current.milliamps)?;
+        row16!(f, self, ChargeVoltage, "{}", c.charge_voltage.millivolts)?;
+        row16!(f, self, OTGVoltage, "{}", c.otg_voltage.millivolts)?;
+        row16!(f, self, OTGCurrent, "{}", c.otg_current.milliamps)?;
+        row16!(f, self, InputVoltage, "{}", c.input_voltage.millivolts)?;
+        row16!(f, self, VsysMin, "{}", c.vsys_min.millivolts)?;
+        row16!(f, self, IinHost, "{}", c.iin_host.milliamps)?;
+
+        row16!(
+            f,
+            self,
+            ChargerStatus,
+            "{} | {}",
+            Names(d.charger_status.status_flags),
+            Names(d.charger_status.fault_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            ProchotStatus,
+            "{} | {} width={}",
+            Names(d.prochot_status.msb_flags),
+            Names(d.prochot_status.lsb_flags),
+            d.prochot_status.prochot_width
+        )?;
+        row16!(f, self, IinDpm, "{}", d.iin_dpm.milliamps)?;
+        row8!(f, self, ADCPSYS, "{}", d.adc.psys.0)?;
+        row8!(f, self, ADCVBUS, "{}", d.adc.vbus.0)?;
+        row8!(f, self, ADCIDCHG, "{}", d.adc.idchg.milliamps)?;
+        row8!(f, self, ADCICHG, "{}", d.adc.ichg.milliamps)?;
+        row8!(f, self, ADCCMPIN, "{}", d.adc.cmpin.0)?;
+        row8!(f, self, ADCIIN, "{}", d.adc.iin.milliamps)?;
+        row8!(f, self, ADCVBAT, "{}", d.adc.vbat.0)?;
+        row8!(f, self, ADCVSYS, "{}", d.adc.vsys.0)?;
+        row8!(f, self, ManufacturerID, "")?;
+        row8!(f, self, DeviceID, "")?;
+
+        row16!(
+            f,
+            self,
+            ChargeOption1,
+            "{} | {}",
+            Names(c.charge_option1.msb_flags),
+            Names(c.charge_option1.lsb_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            ChargeOption2,
+            "{} | {}",
+            Names(d.charge_option2.msb_flags),
+            Names(d.charge_option2.lsb_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            ChargeOption3,
+            "{} | {}",
+            Names(c.charge_option3.msb_flags),
+            Names(c.charge_option3.lsb_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            ProchotOption0,
+            "{} | {}",
+            Names(d.prochot_option0.msb_flags),
+            Names(d.prochot_option0.lsb_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            ProchotOption1,
+            "{} | {}",
+            Names(d.prochot_option1.msb_flags),
+            Names(d.prochot_option1.lsb_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            ADCOption,
+            "{} | {}",
+            Names(d.adc_option.msb_flags),
+            Names(d.adc_option.lsb_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            ChargeOption4,
+            "{} | {}",
+            Names(c.charge_option4.msb_flags),
+            Names(c.charge_option4.lsb_flags)
+        )?;
+        row16!(
+            f,
+            self,
+            VMINActiveProtection,
+            "{} | {}",
+            Names(c.vmin_active_protection.msb_flags),
+            Names(c.vmin_active_protection.lsb_flags)
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for RegisterDump {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "RegisterDump {{ raw: {=[u8]:#x}, decoded: {} }}",
+            &self.raw[..],
+            self.decode()
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+impl RegisterDump {
+    /// Parses a dump in any supported format: binary if `bytes` starts with
+    /// `RegisterDump::MAGIC` (requires the `binrw` feature), text otherwise.
+    pub fn parse(bytes: &[u8]) -> Result<Self, DumpParseError> {
+        if bytes.starts_with(Self::MAGIC) {
+            #[cfg(feature = "binrw")]
+            return Self::read(&mut binrw::io::Cursor::new(bytes))
+                .map_err(|_| DumpParseError::Binary);
+            #[cfg(not(feature = "binrw"))]
+            return Err(DumpParseError::Binary);
+        }
+        core::str::from_utf8(bytes)
+            .map_err(|_| DumpParseError::Binary)?
+            .parse()
+    }
+
+    /// Renders the decoded register table followed by any warnings.
+    pub fn report(&self) -> std::string::String {
+        use core::fmt::Write;
+
+        let mut report = std::format!("{self}");
+        let mut warnings = self.warnings().peekable();
+        if warnings.peek().is_none() {
+            report.push_str("\nNo warnings.\n");
+        } else {
+            report.push_str("\nWarnings:\n");
+            for warning in warnings {
+                // Writing to a String cannot fail.
+                let _ = writeln!(report, "- {warning}");
+            }
+        }
+        report
+    }
+}
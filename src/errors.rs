@@ -1,19 +1,166 @@
+use core::fmt;
+use core::ops::RangeInclusive;
+
 #[cfg(feature = "defmt")]
 extern crate defmt; // Make defmt available for derive macros
 
 #[cfg(feature = "defmt")]
 use defmt::Format;
 
+use crate::data_types::{Inductor, SwitchingFrequency};
+use crate::pins::StatusPin;
+use crate::registers::Register;
+
 /// Represents potential errors when interacting with the chip.
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(Format))] // Conditionally derive Format
 pub enum Error<E> {
-    /// An error occurred during I2C communication.
-    I2c(E),
-    /// Invalid data received from the chip.
-    InvalidData,
-    // Add other specific error types as needed later, e.g.:
-    // UnsupportedFeature,
-    /// CRC validation failed.
-    CrcError,
+    /// An error occurred during I2C communication with `register`.
+    I2c { register: Register, source: E },
+    /// `value` cannot be encoded in `field` without clamping. Units follow the
+    /// field (mV or mA).
+    OutOfRange {
+        field: &'static str,
+        value: u32,
+        min: u32,
+        max: u32,
+    },
+    /// The fitted `inductor` is not rated for switching at `frequency`.
+    UnsupportedFrequency {
+        inductor: Inductor,
+        frequency: SwitchingFrequency,
+    },
+    /// A multi-register transfer starting at `register` was empty or longer
+    /// than the driver's buffer.
+    InvalidLength { register: Register, len: usize },
+    /// ManufacturerID/DeviceID do not identify the expected `ChipVariant`.
+    UnexpectedDevice { manufacturer_id: u8, device_id: u8 },
+    /// Reading `register` back returned `actual` instead of `expected`.
+    VerifyFailed {
+        register: Register,
+        expected: u16,
+        actual: u16,
+    },
+    /// A self-clearing bit in `register` did not clear in time.
+    Timeout { register: Register },
+    /// The cached configuration does not describe the chip, e.g. it is stale
+    /// after a register reset.
+    NotInitialized,
+    /// The board-level setting `field` of the configuration is unset, e.g.
+    /// the PSYS resistor.
+    MissingConfig { field: &'static str },
+    /// The request conflicts with the operating mode read from `register`,
+    /// e.g. re-detecting VINDPM while OTG is enabled.
+    ConflictingMode { register: Register },
+    /// The SMBus PEC byte read back with `register` did not match.
+    CrcError { register: Register },
+    /// Reading or waiting on status pin `pin` failed with `kind`.
+    Pin {
+        pin: StatusPin,
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        kind: embedded_hal::digital::ErrorKind,
+    },
+    /// The shared driver is already in use, e.g. by the context this
+    /// `try_*` call interrupted.
+    WouldBlock,
+}
+
+impl<E> Error<E> {
+    /// Returns the register the failed operation accessed, if there is one.
+    pub fn register(&self) -> Option<Register> {
+        match self {
+            Error::I2c { register, .. }
+            | Error::InvalidLength { register, .. }
+            | Error::VerifyFailed { register, .. }
+            | Error::Timeout { register }
+            | Error::ConflictingMode { register }
+            | Error::CrcError { register } => Some(*register),
+            Error::OutOfRange { .. }
+            | Error::UnsupportedFrequency { .. }
+            | Error::UnexpectedDevice { .. }
+            | Error::NotInitialized
+            | Error::MissingConfig { .. }
+            | Error::Pin { .. }
+            | Error::WouldBlock => None,
+        }
+    }
+
+    /// Returns `OutOfRange` unless `range` contains `value`.
+    pub(crate) fn check_range<T>(
+        field: &'static str,
+        value: T,
+        range: RangeInclusive<T>,
+    ) -> Result<(), Self>
+    where
+        T: PartialOrd + Copy + Into<u16>,
+    {
+        if range.contains(&value) {
+            Ok(())
+        } else {
+            Err(Error::OutOfRange {
+                field,
+                value: value.into().into(),
+                min: (*range.start()).into().into(),
+                max: (*range.end()).into().into(),
+            })
+        }
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::I2c { register, source } => {
+                write!(f, "I2C error accessing {register:?}: {source:?}")
+            }
+            Error::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(f, "{field} {value} is outside {min}..={max}"),
+            Error::UnsupportedFrequency {
+                inductor,
+                frequency,
+            } => write!(f, "{inductor:?} inductor is not rated for {frequency:?}"),
+            Error::InvalidLength { register, len } => {
+                write!(f, "invalid transfer length {len} at {register:?}")
+            }
+            Error::UnexpectedDevice {
+                manufacturer_id,
+                device_id,
+            } => write!(
+                f,
+                "ManufacturerID {manufacturer_id:#04x} / DeviceID {device_id:#04x} is not the expected chip"
+            ),
+            Error::VerifyFailed {
+                register,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{register:?} reads back {actual:#06x}, expected {expected:#06x}"
+            ),
+            Error::Timeout { register } => write!(f, "{register:?} bit did not self-clear"),
+            Error::NotInitialized => f.write_str("driver configuration does not match the chip"),
+            Error::MissingConfig { field } => write!(f, "{field} is not configured"),
+            Error::ConflictingMode { register } => {
+                write!(f, "operation conflicts with the mode set in {register:?}")
+            }
+            Error::CrcError { register } => write!(f, "PEC mismatch reading {register:?}"),
+            Error::Pin { pin, kind } => write!(f, "status pin {pin:?} failed: {kind:?}"),
+            Error::WouldBlock => f.write_str("shared driver is in use"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for Error<E> {}
+
+impl<E: embedded_hal::i2c::Error> embedded_hal::i2c::Error for Error<E> {
+    fn kind(&self) -> embedded_hal::i2c::ErrorKind {
+        match self {
+            Error::I2c { source, .. } => source.kind(),
+            _ => embedded_hal::i2c::ErrorKind::Other,
+        }
+    }
 }
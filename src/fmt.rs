@@ -0,0 +1,41 @@
+//! Internal logging facade.
+//!
+//! The macros here forward to `defmt`, `log` and/or `tracing`, whichever
+//! features are enabled, and compile to nothing otherwise. Format strings must
+//! stay within the syntax all three accept: positional `{}` and `{:?}` only.
+//! Arguments need `defmt::Format` as well as `Display`/`Debug`.
+
+macro_rules! log_at {
+    ($level:ident, $s:literal $(, $x:expr)* $(,)?) => {{
+        #[cfg(feature = "defmt")]
+        ::defmt::$level!($s $(, $x)*);
+        #[cfg(feature = "log")]
+        ::log::$level!($s $(, $x)*);
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!($s $(, $x)*);
+        #[cfg(not(any(feature = "defmt", feature = "log", feature = "tracing")))]
+        {
+            let _ = ($(&$x),*);
+        }
+    }};
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => { log_at!(trace, $($arg)*) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { log_at!(debug, $($arg)*) };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { log_at!(info, $($arg)*) };
+}
+
+macro_rules! warn {
+    ($($arg:tt)*) => { log_at!(warn, $($arg)*) };
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => { log_at!(error, $($arg)*) };
+}
@@ -0,0 +1,177 @@
+//! JEITA temperature-compensated charging.
+//!
+//! The BQ25730 has no thermistor input. [`JeitaProfile`] maps a battery
+//! temperature to a JEITA zone and the charge current and voltage allowed in
+//! it; `Bq25730::apply_jeita()` writes them and sets ChargeCurrent to 0 in
+//! the Cold and Hot zones.
+//!
+//! The temperature can come from anywhere. For an NTC divider on CMPIN,
+//! [`NtcDivider`] converts the CMPIN voltage with the Beta model and
+//! `Bq25730::read_ntc_temperature()` reads and converts it.
+
+use crate::units::{Celsius, MilliAmps, MilliVolts};
+
+/// JEITA temperature zones, coldest first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JeitaZone {
+    /// Below `cold_limit`: no charging.
+    Cold,
+    /// `cold_limit` to `cool_limit`.
+    Cool,
+    /// `cool_limit` to `warm_limit`.
+    Normal,
+    /// `warm_limit` to `hot_limit`.
+    Warm,
+    /// At or above `hot_limit`: no charging.
+    Hot,
+}
+
+impl JeitaZone {
+    const ALL: [JeitaZone; 5] = [
+        JeitaZone::Cold,
+        JeitaZone::Cool,
+        JeitaZone::Normal,
+        JeitaZone::Warm,
+        JeitaZone::Hot,
+    ];
+}
+
+/// Charge current and voltage allowed in a zone.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JeitaLimits {
+    pub charge_current: MilliAmps,
+    pub charge_voltage: MilliVolts,
+}
+
+/// Zone thresholds and per-zone limits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JeitaProfile {
+    pub cold_limit: Celsius,
+    pub cool_limit: Celsius,
+    pub warm_limit: Celsius,
+    pub hot_limit: Celsius,
+    /// Distance past a threshold needed to move back towards Normal.
+    pub hysteresis: Celsius,
+    pub cool: JeitaLimits,
+    pub normal: JeitaLimits,
+    pub warm: JeitaLimits,
+}
+
+impl JeitaProfile {
+    /// The common 0/10/45/60 °C profile with 2 °C hysteresis: half the
+    /// charge current when Cool and 4.1 V instead of 4.2 V per cell (scaled
+    /// to the pack) when Warm.
+    pub fn new(normal: JeitaLimits) -> Self {
+        Self {
+            cold_limit: Celsius(0),
+            cool_limit: Celsius(10),
+            warm_limit: Celsius(45),
+            hot_limit: Celsius(60),
+            hysteresis: Celsius(2),
+            cool: JeitaLimits {
+                charge_current: normal.charge_current / 2,
+                ..normal
+            },
+            normal,
+            warm: JeitaLimits {
+                charge_voltage: normal.charge_voltage - normal.charge_voltage / 42,
+                ..normal
+            },
+        }
+    }
+
+    /// Returns the zone for `temperature`.
+    ///
+    /// With the `previous` zone given, leaving Cold, Cool, Warm or Hot
+    /// towards Normal requires crossing the threshold by `hysteresis`.
+    pub fn zone(&self, temperature: Celsius, previous: Option<JeitaZone>) -> JeitaZone {
+        let bounds = [
+            self.cold_limit,
+            self.cool_limit,
+            self.warm_limit,
+            self.hot_limit,
+        ];
+        // Boundary i separates ALL[i] and ALL[i + 1].
+        let crossed = bounds
+            .iter()
+            .enumerate()
+            .filter(|&(i, &limit)| {
+                let threshold = match previous {
+                    Some(p) if i < 2 && p <= JeitaZone::ALL[i] => limit + self.hysteresis,
+                    Some(p) if i >= 2 && p > JeitaZone::ALL[i] => limit - self.hysteresis,
+                    _ => limit,
+                };
+                temperature >= threshold
+            })
+            .count();
+        JeitaZone::ALL[crossed]
+    }
+
+    /// Returns the limits for `zone`, or `None` where charging must stop.
+    pub fn limits(&self, zone: JeitaZone) -> Option<JeitaLimits> {
+        match zone {
+            JeitaZone::Cool => Some(self.cool),
+            JeitaZone::Normal => Some(self.normal),
+            JeitaZone::Warm => Some(self.warm),
+            JeitaZone::Cold | JeitaZone::Hot => None,
+        }
+    }
+}
+
+/// NTC thermistor from CMPIN to ground with a pull-up to `vref`, described
+/// by the Beta model.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NtcDivider {
+    /// Voltage the pull-up resistor is tied to.
+    pub vref: MilliVolts,
+    pub pullup_ohms: u32,
+    /// NTC resistance at 25 °C.
+    pub r25_ohms: u32,
+    /// Beta constant in kelvin.
+    pub beta: u16,
+}
+
+impl NtcDivider {
+    /// Converts the CMPIN voltage to a temperature, rounded to the nearest
+    /// degree.
+    ///
+    /// Returns `None` for an open (at or above `vref`) or shorted (0 V)
+    /// thermistor.
+    pub fn temperature(&self, cmpin: MilliVolts) -> Option<Celsius> {
+        if cmpin.0 == 0 || cmpin >= self.vref {
+            return None;
+        }
+        let v = f32::from(cmpin.0);
+        let resistance = self.pullup_ohms as f32 * v / (f32::from(self.vref.0) - v);
+        let inv_kelvin =
+            1.0 / 298.15 + ln(resistance / self.r25_ohms as f32) / f32::from(self.beta);
+        let celsius = 1.0 / inv_kelvin - 273.15;
+        let rounded = if celsius >= 0.0 {
+            celsius + 0.5
+        } else {
+            celsius - 0.5
+        };
+        Some(Celsius(rounded as i16))
+    }
+}
+
+/// Natural logarithm of a positive `x`; `core` has no float math.
+fn ln(x: f32) -> f32 {
+    // x = m * 2^e with m in [1, 2).
+    let bits = x.to_bits();
+    let e = ((bits >> 23) & 0xFF) as i32 - 127;
+    let m = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+    // ln(m) = 2 atanh(s) with s = (m - 1) / (m + 1) < 1/3.
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let atanh = s * (1.0 + s2 * (1.0 / 3.0 + s2 * (1.0 / 5.0 + s2 * (1.0 / 7.0 + s2 / 9.0))));
+    e as f32 * core::f32::consts::LN_2 + 2.0 * atanh
+}
@@ -0,0 +1,144 @@
+//! Battery learn mode, for gas-gauge learn cycles.
+//!
+//! With EN_LEARN (ChargeOption0) set the system runs from the battery even
+//! though an adapter is present. `Bq25730::start_learn_cycle()` sets it and
+//! returns a [`LearnCycle`]; the host then calls
+//! `Bq25730::step_learn_cycle()` periodically with the time elapsed since the
+//! previous call. Each step reads VBAT and IDCHG, integrates the discharge
+//! current, and clears EN_LEARN once VBAT reaches the stop voltage or a fault
+//! is latched, returning a [`LearnSummary`].
+//!
+//! The ADC must be converting (e.g. ADC_CONV continuous with EN_ADC_VBAT and
+//! EN_ADC_IDCHG) for the readings to be current.
+
+use core::time::Duration;
+
+use crate::data_types::AdcMeasurements;
+use crate::registers::ChargerStatusFaultFlags;
+use crate::units::{Micros, MilliAmps, MilliVolts};
+
+/// mA x us in one mAh.
+const MA_US_PER_MAH: u64 = 3_600_000_000;
+
+/// State of a running learn cycle, returned by `Bq25730::start_learn_cycle()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LearnCycle {
+    stop_voltage: MilliVolts,
+    start_vbat: MilliVolts,
+    last_vbat: MilliVolts,
+    last_idchg: MilliAmps,
+    /// Discharged charge in mA x us.
+    charge: u64,
+    elapsed_us: u64,
+    end: Option<LearnEndReason>,
+}
+
+impl LearnCycle {
+    /// Starts tracking a cycle from the ADC readings taken when EN_LEARN was set.
+    pub fn new(stop_voltage: MilliVolts, adc: &AdcMeasurements) -> Self {
+        Self {
+            stop_voltage,
+            start_vbat: adc.vbat.0,
+            last_vbat: adc.vbat.0,
+            last_idchg: adc.idchg.milliamps,
+            charge: 0,
+            elapsed_us: 0,
+            end: None,
+        }
+    }
+
+    /// VBAT at which the cycle ends.
+    pub fn stop_voltage(&self) -> MilliVolts {
+        self.stop_voltage
+    }
+
+    /// Returns why the cycle ended, or `None` while it is running.
+    pub fn end_reason(&self) -> Option<LearnEndReason> {
+        self.end
+    }
+
+    /// Integrates IDCHG over `elapsed` (trapezoidal, against the previous
+    /// reading) and checks the end conditions.
+    ///
+    /// Returns the end reason once the cycle has ended; later calls leave the
+    /// totals unchanged.
+    pub fn record(
+        &mut self,
+        elapsed: Micros,
+        adc: &AdcMeasurements,
+        faults: ChargerStatusFaultFlags,
+    ) -> Option<LearnEndReason> {
+        if self.end.is_some() {
+            return self.end;
+        }
+        let idchg = adc.idchg.milliamps;
+        let average = (u64::from(self.last_idchg.0) + u64::from(idchg.0)) / 2;
+        self.charge += average * u64::from(elapsed.0);
+        self.elapsed_us += u64::from(elapsed.0);
+        self.last_vbat = adc.vbat.0;
+        self.last_idchg = idchg;
+
+        self.end = if !faults.is_empty() {
+            Some(LearnEndReason::Fault(faults))
+        } else if adc.vbat.0 <= self.stop_voltage {
+            Some(LearnEndReason::StopVoltage)
+        } else {
+            None
+        };
+        self.end
+    }
+
+    /// Ends the cycle early, e.g. when the host cancels it.
+    pub fn abort(&mut self) {
+        self.end.get_or_insert(LearnEndReason::Aborted);
+    }
+
+    /// Totals so far.
+    pub fn summary(&self) -> LearnSummary {
+        LearnSummary {
+            discharged_mah: (self.charge / MA_US_PER_MAH) as u32,
+            duration: Duration::from_micros(self.elapsed_us),
+            start_vbat: self.start_vbat,
+            end_vbat: self.last_vbat,
+            end_reason: self.end,
+        }
+    }
+}
+
+/// Why a learn cycle ended.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LearnEndReason {
+    /// VBAT reached the stop voltage.
+    StopVoltage,
+    /// ChargerStatus reported these faults.
+    Fault(ChargerStatusFaultFlags),
+    /// The host ended the cycle with `Bq25730::abort_learn_cycle()`.
+    Aborted,
+}
+
+/// Discharge totals of a learn cycle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LearnSummary {
+    /// Charge drawn from the battery, rounded down.
+    pub discharged_mah: u32,
+    /// Sum of the step intervals.
+    pub duration: Duration,
+    pub start_vbat: MilliVolts,
+    /// VBAT at the last step.
+    pub end_vbat: MilliVolts,
+    /// `None` while the cycle is running.
+    pub end_reason: Option<LearnEndReason>,
+}
+
+/// Result of `Bq25730::step_learn_cycle()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LearnStatus {
+    /// EN_LEARN is still set.
+    Running { vbat: MilliVolts, idchg: MilliAmps },
+    /// EN_LEARN has been cleared.
+    Finished(LearnSummary),
+}
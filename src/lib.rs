@@ -1,27 +1,44 @@
 #![no_std]
 #[cfg(feature = "defmt")]
-extern crate defmt; // Make defmt available for derive macros
+extern crate defmt;
+#[cfg(feature = "std")]
+extern crate std; // Make defmt available for derive macros
+
+// Must come first so the logging macros are visible to the other modules.
+#[macro_use]
+mod fmt;
 
 use core::ops::{Deref, DerefMut};
 
+#[cfg(not(feature = "async"))]
+use embedded_hal::delay::DelayNs;
 #[cfg(not(feature = "async"))]
 use embedded_hal::i2c::I2c;
 #[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs;
+#[cfg(feature = "async")]
 use embedded_hal_async::i2c::I2c;
 
+pub mod coulomb;
 pub mod data_types;
+pub mod dump;
 pub mod errors;
+pub mod jeita;
+pub mod learn;
+pub mod pec;
+pub mod pins;
+pub mod protection;
+pub mod ptm;
 pub mod registers;
+#[cfg(feature = "embassy")]
+pub mod service;
+#[cfg(all(feature = "critical-section", not(feature = "async")))]
+pub mod shared;
+pub mod trace;
+pub mod units;
+pub mod variant;
 use crate::data_types::{
-    AdcCmpin,
-    AdcIchg,
-    AdcIdchg,
-    AdcIin,
     AdcMeasurements,
-    AdcPsys,
-    AdcVbat,
-    AdcVbus,
-    AdcVsys,
     // ChargeCurrentSetting, // Updated type
     ChargeOption0,
     ChargeOption1,
@@ -39,17 +56,66 @@ use crate::data_types::{
     // VsysMinSetting, // Updated type - unused direct import
     VminActiveProtection,
 };
-use crate::registers::{ChargeOption1Flags, ChargerStatusFaultFlags, ChargerStatusFlags};
-pub use data_types::{Config, SenseResistorValue};
+use crate::registers::{
+    ChargeOption0Flags, ChargeOption1Flags, ChargeOption2Flags, ChargeOption3MsbFlags,
+    ChargeOption4Flags, ChargerStatusFaultFlags, ChargerStatusFlags, PsysMode, PsysRatio,
+};
+pub use coulomb::{CoulombCounter, CoulombCounterConfig, OcvPoint};
+pub use data_types::{
+    Config, ConfigChanges, ConverterConfig, Inductor, InductorCurrentLimit, PostResetAction,
+    PsysConfig, SenseResistorValue, SwitchingFrequency,
+};
+pub use dump::{DumpParseError, DumpWarning, RegisterDump};
 pub use errors::Error;
+pub use jeita::{JeitaLimits, JeitaProfile, JeitaZone, NtcDivider};
+pub use learn::{LearnCycle, LearnEndReason, LearnStatus, LearnSummary};
+#[cfg(feature = "async")]
+pub use pins::Bq25730Pins;
+pub use pins::{NoPin, PinEvent, PinEventKind, StatusPin};
+pub use protection::{
+    FaultCounter, OvercurrentThreshold, ProtectedFault, ProtectionRecovery, ProtectionSettings,
+    RecoveryPolicy, RecoveryStep,
+};
+pub use ptm::{PassThroughEvent, PassThroughExitReason, PassThroughPolicy};
 use registers::Register; // Re-export Config and SenseResistorValue
+#[cfg(feature = "embassy")]
+pub use service::{ChargerService, Command, Telemetry};
+#[cfg(all(feature = "critical-section", not(feature = "async")))]
+pub use shared::SharedBq25730;
+pub use trace::{ReplayI2c, TracingI2c};
+pub use units::{Celsius, Micros, MilliAmps, MilliAmps32, MilliVolts, MilliWatts};
+pub use variant::ChipVariant;
 
 // SenseResistorValue enum is now defined in data_types.rs
 
 /// The default I2C address of the BQ25730 chip.
 pub const BQ25730_I2C_ADDRESS: u8 = 0x6B;
 
-/// Trait for abstracting register access, with or without CRC.
+/// ManufacturerID register value of a BQ25730.
+pub const BQ25730_MANUFACTURER_ID: u8 = 0x40;
+
+/// DeviceID register value of a BQ25730.
+pub const BQ25730_DEVICE_ID: u8 = 0xD5;
+
+/// DeviceID register value of a BQ25731.
+///
+/// Taken from the DeviceID register description in the BQ25731 datasheet;
+/// the BQ25730 datasheet in `docs/` only lists the BQ25730's D5h.
+pub const BQ25731_DEVICE_ID: u8 = 0xD6;
+
+/// Interval between reads while waiting for a self-clearing bit (RESET_REG, RESET_VINDPM).
+const SELF_CLEAR_POLL_INTERVAL_MS: u32 = 1;
+
+/// How long to wait for a self-clearing bit before giving up. RESET_VINDPM
+/// keeps the converter off while VBUS is measured, which takes far longer
+/// than a register reset.
+const SELF_CLEAR_TIMEOUT_MS: u32 = 100;
+
+/// Trait for abstracting register access.
+///
+/// `Bq25730` is the only implementation. Whether its transfers carry an
+/// SMBus PEC byte is a runtime flag chosen at construction
+/// ([`Bq25730::new_with_pec`]), not a separate implementation.
 #[maybe_async_cfg::maybe(
     sync(cfg(not(feature = "async")), self = "RegisterAccess",),
     async(feature = "async", keep_self)
@@ -88,9 +154,16 @@ where
     address: u8,
     pub i2c: I2C,
     config: Config, // Replaced cell_count, rsns_bat, rsns_ac
+    config_stale: bool,
+    /// Fault flags seen by the last `read_charger_status()`, used to log transitions.
+    faults: ChargerStatusFaultFlags,
+    /// Append and check an SMBus PEC byte on every transfer.
+    pec: bool,
+    /// Chip the range checks and ADC scaling are taken from.
+    variant: ChipVariant,
 }
 
-/// Trait for abstracting register access, with or without CRC.
+/// Constructors and accessors; `new_with_pec()` selects SMBus PEC.
 #[maybe_async_cfg::maybe(
     sync(cfg(not(feature = "async")), self = "Bq25730",),
     async(feature = "async", keep_self)
@@ -111,14 +184,52 @@ where
             address,
             i2c,
             config,
+            config_stale: false,
+            faults: ChargerStatusFaultFlags::empty(),
+            pec: false,
+            variant: ChipVariant::Bq25730,
+        }
+    }
+
+    /// Creates a driver that appends an SMBus PEC byte to every write and
+    /// checks the PEC byte of every read (see the `pec` module).
+    ///
+    /// A read whose PEC does not match fails with `Error::CrcError`.
+    pub fn new_with_pec(i2c: I2C, address: u8, config: Config) -> Self {
+        Self {
+            pec: true,
+            ..Self::new(i2c, address, config)
         }
     }
 
+    /// Returns `true` if transfers carry an SMBus PEC byte.
+    pub fn pec_enabled(&self) -> bool {
+        self.pec
+    }
+
+    /// Returns the chip variant used for range checks and ADC scaling.
+    pub fn variant(&self) -> ChipVariant {
+        self.variant
+    }
+
+    /// Selects the chip variant without reading the ID registers; see
+    /// `read_device_id()` for automatic detection.
+    pub fn set_variant(&mut self, variant: ChipVariant) {
+        self.variant = variant;
+    }
+
     /// Returns the I2C address of the BQ25730 chip.
     pub fn address(&self) -> u8 {
         self.address
     }
 
+    /// Returns `true` if the chip registers no longer match the cached
+    /// configuration, e.g. after `reset_registers(PostResetAction::InvalidateConfig)`.
+    /// Cleared by `init()` and `apply_config()`.
+    pub fn is_config_stale(&self) -> bool {
+        self.config_stale
+    }
+
     /// Returns a reference to the current configuration.
     pub fn config(&self) -> &Config {
         &self.config
@@ -132,7 +243,8 @@ where
     }
 }
 
-/// Trait for abstracting register access, with or without CRC.
+/// Register access, appending and checking an SMBus PEC byte when the
+/// driver was created with `new_with_pec()`.
 #[maybe_async_cfg::maybe(
     sync(cfg(not(feature = "async")), self = "Bq25730",),
     async(feature = "async", keep_self)
@@ -144,12 +256,7 @@ where
     type ReadBuffer = heapless::Vec<u8, 30>;
 
     async fn read_register(&mut self, reg: Register) -> Result<u8, Error<E>> {
-        let mut data = [0u8; 1];
-        self.i2c
-            .write_read(self.address, &[reg as u8], &mut data)
-            .await
-            .map_err(Error::I2c)?;
-        Ok(data[0])
+        Ok(self.read_registers(reg, 1).await?[0])
     }
 
     async fn read_registers(
@@ -158,48 +265,81 @@ where
         len: usize,
     ) -> Result<Self::ReadBuffer, Error<E>> {
         if len == 0 || len > 30 {
-            #[cfg(feature = "defmt")]
-            defmt::error!("Invalid read length: {}", len);
-            return Err(Error::InvalidData);
+            error!("Invalid read length: {}", len);
+            return Err(Error::InvalidLength { register: reg, len });
         }
 
-        let mut buffer: heapless::Vec<u8, 30> = heapless::Vec::new();
-        buffer.resize(len, 0).map_err(|_| Error::InvalidData)?;
+        // One extra byte for the PEC, when enabled.
+        let mut buffer: heapless::Vec<u8, 31> = heapless::Vec::new();
+        buffer
+            .resize(len + self.pec as usize, 0)
+            .map_err(|_| Error::InvalidLength { register: reg, len })?;
 
         self.i2c
             .write_read(self.address, &[reg as u8], &mut buffer)
             .await
-            .map_err(Error::I2c)?;
+            .map_err(|source| Error::I2c {
+                register: reg,
+                source,
+            })?;
+
+        if self.pec {
+            let received = buffer[len];
+            let expected = pec::read_pec(self.address, reg as u8, &buffer[..len]);
+            if received != expected {
+                error!(
+                    "PEC mismatch reading {:?}: got {}, expected {}",
+                    reg, received, expected
+                );
+                return Err(Error::CrcError { register: reg });
+            }
+        }
 
-        Ok(buffer)
+        let mut data = Self::ReadBuffer::new();
+        data.extend_from_slice(&buffer[..len])
+            .map_err(|_| Error::InvalidLength { register: reg, len })?;
+        Ok(data)
     }
 
     async fn write_register(&mut self, reg: Register, value: u8) -> Result<(), Error<E>> {
-        self.i2c
-            .write(self.address, &[reg as u8, value])
-            .await
-            .map_err(Error::I2c)
+        self.write_registers(reg, &[value]).await
     }
 
     async fn write_registers(&mut self, reg: Register, values: &[u8]) -> Result<(), Error<E>> {
         if values.is_empty() || values.len() > 30 {
-            #[cfg(feature = "defmt")]
-            defmt::error!("Invalid write length: {}", values.len());
-            return Err(Error::InvalidData);
+            error!("Invalid write length: {}", values.len());
+            return Err(Error::InvalidLength {
+                register: reg,
+                len: values.len(),
+            });
         }
 
-        let mut data_to_write = heapless::Vec::<u8, 31>::new();
+        let invalid_length = || Error::InvalidLength {
+            register: reg,
+            len: values.len(),
+        };
+        // Register, up to 30 data bytes and the PEC.
+        let mut data_to_write = heapless::Vec::<u8, 32>::new();
         data_to_write
             .push(reg as u8)
-            .map_err(|_| Error::InvalidData)?;
+            .map_err(|_| invalid_length())?;
         data_to_write
             .extend_from_slice(values)
-            .map_err(|_| Error::InvalidData)?;
+            .map_err(|_| invalid_length())?;
+        if self.pec {
+            data_to_write
+                .push(pec::write_pec(self.address, reg as u8, values))
+                .map_err(|_| invalid_length())?;
+        }
+        trace!("write {:?} = {:?}", reg, values);
 
         self.i2c
             .write(self.address, &data_to_write)
             .await
-            .map_err(Error::I2c)
+            .map_err(|source| Error::I2c {
+                register: reg,
+                source,
+            })
     }
 
     async fn write_registers_bulk(&mut self, values: &[(Register, u8)]) -> Result<(), Error<E>> {
@@ -223,10 +363,17 @@ where
     Self: RegisterAccess<E>,
 {
     /// Initializes the BQ25730 charger using the provided configuration.
+    ///
+    /// Returns `Error::OutOfRange` without writing anything if a setting does
+    /// not fit its register field, or `Error::UnsupportedFrequency` if the
+    /// converter switching frequency does not suit the fitted inductor.
     pub async fn init(&mut self) -> Result<(), Error<E>> {
+        check_config(&self.config, self.variant)?;
+        debug!("init: charge and OTG limits");
         // Write ChargeOption0, ChargeCurrent, ChargeVoltage, OTGVoltage, and OTGCurrent (Registers 0x00-0x09)
         // These are contiguous registers.
-        let charge_option0_bytes = self.config.charge_option0.to_msb_lsb_bytes();
+        let (charge_option0, charge_option3, charge_option4) = self.config.option_registers();
+        let charge_option0_bytes = charge_option0.to_msb_lsb_bytes();
         let (cc_lsb, cc_msb) = self.config.charge_current.to_msb_lsb_bytes();
         let (cv_lsb, cv_msb) = self.config.charge_voltage.to_msb_lsb_bytes();
         let (otg_v_lsb, otg_v_msb) = self.config.otg_voltage.to_msb_lsb_bytes();
@@ -250,12 +397,14 @@ where
         .await?;
 
         // Write ChargeOption1 (Registers 0x30-0x31) - Not contiguous with the previous block
+        debug!("init: ChargeOption1");
         let (lsb_co1, msb_co1) = self.config.charge_option1.to_msb_lsb_bytes();
         self.write_registers(Register::ChargeOption1, &[lsb_co1, msb_co1])
             .await?;
 
         // Group 2: InputVoltage, VsysMin, IinHost (Registers 0x0A-0x0F)
         // These are contiguous registers.
+        debug!("init: input and VSYS_MIN limits");
         let (iv_lsb, iv_msb) = self.config.input_voltage.to_msb_lsb_bytes();
         let (vm_lsb, vm_msb) = self.config.vsys_min.to_msb_lsb_bytes();
         let (ih_lsb, ih_msb) = self.config.iin_host.to_msb_lsb_bytes(self.config.rsns_ac);
@@ -273,13 +422,15 @@ where
         .await?;
 
         // Write ChargeOption3 (Registers 0x34-0x35)
-        let (lsb_co3, msb_co3) = self.config.charge_option3.to_msb_lsb_bytes();
+        debug!("init: ChargeOption3");
+        let (lsb_co3, msb_co3) = charge_option3.to_msb_lsb_bytes();
         self.write_registers(Register::ChargeOption3, &[lsb_co3, msb_co3])
             .await?;
 
         // Write ChargeOption4 and VminActiveProtection (Registers 0x3C-0x3F)
         // These are contiguous registers.
-        let (lsb_co4, msb_co4) = self.config.charge_option4.to_msb_lsb_bytes();
+        debug!("init: ChargeOption4 and VminActiveProtection");
+        let (lsb_co4, msb_co4) = charge_option4.to_msb_lsb_bytes();
         let (lsb_vmin, msb_vmin) = self.config.vmin_active_protection.to_msb_lsb_bytes();
         self.write_registers(
             Register::ChargeOption4,
@@ -293,25 +444,204 @@ where
         .await?;
 
         // Clear SYSOVP and VSYS_UVP faults from ChargerStatus
+        debug!("init: clearing SYSOVP and VSYS_UVP faults");
         let mut charger_status = self.read_charger_status().await?;
-        charger_status.fault_flags.remove(
-            registers::ChargerStatusFaultFlags::FAULT_SYSOVP
-                | registers::ChargerStatusFaultFlags::FAULT_VSYS_UVP,
-        );
+        let latched = charger_status.fault_flags
+            & (registers::ChargerStatusFaultFlags::FAULT_SYSOVP
+                | registers::ChargerStatusFaultFlags::FAULT_VSYS_UVP);
+        if !latched.is_empty() {
+            warn!("init: clearing latched {}", latched);
+        }
+        charger_status.fault_flags.remove(latched);
         self.set_charger_status(charger_status).await?;
 
+        self.config_stale = false;
+        info!("init: done");
         Ok(())
     }
 
+    /// Pushes `new_config` to the chip, writing only the registers that differ
+    /// from the cached configuration.
+    ///
+    /// Writes are ordered so the charger never runs a raised current limit
+    /// against a stale voltage target: current limits that go down are written
+    /// first, then option and voltage registers, and current limits that go up
+    /// are written last.
+    ///
+    /// If the cached configuration is stale (see `is_config_stale()`), every
+    /// configuration register is written and all current limits are treated as
+    /// going up, since the chip is at its reset values. `new_config` is range-checked first,
+    /// as in `init()`.
+    ///
+    /// On success the cached configuration is replaced by `new_config` and the
+    /// set of written registers is returned. If a write fails, the cached
+    /// configuration is left untouched and the chip may be partially updated.
+    pub async fn apply_config(&mut self, new_config: &Config) -> Result<ConfigChanges, Error<E>> {
+        check_config(new_config, self.variant)?;
+        let changes = if self.config_stale {
+            ConfigChanges::all()
+        } else {
+            self.config.diff(new_config)
+        };
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+
+        let current_limits =
+            ConfigChanges::CHARGE_CURRENT | ConfigChanges::OTG_CURRENT | ConfigChanges::IIN_HOST;
+        let mut decreasing = ConfigChanges::empty();
+        // A stale cache says nothing about the chip, which is back at its
+        // reset values; treat every current limit as going up.
+        if !self.config_stale {
+            decreasing.set(
+                ConfigChanges::CHARGE_CURRENT,
+                new_config.charge_current.milliamps < self.config.charge_current.milliamps,
+            );
+            decreasing.set(
+                ConfigChanges::OTG_CURRENT,
+                new_config.otg_current.milliamps < self.config.otg_current.milliamps,
+            );
+            decreasing.set(
+                ConfigChanges::IIN_HOST,
+                new_config.iin_host.milliamps < self.config.iin_host.milliamps,
+            );
+        }
+        let decreasing = changes & decreasing;
+        let increasing = changes & current_limits & !decreasing;
+        let others = changes & !current_limits;
+
+        let values = new_config.register_values();
+        for phase in [decreasing, others, increasing] {
+            for &(reg, lsb, msb) in values.iter() {
+                if phase.intersects(ConfigChanges::from_register(reg)) {
+                    self.write_registers(reg, &[lsb, msb]).await?;
+                }
+            }
+        }
+
+        self.config = *new_config;
+        self.config_stale = false;
+        Ok(changes)
+    }
+
+    /// Reads the configuration registers (0x00-0x0F and 0x30-0x3F) and decodes
+    /// them into a `Config`.
+    ///
+    /// The sense resistor values are taken from ChargeOption1, not from the
+    /// cached configuration. The cached configuration is not modified; use
+    /// `config_mut()` to adopt the returned value, e.g. after a warm MCU reset.
+    /// Board-level settings that have no register (`psys`) are copied from
+    /// the cached configuration.
+    pub async fn read_config(&mut self) -> Result<Config, Error<E>> {
+        let mut low = [0u8; 16];
+        low.copy_from_slice(&self.read_registers(Register::ChargeOption0, 16).await?);
+        let mut high = [0u8; 16];
+        high.copy_from_slice(&self.read_registers(Register::ChargeOption1, 16).await?);
+        let mut config = Config::from_registers(&low, &high);
+        config.psys = self.config.psys;
+        Ok(config)
+    }
+
+    /// Reads the configuration registers back and compares them with the
+    /// cached configuration.
+    ///
+    /// Returns `Error::NotInitialized` while the cache is stale and
+    /// `Error::VerifyFailed` for the first register that differs.
+    pub async fn verify_config(&mut self) -> Result<(), Error<E>> {
+        if self.config_stale {
+            return Err(Error::NotInitialized);
+        }
+        let chip = self.read_config().await?;
+        for (&(register, lsb, msb), &(_, chip_lsb, chip_msb)) in self
+            .config
+            .register_values()
+            .iter()
+            .zip(chip.register_values().iter())
+        {
+            if (lsb, msb) != (chip_lsb, chip_msb) {
+                return Err(Error::VerifyFailed {
+                    register,
+                    expected: u16::from_le_bytes([lsb, msb]),
+                    actual: u16::from_le_bytes([chip_lsb, chip_msb]),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks ManufacturerID and DeviceID, returning `Error::UnexpectedDevice`
+    /// if the chip at `address` is not the selected `variant()`.
+    pub async fn verify_device_id(&mut self) -> Result<(), Error<E>> {
+        let (manufacturer_id, device_id) = self.read_ids().await?;
+        if manufacturer_id != self.variant.manufacturer_id()
+            || device_id != self.variant.device_id()
+        {
+            return Err(Error::UnexpectedDevice {
+                manufacturer_id,
+                device_id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Reads ManufacturerID and DeviceID and selects the matching variant.
+    ///
+    /// Returns `Error::UnexpectedDevice`, leaving the selected variant
+    /// unchanged, if the chip is not a supported member of the family.
+    pub async fn read_device_id(&mut self) -> Result<ChipVariant, Error<E>> {
+        let (manufacturer_id, device_id) = self.read_ids().await?;
+        let variant =
+            ChipVariant::from_ids(manufacturer_id, device_id).ok_or(Error::UnexpectedDevice {
+                manufacturer_id,
+                device_id,
+            })?;
+        if variant != self.variant {
+            info!("Detected {}", variant.name());
+        }
+        self.variant = variant;
+        Ok(variant)
+    }
+
+    async fn read_ids(&mut self) -> Result<(u8, u8), Error<E>> {
+        let ids = self.read_registers(Register::ManufacturerID, 2).await?;
+        Ok((ids[0], ids[1]))
+    }
+
+    /// Reads every register defined in `Register` into a `RegisterDump`.
+    pub async fn dump_registers(&mut self) -> Result<RegisterDump, Error<E>> {
+        let mut blocks = [[0u8; 16]; 3];
+        let starts = [
+            Register::ChargeOption0,
+            Register::ChargerStatus,
+            Register::ChargeOption1,
+        ];
+        for (block, start) in blocks.iter_mut().zip(starts) {
+            block.copy_from_slice(&self.read_registers(start, 16).await?);
+        }
+        Ok(RegisterDump::from_blocks(
+            &blocks[0], &blocks[1], &blocks[2],
+        ))
+    }
+
     /// Reads the Charger Status register.
     pub async fn read_charger_status(&mut self) -> Result<ChargerStatus, Error<E>> {
         let raw_status = self.read_registers(Register::ChargerStatus, 2).await?;
         let lsb = raw_status.as_ref()[0];
         let msb = raw_status.as_ref()[1];
-        Ok(ChargerStatus {
+        let status = ChargerStatus {
             status_flags: ChargerStatusFlags::from_bits_truncate(msb),
             fault_flags: ChargerStatusFaultFlags::from_bits_truncate(lsb),
-        })
+        };
+        let raised = status.fault_flags.difference(self.faults);
+        let cleared = self.faults.difference(status.fault_flags);
+        if !raised.is_empty() {
+            warn!("Charger fault raised: {}", raised);
+        }
+        if !cleared.is_empty() {
+            info!("Charger fault cleared: {}", cleared);
+        }
+        self.faults = status.fault_flags;
+        Ok(status)
     }
 
     /// Sets the Charger Status register.
@@ -332,43 +662,15 @@ where
     }
     /// Reads all ADC measurement registers.
     pub async fn read_adc_measurements(&mut self) -> Result<AdcMeasurements, Error<E>> {
-        // Determine ADC offset based on cell count from config (if available and reliable)
-        // For now, assuming a fixed cell count or a way to get it.
-        // The `Config` struct itself doesn't store cell_count directly,
-        // but its default values are derived from it.
-        // This part might need adjustment if cell_count isn't fixed or passed differently.
-        // For simplicity, let's assume a default or a way to access it.
-        // A placeholder for cell_count logic:
-        let cell_count_for_adc = 4; // Placeholder, ideally from config or a reliable source
-
-        let offset_mv = match cell_count_for_adc {
-            1..=4 => 2880,
-            5 => 8160,
-            _ => {
-                #[cfg(feature = "defmt")]
-                defmt::warn!(
-                    "Unsupported cell count for ADC offset: {}. Using 2.88V offset.",
-                    cell_count_for_adc
-                );
-                2880
-            }
-        };
         let adc_data_raw = self.read_registers(Register::ADCPSYS, 8).await?;
-        Ok(AdcMeasurements {
-            vbat: AdcVbat::from_register_value(
-                adc_data_raw.as_ref()[6], // LSB for ADCVBAT is 0x2C
-                adc_data_raw.as_ref()[7], // MSB for ADCVBAT is 0x2D
-                offset_mv,
-            ),
-            // ADCVSYS is also at 0x2D (MSB) and 0x2C (LSB, though LSB is often 0 for 8-bit ADCs in 2-byte reads)
-            psys: AdcPsys::from_u8(adc_data_raw.as_ref()[0]), // ADCPSYS at 0x26
-            vbus: AdcVbus::from_u8(adc_data_raw.as_ref()[1]), // ADCVBUS at 0x27
-            idchg: AdcIdchg::from_raw(adc_data_raw.as_ref()[2], self.config.rsns_bat), // ADCIDCHG at 0x28
-            ichg: AdcIchg::from_raw(adc_data_raw.as_ref()[3], self.config.rsns_bat), // ADCICHG at 0x29
-            cmpin: AdcCmpin::from_u8(adc_data_raw.as_ref()[4]), // ADCCMPIN at 0x2A
-            iin: AdcIin::from_raw(adc_data_raw.as_ref()[5], self.config.rsns_ac), // ADCIIN at 0x2B
-            vsys: AdcVsys::from_register_value(0, adc_data_raw.as_ref()[7], offset_mv), // Assuming MSB is at index 7 (0x2D)
-        })
+        let mut block = [0u8; 8];
+        block.copy_from_slice(&adc_data_raw);
+        Ok(AdcMeasurements::from_adc_block(
+            &block,
+            self.config.rsns_bat,
+            self.config.rsns_ac,
+            data_types::ADC_OFFSET_MV,
+        ))
     }
 
     /// Sets the ADCOption register.
@@ -389,6 +691,68 @@ where
         ])))
     }
 
+    /// Reads the PSYS ADC channel and converts it into system power.
+    ///
+    /// Uses the resistor in `config.psys`, the PSYS_CONFIG and PSYS_RATIO bits
+    /// read from ChargeOption1, and ADC_FULLSCALE from ADCOption. Returns 0 mW
+    /// while PSYS is disabled, and `Error::MissingConfig` if no PSYS resistor
+    /// is configured. The ADC must be running with EN_ADC_PSYS set.
+    pub async fn read_system_power_mw(&mut self) -> Result<MilliWatts, Error<E>> {
+        let psys = self.config.psys;
+        if psys.resistor_ohms.is_none_or(|r| r == 0) {
+            return Err(Error::MissingConfig {
+                field: "psys.resistor_ohms",
+            });
+        }
+
+        let charge_option1 = self.read_charge_option1().await?;
+        if !charge_option1.msb_flags.get_psys_mode().is_enabled() {
+            return Ok(MilliWatts::ZERO);
+        }
+        let ratio = charge_option1.msb_flags.get_psys_ratio();
+
+        let adc_option = self.read_adc_option().await?;
+        let lsb_mv = if adc_option
+            .msb_flags
+            .contains(registers::AdcOptionMsbFlags::ADC_FULLSCALE)
+        {
+            12
+        } else {
+            8
+        };
+        let raw = self.read_registers(Register::ADCPSYS, 1).await?;
+        let vpsys = MilliVolts(raw[0] as u16 * lsb_mv);
+
+        psys.power_from_voltage(vpsys, ratio)
+            .ok_or(Error::MissingConfig {
+                field: "psys.resistor_ohms",
+            })
+    }
+
+    /// Sets PSYS_CONFIG, selecting which power paths PSYS reports.
+    pub async fn set_psys_mode(&mut self, mode: PsysMode) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option1().await?;
+        options.msb_flags.set_psys_mode(mode);
+        self.set_charge_option1(options).await
+    }
+
+    /// Sets PSYS_RATIO, the PSYS output current per watt of system power.
+    pub async fn set_psys_ratio(&mut self, ratio: PsysRatio) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option1().await?;
+        options.msb_flags.set_psys_ratio(ratio);
+        self.set_charge_option1(options).await
+    }
+
+    /// Sets PSYS_OTG_IDCHG. When `battery_only` is true, PSYS reports battery
+    /// discharge power only in OTG mode, instead of discharge minus OTG output power.
+    pub async fn set_psys_otg_idchg(&mut self, battery_only: bool) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option3().await?;
+        options
+            .lsb_flags
+            .set(registers::ChargeOption3Flags::PSYS_OTG_IDCHG, battery_only);
+        self.set_charge_option3(options).await
+    }
+
     /// Reads the Charge Current register and returns the setting.
     pub async fn read_charge_current_setting(
         &mut self,
@@ -407,6 +771,7 @@ where
         &mut self,
         current: data_types::ChargeCurrentSetting,
     ) -> Result<(), Error<E>> {
+        check_charge_current(&current)?;
         let raw_value = current.to_raw();
         self.write_registers(Register::ChargeCurrent, &raw_value.to_le_bytes())
             .await
@@ -430,6 +795,7 @@ where
         &mut self,
         voltage: data_types::ChargeVoltageSetting,
     ) -> Result<(), Error<E>> {
+        check_charge_voltage(&voltage, self.variant)?;
         let raw_value = voltage.to_raw();
         self.write_registers(Register::ChargeVoltage, &raw_value.to_le_bytes())
             .await
@@ -450,6 +816,7 @@ where
         &mut self,
         voltage: data_types::OtgVoltageSetting,
     ) -> Result<(), Error<E>> {
+        check_otg_voltage(&voltage)?;
         let raw_value = voltage.to_raw();
         self.write_registers(Register::OTGVoltage, &raw_value.to_le_bytes())
             .await
@@ -475,6 +842,7 @@ where
         &mut self,
         current: data_types::OtgCurrentSetting,
     ) -> Result<(), Error<E>> {
+        check_otg_current(&current)?;
         let raw_7bit_current = current.to_raw();
         // Read the current MSB value to preserve other bits (e.g., bit 7 if it's used for something else, though datasheet says reserved)
         let mut msb_val = self.read_register(Register::OTGCurrentMsb).await?;
@@ -501,6 +869,7 @@ where
         &mut self,
         voltage: data_types::InputVoltageSetting,
     ) -> Result<(), Error<E>> {
+        check_input_voltage(&voltage)?;
         let raw_value = voltage.to_raw();
         self.write_registers(Register::InputVoltage, &raw_value.to_le_bytes())
             .await
@@ -519,6 +888,7 @@ where
         &mut self,
         voltage: data_types::VsysMinSetting,
     ) -> Result<(), Error<E>> {
+        check_vsys_min(&voltage)?;
         let raw_value = voltage.to_raw();
         self.write_registers(Register::VsysMin, &raw_value.to_le_bytes())
             .await
@@ -540,6 +910,7 @@ where
         &mut self,
         current: data_types::IinHostSetting,
     ) -> Result<(), Error<E>> {
+        check_iin_host(&current, self.config.rsns_ac)?;
         let raw_value = current.to_raw(self.config.rsns_ac);
         self.write_registers(Register::IinHost, &raw_value.to_le_bytes())
             .await
@@ -554,6 +925,11 @@ where
 
     /// Writes the IIN_DPM register with the value in mA.
     pub async fn set_iin_dpm(&mut self, current: IinDpm) -> Result<(), Error<E>> {
+        Error::check_range(
+            "iin_dpm",
+            current.milliamps,
+            IinDpm::valid_range(current.rsns_ac),
+        )?;
         let raw_7bit = current.to_raw();
         let mut msb_val = self.read_register(Register::IinDpmMsb).await?;
         msb_val &= !0x7F;
@@ -641,6 +1017,44 @@ where
         ])))
     }
 
+    /// Sets the ProchotOption0 register.
+    pub async fn set_prochot_option0(
+        &mut self,
+        options: data_types::ProchotOption0,
+    ) -> Result<(), Error<E>> {
+        let raw_value = options.to_u16();
+        self.write_registers(Register::ProchotOption0, &raw_value.to_le_bytes())
+            .await
+    }
+
+    /// Reads the ProchotOption0 register.
+    pub async fn read_prochot_option0(&mut self) -> Result<data_types::ProchotOption0, Error<E>> {
+        let raw_options = self.read_registers(Register::ProchotOption0, 2).await?;
+        Ok(data_types::ProchotOption0::from_u16(u16::from_le_bytes([
+            raw_options.as_ref()[0],
+            raw_options.as_ref()[1],
+        ])))
+    }
+
+    /// Sets the ProchotOption1 register.
+    pub async fn set_prochot_option1(
+        &mut self,
+        options: data_types::ProchotOption1,
+    ) -> Result<(), Error<E>> {
+        let raw_value = options.to_u16();
+        self.write_registers(Register::ProchotOption1, &raw_value.to_le_bytes())
+            .await
+    }
+
+    /// Reads the ProchotOption1 register.
+    pub async fn read_prochot_option1(&mut self) -> Result<data_types::ProchotOption1, Error<E>> {
+        let raw_options = self.read_registers(Register::ProchotOption1, 2).await?;
+        Ok(data_types::ProchotOption1::from_u16(u16::from_le_bytes([
+            raw_options.as_ref()[0],
+            raw_options.as_ref()[1],
+        ])))
+    }
+
     /// Enters ship mode.
     /// This function sets the SHIP_MODE bit in ChargeOption1 register.
     pub async fn enter_ship_mode(&mut self) -> Result<(), Error<E>> {
@@ -656,6 +1070,83 @@ where
         Ok(())
     }
 
+    /// Resets all registers to their power-on defaults (except VINDPM) via
+    /// RESET_REG and waits up to 100 ms for the bit to self-clear, polling
+    /// every millisecond with `delay`.
+    ///
+    /// With `PostResetAction::Reinitialize` the cached configuration is written
+    /// back using `init()`. With `PostResetAction::InvalidateConfig` the cache
+    /// is only marked stale; see `is_config_stale()`.
+    pub async fn reset_registers(
+        &mut self,
+        action: PostResetAction,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<E>> {
+        self.trigger_charge_option3_msb(ChargeOption3MsbFlags::RESET_REG, delay)
+            .await?;
+        match action {
+            PostResetAction::Reinitialize => self.init().await,
+            PostResetAction::InvalidateConfig => {
+                self.config_stale = true;
+                Ok(())
+            }
+        }
+    }
+
+    /// Re-detects the VINDPM threshold via RESET_VINDPM and returns it.
+    ///
+    /// The converter is briefly disabled while the chip measures VBUS; this
+    /// waits up to 100 ms for the measurement, polling every millisecond with
+    /// `delay`. The cached configuration is updated with the new input
+    /// voltage setting.
+    pub async fn remeasure_vindpm(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<data_types::InputVoltageSetting, Error<E>> {
+        self.trigger_charge_option3_msb(ChargeOption3MsbFlags::RESET_VINDPM, delay)
+            .await?;
+        let input_voltage = self.read_input_voltage_setting().await?;
+        self.config.input_voltage = input_voltage;
+        Ok(input_voltage)
+    }
+
+    /// Sets a self-clearing ChargeOption3 MSB bit and polls until the chip clears it.
+    async fn trigger_charge_option3_msb(
+        &mut self,
+        flag: ChargeOption3MsbFlags,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<E>> {
+        let mut charge_option3 = self.read_charge_option3().await?;
+        if flag == ChargeOption3MsbFlags::RESET_VINDPM
+            && charge_option3
+                .msb_flags
+                .contains(ChargeOption3MsbFlags::EN_OTG)
+        {
+            // VBUS is driven by the charger itself in OTG mode.
+            return Err(Error::ConflictingMode {
+                register: Register::ChargeOption3,
+            });
+        }
+        charge_option3.msb_flags.insert(flag);
+        self.set_charge_option3(charge_option3).await?;
+
+        let mut waited_ms = 0;
+        loop {
+            if !self.read_charge_option3().await?.msb_flags.contains(flag) {
+                return Ok(());
+            }
+            if waited_ms >= SELF_CLEAR_TIMEOUT_MS {
+                break;
+            }
+            delay.delay_ms(SELF_CLEAR_POLL_INTERVAL_MS).await;
+            waited_ms += SELF_CLEAR_POLL_INTERVAL_MS;
+        }
+        error!("ChargeOption3 bit did not self-clear: {}", flag);
+        Err(Error::Timeout {
+            register: Register::ChargeOption3,
+        })
+    }
+
     /// Sets the VminActiveProtection register.
     pub async fn set_vmin_active_protection(
         &mut self,
@@ -693,27 +1184,33 @@ where
     }
 
     /// Sets the VBUS_VAP_TH value from voltage in mV.
-    pub async fn set_vbus_vap_th_mv(&mut self, voltage_mv: u16) -> Result<(), Error<E>> {
+    pub async fn set_vbus_vap_th_mv(&mut self, voltage_mv: MilliVolts) -> Result<(), Error<E>> {
+        Error::check_range(
+            "vbus_vap_th",
+            voltage_mv,
+            MilliVolts(3200)..=MilliVolts(15900),
+        )?;
         let mut vmin_prot = self.read_vmin_active_protection().await?;
         vmin_prot.set_vbus_vap_th_mv(voltage_mv);
         self.set_vmin_active_protection(vmin_prot).await
     }
 
     /// Gets the VBUS_VAP_TH value in mV.
-    pub async fn get_vbus_vap_th_mv(&mut self) -> Result<u16, Error<E>> {
+    pub async fn get_vbus_vap_th_mv(&mut self) -> Result<MilliVolts, Error<E>> {
         let vmin_prot = self.read_vmin_active_protection().await?;
         Ok(vmin_prot.vbus_vap_th_mv())
     }
 
     /// Sets the VSYS_TH2 value from voltage in mV (assuming 2s-5s mode).
-    pub async fn set_vsys_th2_mv(&mut self, voltage_mv: u16) -> Result<(), Error<E>> {
+    pub async fn set_vsys_th2_mv(&mut self, voltage_mv: MilliVolts) -> Result<(), Error<E>> {
+        Error::check_range("vsys_th2", voltage_mv, MilliVolts(3200)..=MilliVolts(9500))?;
         let mut vmin_prot = self.read_vmin_active_protection().await?;
         vmin_prot.set_vsys_th2_mv(voltage_mv);
         self.set_vmin_active_protection(vmin_prot).await
     }
 
     /// Gets the VSYS_TH2 value in mV (assuming 2s-5s mode).
-    pub async fn get_vsys_th2_mv(&mut self) -> Result<u16, Error<E>> {
+    pub async fn get_vsys_th2_mv(&mut self) -> Result<MilliVolts, Error<E>> {
         let vmin_prot = self.read_vmin_active_protection().await?;
         Ok(vmin_prot.vsys_th2_mv())
     }
@@ -738,6 +1235,20 @@ where
         Ok(vmin_prot.en_vsysth2_follow_vsysth1())
     }
 
+    /// Enables OTG mode by setting EN_OTG.
+    pub async fn enable_otg(&mut self) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option3().await?;
+        options.msb_flags.insert(ChargeOption3MsbFlags::EN_OTG);
+        self.set_charge_option3(options).await
+    }
+
+    /// Disables OTG mode by clearing EN_OTG.
+    pub async fn disable_otg(&mut self) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option3().await?;
+        options.msb_flags.remove(ChargeOption3MsbFlags::EN_OTG);
+        self.set_charge_option3(options).await
+    }
+
     /// Enables the EN_FRS bit.
     pub async fn enable_frs(&mut self) -> Result<(), Error<E>> {
         let mut vmin_prot = self.read_vmin_active_protection().await?;
@@ -757,4 +1268,451 @@ where
         let vmin_prot = self.read_vmin_active_protection().await?;
         Ok(vmin_prot.en_frs())
     }
+
+    /// Requests Pass Through Mode by setting EN_PTM.
+    pub async fn enter_ptm(&mut self) -> Result<(), Error<E>> {
+        let options = self.read_charge_option1().await?;
+        self.write_en_ptm(options, true).await
+    }
+
+    /// Leaves Pass Through Mode by clearing EN_PTM.
+    pub async fn exit_ptm(&mut self) -> Result<(), Error<E>> {
+        let options = self.read_charge_option1().await?;
+        self.write_en_ptm(options, false).await
+    }
+
+    /// Returns STAT_PTM: whether the converter is actually in Pass Through Mode.
+    pub async fn ptm_active(&mut self) -> Result<bool, Error<E>> {
+        let options = self.read_charge_option4().await?;
+        Ok(options.lsb_flags.contains(ChargeOption4Flags::STAT_PTM))
+    }
+
+    /// Reads ChargerStatus and the ADC results, applies `policy` to EN_PTM and
+    /// returns the transition made, if any.
+    pub async fn run_pass_through_policy(
+        &mut self,
+        policy: &PassThroughPolicy,
+    ) -> Result<Option<PassThroughEvent>, Error<E>> {
+        let status = self.read_charger_status().await?;
+        let adc = self.read_adc_measurements().await?;
+        let options = self.read_charge_option1().await?;
+        let enabled = options.lsb_flags.contains(ChargeOption1Flags::EN_PTM);
+        let event = policy.evaluate(enabled, &adc, status.fault_flags);
+        if let Some(event) = event {
+            self.write_en_ptm(options, event == PassThroughEvent::Entered)
+                .await?;
+            info!("PTM: {}", event);
+        }
+        Ok(event)
+    }
+
+    async fn write_en_ptm(
+        &mut self,
+        mut options: ChargeOption1,
+        enable: bool,
+    ) -> Result<(), Error<E>> {
+        options.lsb_flags.set(ChargeOption1Flags::EN_PTM, enable);
+        self.set_charge_option1(options).await
+    }
+
+    /// Sets EN_LEARN so the system discharges the battery with the adapter
+    /// present, and starts tracking a learn cycle that ends at `stop_voltage`.
+    ///
+    /// Drive the cycle with `step_learn_cycle()`.
+    pub async fn start_learn_cycle(
+        &mut self,
+        stop_voltage: MilliVolts,
+    ) -> Result<LearnCycle, Error<E>> {
+        let adc = self.read_adc_measurements().await?;
+        self.write_en_learn(true).await?;
+        info!("Learn cycle started, stop at {}", stop_voltage);
+        Ok(LearnCycle::new(stop_voltage, &adc))
+    }
+
+    /// Reads ChargerStatus and the ADC results and records them in `cycle`,
+    /// `elapsed` after the previous step (or `start_learn_cycle()`).
+    ///
+    /// Clears EN_LEARN and returns `LearnStatus::Finished` once VBAT is at or
+    /// below the stop voltage or a fault is latched. Stepping a finished
+    /// cycle returns its summary without touching the bus.
+    pub async fn step_learn_cycle(
+        &mut self,
+        cycle: &mut LearnCycle,
+        elapsed: Micros,
+    ) -> Result<LearnStatus, Error<E>> {
+        if cycle.end_reason().is_some() {
+            return Ok(LearnStatus::Finished(cycle.summary()));
+        }
+        let status = self.read_charger_status().await?;
+        let adc = self.read_adc_measurements().await?;
+        match cycle.record(elapsed, &adc, status.fault_flags) {
+            None => Ok(LearnStatus::Running {
+                vbat: adc.vbat.0,
+                idchg: adc.idchg.milliamps,
+            }),
+            Some(reason) => {
+                self.write_en_learn(false).await?;
+                let summary = cycle.summary();
+                info!(
+                    "Learn cycle ended ({:?}): {} mAh",
+                    reason, summary.discharged_mah
+                );
+                Ok(LearnStatus::Finished(summary))
+            }
+        }
+    }
+
+    /// Clears EN_LEARN and ends `cycle` early, returning its totals.
+    pub async fn abort_learn_cycle(
+        &mut self,
+        cycle: &mut LearnCycle,
+    ) -> Result<LearnSummary, Error<E>> {
+        self.write_en_learn(false).await?;
+        cycle.abort();
+        Ok(cycle.summary())
+    }
+
+    async fn write_en_learn(&mut self, enable: bool) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option0().await?;
+        options.lsb_flags.set(ChargeOption0Flags::EN_LEARN, enable);
+        self.set_charge_option0(options).await
+    }
+
+    /// Writes the VSYS_UVP, hiccup, ACOC and BATOC settings to ChargeOption0,
+    /// ChargeOption2 and ChargeOption4, preserving their other bits.
+    ///
+    /// Returns `Error::OutOfRange` without writing anything if `vsys_uvp` is
+    /// outside its range on the selected variant.
+    pub async fn set_protection_settings(
+        &mut self,
+        settings: &ProtectionSettings,
+    ) -> Result<(), Error<E>> {
+        Error::check_range(
+            "vsys_uvp",
+            settings.vsys_uvp,
+            ProtectionSettings::vsys_uvp_range(self.variant),
+        )?;
+        let mut option0 = self.read_charge_option0().await?;
+        let mut option2 = self.read_charge_option2().await?;
+        let mut option4 = self.read_charge_option4().await?;
+        settings.apply_to(self.variant, &mut option0, &mut option2, &mut option4);
+        self.set_charge_option0(option0).await?;
+        self.set_charge_option2(option2).await?;
+        self.set_charge_option4(option4).await
+    }
+
+    /// Reads the VSYS_UVP, hiccup, ACOC and BATOC settings.
+    pub async fn read_protection_settings(&mut self) -> Result<ProtectionSettings, Error<E>> {
+        let option0 = self.read_charge_option0().await?;
+        let option2 = self.read_charge_option2().await?;
+        let option4 = self.read_charge_option4().await?;
+        Ok(ProtectionSettings::from_registers(
+            self.variant,
+            &option0,
+            &option2,
+            &option4,
+        ))
+    }
+
+    /// Enables ACOC at `threshold` of ILIM2 and returns the input current it
+    /// trips at, from the IIN_DPM and ILIM2_VTH settings and `rsns_ac`.
+    ///
+    /// Returns `Error::OutOfRange` without writing anything if ILIM2_VTH
+    /// holds a reserved code, or if the trip current falls outside the 50 mV
+    /// to 180 mV sense-voltage clamp, where the chip would trip at the clamp
+    /// instead.
+    pub async fn set_acoc(
+        &mut self,
+        threshold: OvercurrentThreshold,
+    ) -> Result<MilliAmps32, Error<E>> {
+        let prochot = self.read_prochot_option0().await?;
+        let codes = data_types::ProchotOption0::ILIM2_VTH_CODES;
+        let ilim2_percent = prochot.ilim2_percent().ok_or(Error::OutOfRange {
+            field: "ilim2_vth",
+            value: u32::from(prochot.ilim2_vth_code()),
+            min: u32::from(*codes.start()),
+            max: u32::from(*codes.end()),
+        })?;
+        let iin_dpm = self.read_iin_dpm().await?;
+        let trip_ma = threshold.acoc_trip_ma(iin_dpm.milliamps, ilim2_percent);
+        let range = OvercurrentThreshold::acoc_range_ma(self.config.rsns_ac);
+        if !range.contains(&trip_ma) {
+            return Err(Error::OutOfRange {
+                field: "acoc",
+                value: trip_ma.0,
+                min: range.start().0,
+                max: range.end().0,
+            });
+        }
+        self.write_overcurrent(
+            ChargeOption2Flags::EN_ACOC,
+            ChargeOption2Flags::ACOC_VTH,
+            Some(threshold),
+        )
+        .await?;
+        info!("ACOC at {}", trip_ma);
+        Ok(trip_ma)
+    }
+
+    /// Enables BATOC at `threshold` of IDCHG_TH2 and returns the battery
+    /// discharge current it trips at, from the IDCHG_TH1 and IDCHG_TH2
+    /// settings and `rsns_bat`.
+    ///
+    /// Returns `Error::OutOfRange` without writing anything if that current
+    /// falls outside the 100 mV to 360 mV sense-voltage clamp, where the chip
+    /// would trip at the clamp instead.
+    pub async fn set_batoc(
+        &mut self,
+        threshold: OvercurrentThreshold,
+    ) -> Result<MilliAmps32, Error<E>> {
+        let rsns_bat = self.config.rsns_bat;
+        let prochot = self.read_prochot_option1().await?;
+        let option4 = self.read_charge_option4().await?;
+        let idchg_th2 = MilliAmps32::from(prochot.idchg_th1(rsns_bat))
+            * u32::from(option4.idchg_th2_percent())
+            / 100;
+        let trip_ma = threshold.batoc_trip_ma(idchg_th2);
+        let range = OvercurrentThreshold::batoc_range_ma(rsns_bat);
+        if !range.contains(&trip_ma) {
+            return Err(Error::OutOfRange {
+                field: "batoc",
+                value: trip_ma.0,
+                min: range.start().0,
+                max: range.end().0,
+            });
+        }
+        self.write_overcurrent(
+            ChargeOption2Flags::EN_BATOC,
+            ChargeOption2Flags::BATOC_VTH,
+            Some(threshold),
+        )
+        .await?;
+        info!("BATOC at {}", trip_ma);
+        Ok(trip_ma)
+    }
+
+    /// Disables ACOC by clearing EN_ACOC.
+    pub async fn disable_acoc(&mut self) -> Result<(), Error<E>> {
+        self.write_overcurrent(
+            ChargeOption2Flags::EN_ACOC,
+            ChargeOption2Flags::ACOC_VTH,
+            None,
+        )
+        .await
+    }
+
+    /// Disables BATOC by clearing EN_BATOC.
+    pub async fn disable_batoc(&mut self) -> Result<(), Error<E>> {
+        self.write_overcurrent(
+            ChargeOption2Flags::EN_BATOC,
+            ChargeOption2Flags::BATOC_VTH,
+            None,
+        )
+        .await
+    }
+
+    async fn write_overcurrent(
+        &mut self,
+        enable: ChargeOption2Flags,
+        vth: ChargeOption2Flags,
+        threshold: Option<OvercurrentThreshold>,
+    ) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option2().await?;
+        options.lsb_flags.set(enable, threshold.is_some());
+        if let Some(threshold) = threshold {
+            options
+                .lsb_flags
+                .set(vth, threshold == OvercurrentThreshold::Percent200);
+        }
+        self.set_charge_option2(options).await
+    }
+
+    /// Reads ChargerStatus, records it in `recovery` at `now` and applies the
+    /// result: faults whose backoff has run out are written to 0, and a fault
+    /// out of retries sets CHRG_INHIBIT.
+    pub async fn run_protection_recovery(
+        &mut self,
+        recovery: &mut ProtectionRecovery,
+        now: core::time::Duration,
+    ) -> Result<RecoveryStep, Error<E>> {
+        let mut status = self.read_charger_status().await?;
+        let step = recovery.update(status.fault_flags, now);
+        if !step.clear.is_empty() {
+            status.fault_flags.remove(step.clear);
+            self.set_charger_status(status).await?;
+            info!("Protection: cleared {}", step.clear);
+        }
+        if !step.safe_state.is_empty() {
+            error!(
+                "Protection: {} out of retries, charging inhibited",
+                step.safe_state
+            );
+            self.write_chrg_inhibit(true).await?;
+        }
+        Ok(step)
+    }
+
+    /// Leaves the safe state: clears CHRG_INHIBIT and every latched fault
+    /// handled by `recovery`, and resets its consecutive counts.
+    pub async fn reset_protection_recovery(
+        &mut self,
+        recovery: &mut ProtectionRecovery,
+    ) -> Result<(), Error<E>> {
+        let mut status = self.read_charger_status().await?;
+        for fault in ProtectedFault::ALL {
+            status.fault_flags.remove(fault.flag());
+        }
+        self.set_charger_status(status).await?;
+        self.write_chrg_inhibit(false).await?;
+        recovery.reset();
+        Ok(())
+    }
+
+    async fn write_chrg_inhibit(&mut self, inhibit: bool) -> Result<(), Error<E>> {
+        let mut options = self.read_charge_option0().await?;
+        options
+            .lsb_flags
+            .set(ChargeOption0Flags::CHRG_INHIBIT, inhibit);
+        self.set_charge_option0(options).await
+    }
+
+    /// Writes the charge limits of the JEITA zone of `temperature`, or a
+    /// ChargeCurrent of 0 in the Cold and Hot zones, and returns the zone.
+    ///
+    /// Pass the zone returned by the previous call as `previous` for
+    /// hysteresis; nothing is written while the zone stays the same.
+    pub async fn apply_jeita(
+        &mut self,
+        profile: &JeitaProfile,
+        temperature: Celsius,
+        previous: Option<JeitaZone>,
+    ) -> Result<JeitaZone, Error<E>> {
+        let zone = profile.zone(temperature, previous);
+        if previous == Some(zone) {
+            return Ok(zone);
+        }
+        let rsns_bat = self.config.rsns_bat;
+        match profile.limits(zone) {
+            Some(limits) => {
+                self.set_charge_voltage_setting(data_types::ChargeVoltageSetting::from_millivolts(
+                    limits.charge_voltage,
+                ))
+                .await?;
+                self.set_charge_current_setting(data_types::ChargeCurrentSetting::from_milliamps(
+                    limits.charge_current,
+                    rsns_bat,
+                ))
+                .await?;
+            }
+            None => {
+                warn!("Charging stopped at {}", temperature);
+                self.set_charge_current_setting(data_types::ChargeCurrentSetting::from_milliamps(
+                    MilliAmps(0),
+                    rsns_bat,
+                ))
+                .await?;
+            }
+        }
+        info!("JEITA zone {:?} at {}", zone, temperature);
+        Ok(zone)
+    }
+
+    /// Reads ADCCMPIN and converts it with `ntc`, returning `None` for an
+    /// open or shorted thermistor.
+    pub async fn read_ntc_temperature(
+        &mut self,
+        ntc: &NtcDivider,
+    ) -> Result<Option<Celsius>, Error<E>> {
+        let raw = self.read_register(Register::ADCCMPIN).await?;
+        Ok(ntc.temperature(data_types::AdcCmpin::from_u8(raw).0))
+    }
+}
+
+fn check_charge_current<E>(setting: &data_types::ChargeCurrentSetting) -> Result<(), Error<E>> {
+    Error::check_range(
+        "charge_current",
+        setting.milliamps,
+        data_types::ChargeCurrentSetting::valid_range(setting.rsns_bat),
+    )
+}
+
+fn check_charge_voltage<E>(
+    setting: &data_types::ChargeVoltageSetting,
+    variant: ChipVariant,
+) -> Result<(), Error<E>> {
+    Error::check_range(
+        "charge_voltage",
+        setting.millivolts,
+        variant.charge_voltage_range(),
+    )
+}
+
+fn check_otg_voltage<E>(setting: &data_types::OtgVoltageSetting) -> Result<(), Error<E>> {
+    Error::check_range(
+        "otg_voltage",
+        setting.millivolts,
+        data_types::OtgVoltageSetting::valid_range(),
+    )
+}
+
+fn check_otg_current<E>(setting: &data_types::OtgCurrentSetting) -> Result<(), Error<E>> {
+    Error::check_range(
+        "otg_current",
+        setting.milliamps,
+        data_types::OtgCurrentSetting::valid_range(setting.rsns_bat),
+    )
+}
+
+fn check_input_voltage<E>(setting: &data_types::InputVoltageSetting) -> Result<(), Error<E>> {
+    Error::check_range(
+        "input_voltage",
+        setting.millivolts,
+        data_types::InputVoltageSetting::valid_range(),
+    )
+}
+
+fn check_vsys_min<E>(setting: &data_types::VsysMinSetting) -> Result<(), Error<E>> {
+    Error::check_range(
+        "vsys_min",
+        setting.millivolts,
+        data_types::VsysMinSetting::valid_range(),
+    )
+}
+
+fn check_iin_host<E>(
+    setting: &data_types::IinHostSetting,
+    rsns_ac: SenseResistorValue,
+) -> Result<(), Error<E>> {
+    Error::check_range(
+        "iin_host",
+        setting.milliamps,
+        data_types::IinHostSetting::valid_range(rsns_ac),
+    )
+}
+
+/// Range-checks every setting `init()` and `apply_config()` would write.
+fn check_config<E>(config: &Config, variant: ChipVariant) -> Result<(), Error<E>> {
+    check_charge_current(&config.charge_current)?;
+    check_charge_voltage(&config.charge_voltage, variant)?;
+    check_otg_voltage(&config.otg_voltage)?;
+    check_otg_current(&config.otg_current)?;
+    check_input_voltage(&config.input_voltage)?;
+    check_vsys_min(&config.vsys_min)?;
+    check_iin_host(&config.iin_host, config.rsns_ac)?;
+    if let Some(converter) = &config.converter {
+        check_converter(converter)?;
+    }
+    Ok(())
+}
+
+fn check_converter<E>(converter: &data_types::ConverterConfig) -> Result<(), Error<E>> {
+    match converter.inductor {
+        Some(inductor) if !inductor.supports(converter.frequency) => {
+            Err(Error::UnsupportedFrequency {
+                inductor,
+                frequency: converter.frequency,
+            })
+        }
+        _ => Ok(()),
+    }
 }
@@ -0,0 +1,78 @@
+//! SMBus Packet Error Checking.
+//!
+//! The PEC byte is a CRC-8 (polynomial 0x07, initial value 0, no reflection)
+//! over every byte of the transaction, including the address bytes:
+//!
+//! * write: `addr<<1 | 0`, register, data...
+//! * read: `addr<<1 | 0`, register, `addr<<1 | 1`, data...
+//!
+//! [`Bq25730::new_with_pec`](crate::Bq25730::new_with_pec) appends it to
+//! writes and checks it on reads.
+
+const POLYNOMIAL: u8 = 0x07;
+
+/// CRC-8 lookup table for [`POLYNOMIAL`].
+const TABLE: [u8; 256] = {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ POLYNOMIAL
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Incremental PEC calculation, for transactions that span several buffers.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Pec(u8);
+
+impl Pec {
+    /// Starts a new calculation.
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Feeds `bytes` into the checksum.
+    pub fn update(&mut self, bytes: &[u8]) -> &mut Self {
+        for &byte in bytes {
+            self.0 = TABLE[(self.0 ^ byte) as usize];
+        }
+        self
+    }
+
+    /// Returns the checksum of the bytes fed so far.
+    pub const fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Returns the PEC of `bytes`.
+pub fn pec(bytes: &[u8]) -> u8 {
+    Pec::new().update(bytes).value()
+}
+
+/// PEC of a register write: address, register and `values`.
+pub(crate) fn write_pec(address: u8, reg: u8, values: &[u8]) -> u8 {
+    Pec::new()
+        .update(&[address << 1, reg])
+        .update(values)
+        .value()
+}
+
+/// PEC of a register read: address, register, repeated-start address and `data`.
+pub(crate) fn read_pec(address: u8, reg: u8, data: &[u8]) -> u8 {
+    Pec::new()
+        .update(&[address << 1, reg, (address << 1) | 1])
+        .update(data)
+        .value()
+}
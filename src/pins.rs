@@ -0,0 +1,197 @@
+//! Wake-up on the CHRG_OK, /PROCHOT and CMPOUT status pins.
+//!
+//! With the `async` feature, [`Bq25730Pins`] pairs the driver with the
+//! status pins so a task can sleep until one of them changes instead of
+//! polling I2C. `wait_for_event()` awaits an edge on any of the pins, then
+//! reads ChargerStatus and ProchotStatus and reports what happened as a
+//! [`PinEvent`]. Pins that are not wired on the board can be given as
+//! [`NoPin`].
+
+use core::convert::Infallible;
+
+use embedded_hal::digital::{ErrorType, InputPin};
+
+use crate::data_types::{ChargerStatus, ProchotStatus};
+use crate::registers::ProchotStatusFlags;
+
+/// One of the chip's status outputs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StatusPin {
+    ChrgOk,
+    Prochot,
+    Cmpout,
+}
+
+/// What a status pin edge means.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PinEventKind {
+    /// CHRG_OK went high: a valid adapter is present.
+    AdapterPresent,
+    /// CHRG_OK went low: the adapter was removed or is out of range.
+    AdapterLost,
+    /// /PROCHOT was asserted (low) by the given ProchotStatus sources.
+    ProchotAsserted(ProchotStatusFlags),
+    /// /PROCHOT was released.
+    ProchotReleased,
+    /// CMPOUT changed level.
+    Comparator { high: bool },
+}
+
+/// A status pin edge, with the status registers read after it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PinEvent {
+    pub pin: StatusPin,
+    pub kind: PinEventKind,
+    pub charger_status: ChargerStatus,
+    pub prochot_status: ProchotStatus,
+}
+
+/// Placeholder for a status pin that is not connected; it never changes.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl InputPin for NoPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::digital::Wait for NoPin {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        core::future::pending().await
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        core::future::pending().await
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        core::future::pending().await
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        core::future::pending().await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        core::future::pending().await
+    }
+}
+
+#[cfg(feature = "async")]
+pub use wrapper::Bq25730Pins;
+
+#[cfg(feature = "async")]
+mod wrapper {
+    use core::future::{Future, poll_fn};
+    use core::pin::pin;
+    use core::task::Poll;
+
+    use embedded_hal::digital::{Error as _, InputPin};
+    use embedded_hal_async::digital::Wait;
+    use embedded_hal_async::i2c::I2c;
+
+    use super::{PinEvent, PinEventKind, StatusPin};
+    use crate::{Bq25730, errors::Error};
+
+    /// The driver together with its CHRG_OK, /PROCHOT and CMPOUT pins.
+    pub struct Bq25730Pins<I2C, CHRG, PROCHOT, CMP>
+    where
+        I2C: I2c + 'static,
+    {
+        pub charger: Bq25730<I2C>,
+        pub chrg_ok: CHRG,
+        pub prochot: PROCHOT,
+        pub cmpout: CMP,
+    }
+
+    impl<I2C, E, CHRG, PROCHOT, CMP> Bq25730Pins<I2C, CHRG, PROCHOT, CMP>
+    where
+        I2C: I2c<Error = E> + Send,
+        CHRG: InputPin + Wait,
+        PROCHOT: InputPin + Wait,
+        CMP: InputPin + Wait,
+    {
+        pub fn new(charger: Bq25730<I2C>, chrg_ok: CHRG, prochot: PROCHOT, cmpout: CMP) -> Self {
+            Self {
+                charger,
+                chrg_ok,
+                prochot,
+                cmpout,
+            }
+        }
+
+        /// Returns the driver and the pins.
+        pub fn release(self) -> (Bq25730<I2C>, CHRG, PROCHOT, CMP) {
+            (self.charger, self.chrg_ok, self.prochot, self.cmpout)
+        }
+
+        /// Waits for an edge on any status pin, then reads ChargerStatus and
+        /// ProchotStatus and returns the event.
+        ///
+        /// If several pins change at once, CHRG_OK is reported first, then
+        /// /PROCHOT, then CMPOUT; the others are picked up by the next call
+        /// only if they change again.
+        pub async fn wait_for_event(&mut self) -> Result<PinEvent, Error<E>> {
+            let (pin, result) = {
+                let mut chrg_ok = pin!(self.chrg_ok.wait_for_any_edge());
+                let mut prochot = pin!(self.prochot.wait_for_any_edge());
+                let mut cmpout = pin!(self.cmpout.wait_for_any_edge());
+                poll_fn(|cx| {
+                    if let Poll::Ready(result) = chrg_ok.as_mut().poll(cx) {
+                        return Poll::Ready((StatusPin::ChrgOk, result.map_err(|e| e.kind())));
+                    }
+                    if let Poll::Ready(result) = prochot.as_mut().poll(cx) {
+                        return Poll::Ready((StatusPin::Prochot, result.map_err(|e| e.kind())));
+                    }
+                    if let Poll::Ready(result) = cmpout.as_mut().poll(cx) {
+                        return Poll::Ready((StatusPin::Cmpout, result.map_err(|e| e.kind())));
+                    }
+                    Poll::Pending
+                })
+                .await
+            };
+            result.map_err(|kind| Error::Pin { pin, kind })?;
+
+            let high = match pin {
+                StatusPin::ChrgOk => self.chrg_ok.is_high().map_err(|e| e.kind()),
+                StatusPin::Prochot => self.prochot.is_high().map_err(|e| e.kind()),
+                StatusPin::Cmpout => self.cmpout.is_high().map_err(|e| e.kind()),
+            }
+            .map_err(|kind| Error::Pin { pin, kind })?;
+            let charger_status = self.charger.read_charger_status().await?;
+            let prochot_status = self.charger.read_prochot_status().await?;
+
+            let kind = match (pin, high) {
+                (StatusPin::ChrgOk, true) => PinEventKind::AdapterPresent,
+                (StatusPin::ChrgOk, false) => PinEventKind::AdapterLost,
+                (StatusPin::Prochot, false) => {
+                    PinEventKind::ProchotAsserted(prochot_status.lsb_flags)
+                }
+                (StatusPin::Prochot, true) => PinEventKind::ProchotReleased,
+                (StatusPin::Cmpout, high) => PinEventKind::Comparator { high },
+            };
+            debug!("Status pin {:?}: {:?}", pin, kind);
+            Ok(PinEvent {
+                pin,
+                kind,
+                charger_status,
+                prochot_status,
+            })
+        }
+    }
+}
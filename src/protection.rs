@@ -0,0 +1,445 @@
+//! Converter protections and recovery from latched faults.
+//!
+//! [`ProtectionSettings`] covers the VSYS_UVP threshold and hiccup mode
+//! (ChargeOption0/ChargeOption4) and the ACOC and BATOC comparators
+//! (ChargeOption2); `Bq25730::set_protection_settings()` writes them.
+//! `Bq25730::set_acoc()` and `set_batoc()` instead enable one comparator and
+//! return its trip current, worked out from ILIM2 or IDCHG_TH2 and the sense
+//! resistor.
+//!
+//! SYSOVP, VSYS_UVP, BATOC and ACOC latch their ChargerStatus fault bit and
+//! keep the converter off until the host writes it back to 0. With hiccup
+//! enabled, VSYS_UVP restarts the converter on its own, but after seven
+//! failed restarts it latches off as well. `init()` clears FAULT_SYSOVP and
+//! FAULT_VSYS_UVP once; [`ProtectionRecovery`] handles faults latched later,
+//! clearing each after a per-fault [`RecoveryPolicy`] and counting them.
+//! `Bq25730::run_protection_recovery()` polls ChargerStatus and applies it.
+
+use core::ops::RangeInclusive;
+use core::time::Duration;
+
+use crate::data_types::{ChargeOption0, ChargeOption2, ChargeOption4, SenseResistorValue};
+use crate::registers::{
+    ChargeOption0Flags, ChargeOption2Flags, ChargeOption4MsbFlags, ChargerStatusFaultFlags,
+};
+use crate::units::{MilliAmps, MilliAmps32, MilliVolts};
+use crate::variant::ChipVariant;
+
+const VSYS_UVP_SHIFT: u8 = 5;
+const VSYS_UVP_MIN_MV: u16 = 2400;
+const VSYS_UVP_STEP_MV: u16 = 800;
+/// VSYS_UVP threshold of chips without an adjustable one (BQ25731).
+const VSYS_UVP_FIXED_MV: u16 = 1600;
+/// Sense voltage the ACOC threshold is clamped to (VACOC_FLOOR, VACOC_CEILING).
+const ACOC_CLAMP_MV: RangeInclusive<u32> = 50..=180;
+/// Sense voltage the BATOC threshold is clamped to.
+const BATOC_CLAMP_MV: RangeInclusive<u32> = 100..=360;
+
+/// Currents that put a sense voltage within `clamp_mv` across `rsns`.
+fn clamp_range_ma(
+    clamp_mv: RangeInclusive<u32>,
+    rsns: SenseResistorValue,
+) -> RangeInclusive<MilliAmps32> {
+    let milliohms = match rsns {
+        SenseResistorValue::R5mOhm => 5,
+        SenseResistorValue::R10mOhm => 10,
+    };
+    MilliAmps32(clamp_mv.start() * 1000 / milliohms)
+        ..=MilliAmps32(clamp_mv.end() * 1000 / milliohms)
+}
+
+/// ACOC or BATOC trip point relative to its reference: ILIM2 for ACOC,
+/// IDCHG_TH2 for BATOC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OvercurrentThreshold {
+    /// 133% of the reference (ACOC_VTH/BATOC_VTH = 0).
+    Percent133,
+    /// 200% of the reference (ACOC_VTH/BATOC_VTH = 1).
+    Percent200,
+}
+
+impl OvercurrentThreshold {
+    /// The threshold in percent of its reference.
+    pub fn percent(self) -> u16 {
+        match self {
+            OvercurrentThreshold::Percent133 => 133,
+            OvercurrentThreshold::Percent200 => 200,
+        }
+    }
+
+    /// ACOC trip current for an IIN_DPM of `iin_dpm` and ILIM2 at
+    /// `ilim2_percent` of it.
+    pub fn acoc_trip_ma(self, iin_dpm: MilliAmps, ilim2_percent: u16) -> MilliAmps32 {
+        MilliAmps32::from(iin_dpm) * u32::from(ilim2_percent) / 100 * u32::from(self.percent())
+            / 100
+    }
+
+    /// BATOC trip current for an IDCHG_TH2 of `idchg_th2`, before the
+    /// sense-voltage clamp.
+    pub fn batoc_trip_ma(self, idchg_th2: MilliAmps32) -> MilliAmps32 {
+        idchg_th2 * u32::from(self.percent()) / 100
+    }
+
+    /// Input currents the ACOC threshold can trip at: the chip clamps it to
+    /// 50 mV to 180 mV across `rsns_ac`.
+    pub fn acoc_range_ma(rsns_ac: SenseResistorValue) -> RangeInclusive<MilliAmps32> {
+        clamp_range_ma(ACOC_CLAMP_MV, rsns_ac)
+    }
+
+    /// Battery currents the BATOC threshold can trip at: the chip clamps
+    /// it to 100 mV to 360 mV across `rsns_bat`.
+    pub fn batoc_range_ma(rsns_bat: SenseResistorValue) -> RangeInclusive<MilliAmps32> {
+        clamp_range_ma(BATOC_CLAMP_MV, rsns_bat)
+    }
+
+    fn from_bit(set: bool) -> Self {
+        if set {
+            OvercurrentThreshold::Percent200
+        } else {
+            OvercurrentThreshold::Percent133
+        }
+    }
+}
+
+/// Protection comparator settings. The default is the power-on state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtectionSettings {
+    /// VSYS under-voltage protection on (VSYS_UVP_ENZ clear).
+    pub vsys_uvp_enabled: bool,
+    /// VSYS_UVP threshold: 2400 mV to 8000 mV in 800 mV steps (rounded
+    /// down) on a BQ25730, fixed at 1600 mV on a BQ25731.
+    pub vsys_uvp: MilliVolts,
+    /// Restart the converter after VSYS_UVP (VSYS_UVP_NO_HICCUP clear).
+    pub hiccup: bool,
+    /// Input overcurrent threshold, or `None` to disable ACOC.
+    pub acoc: Option<OvercurrentThreshold>,
+    /// Battery discharge overcurrent threshold, or `None` to disable BATOC.
+    pub batoc: Option<OvercurrentThreshold>,
+}
+
+impl Default for ProtectionSettings {
+    fn default() -> Self {
+        Self::power_on(ChipVariant::Bq25730)
+    }
+}
+
+impl ProtectionSettings {
+    /// Power-on state of `variant`.
+    pub fn power_on(variant: ChipVariant) -> Self {
+        Self {
+            vsys_uvp_enabled: true,
+            vsys_uvp: *Self::vsys_uvp_range(variant).start(),
+            hiccup: true,
+            acoc: None,
+            batoc: Some(OvercurrentThreshold::Percent200),
+        }
+    }
+
+    /// Range of the VSYS_UVP threshold on `variant`.
+    pub fn vsys_uvp_range(variant: ChipVariant) -> RangeInclusive<MilliVolts> {
+        if variant.has_adjustable_vsys_uvp() {
+            MilliVolts(VSYS_UVP_MIN_MV)..=MilliVolts(VSYS_UVP_MIN_MV + 7 * VSYS_UVP_STEP_MV)
+        } else {
+            MilliVolts(VSYS_UVP_FIXED_MV)..=MilliVolts(VSYS_UVP_FIXED_MV)
+        }
+    }
+
+    /// Decodes the settings of `variant` from the registers holding them.
+    pub fn from_registers(
+        variant: ChipVariant,
+        option0: &ChargeOption0,
+        option2: &ChargeOption2,
+        option4: &ChargeOption4,
+    ) -> Self {
+        let lsb2 = option2.lsb_flags;
+        let code = (option4.msb_flags & ChargeOption4MsbFlags::VSYS_UVP).bits() >> VSYS_UVP_SHIFT;
+        let vsys_uvp = if variant.has_adjustable_vsys_uvp() {
+            MilliVolts(VSYS_UVP_MIN_MV + u16::from(code) * VSYS_UVP_STEP_MV)
+        } else {
+            MilliVolts(VSYS_UVP_FIXED_MV)
+        };
+        Self {
+            vsys_uvp_enabled: !option0.lsb_flags.contains(ChargeOption0Flags::VSYS_UVP_ENZ),
+            vsys_uvp,
+            hiccup: !option4
+                .msb_flags
+                .contains(ChargeOption4MsbFlags::VSYS_UVP_NO_HICCUP),
+            acoc: lsb2.contains(ChargeOption2Flags::EN_ACOC).then(|| {
+                OvercurrentThreshold::from_bit(lsb2.contains(ChargeOption2Flags::ACOC_VTH))
+            }),
+            batoc: lsb2.contains(ChargeOption2Flags::EN_BATOC).then(|| {
+                OvercurrentThreshold::from_bit(lsb2.contains(ChargeOption2Flags::BATOC_VTH))
+            }),
+        }
+    }
+
+    /// Writes the settings into the registers holding them on `variant`,
+    /// leaving their other bits unchanged. `vsys_uvp` is clamped to its range;
+    /// the VSYS_UVP field is left alone where the threshold is fixed.
+    pub fn apply_to(
+        &self,
+        variant: ChipVariant,
+        option0: &mut ChargeOption0,
+        option2: &mut ChargeOption2,
+        option4: &mut ChargeOption4,
+    ) {
+        option0
+            .lsb_flags
+            .set(ChargeOption0Flags::VSYS_UVP_ENZ, !self.vsys_uvp_enabled);
+
+        if variant.has_adjustable_vsys_uvp() {
+            let range = Self::vsys_uvp_range(variant);
+            let mv = self.vsys_uvp.clamp(*range.start(), *range.end()).0;
+            let code = ((mv - VSYS_UVP_MIN_MV) / VSYS_UVP_STEP_MV) as u8;
+            option4.msb_flags.remove(ChargeOption4MsbFlags::VSYS_UVP);
+            option4.msb_flags |= ChargeOption4MsbFlags::from_bits_truncate(code << VSYS_UVP_SHIFT);
+        }
+        option4
+            .msb_flags
+            .set(ChargeOption4MsbFlags::VSYS_UVP_NO_HICCUP, !self.hiccup);
+
+        let lsb2 = &mut option2.lsb_flags;
+        lsb2.set(ChargeOption2Flags::EN_ACOC, self.acoc.is_some());
+        if let Some(threshold) = self.acoc {
+            lsb2.set(
+                ChargeOption2Flags::ACOC_VTH,
+                threshold == OvercurrentThreshold::Percent200,
+            );
+        }
+        lsb2.set(ChargeOption2Flags::EN_BATOC, self.batoc.is_some());
+        if let Some(threshold) = self.batoc {
+            lsb2.set(
+                ChargeOption2Flags::BATOC_VTH,
+                threshold == OvercurrentThreshold::Percent200,
+            );
+        }
+    }
+}
+
+/// A latching protection handled by [`ProtectionRecovery`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProtectedFault {
+    SysOvp,
+    VsysUvp,
+    BatOc,
+    AcOc,
+}
+
+impl ProtectedFault {
+    pub const ALL: [ProtectedFault; 4] = [
+        ProtectedFault::SysOvp,
+        ProtectedFault::VsysUvp,
+        ProtectedFault::BatOc,
+        ProtectedFault::AcOc,
+    ];
+
+    /// The ChargerStatus bit latched by this fault.
+    pub fn flag(self) -> ChargerStatusFaultFlags {
+        match self {
+            ProtectedFault::SysOvp => ChargerStatusFaultFlags::FAULT_SYSOVP,
+            ProtectedFault::VsysUvp => ChargerStatusFaultFlags::FAULT_VSYS_UVP,
+            ProtectedFault::BatOc => ChargerStatusFaultFlags::FAULT_BATOC,
+            ProtectedFault::AcOc => ChargerStatusFaultFlags::FAULT_ACOC,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// What to do with a latched fault.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecoveryPolicy {
+    /// Leave the fault latched.
+    Ignore,
+    /// Clear the fault after `initial_backoff`, doubling the wait with every
+    /// consecutive occurrence up to `max_backoff`.
+    AutoClear {
+        initial_backoff: Duration,
+        max_backoff: Duration,
+    },
+    /// Clear the fault after `delay` up to `max_retries` consecutive times,
+    /// then leave it latched and put the charger in the safe state
+    /// (CHRG_INHIBIT set) until `Bq25730::reset_protection_recovery()`.
+    RetryThenSafeState { delay: Duration, max_retries: u8 },
+}
+
+/// Occurrence counts of one fault.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FaultCounter {
+    /// Occurrences since the recovery was created.
+    pub total: u32,
+    /// Occurrences without a fault-free `settle_time` in between.
+    pub consecutive: u8,
+}
+
+/// Faults affected by one [`ProtectionRecovery::update()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecoveryStep {
+    /// Faults newly latched.
+    pub latched: ChargerStatusFaultFlags,
+    /// Faults whose backoff has run out, to be written to 0.
+    pub clear: ChargerStatusFaultFlags,
+    /// Faults out of retries; the charger goes to the safe state.
+    pub safe_state: ChargerStatusFaultFlags,
+}
+
+impl RecoveryStep {
+    /// Returns `true` if nothing happened.
+    pub fn is_empty(&self) -> bool {
+        self.latched.is_empty() && self.clear.is_empty() && self.safe_state.is_empty()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct FaultState {
+    counter: FaultCounter,
+    /// When to clear the fault latched now.
+    clear_at: Option<Duration>,
+    /// When the fault was last cleared or went away.
+    last_cleared: Option<Duration>,
+}
+
+/// Latched-fault bookkeeping; see the module documentation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProtectionRecovery {
+    policies: [RecoveryPolicy; 4],
+    /// Fault-free time after which a fault's consecutive count restarts.
+    pub settle_time: Duration,
+    faults: [FaultState; 4],
+    safe_state: bool,
+}
+
+impl ProtectionRecovery {
+    /// Creates a recovery applying `policy` to every fault, with a
+    /// one-minute `settle_time`.
+    pub fn new(policy: RecoveryPolicy) -> Self {
+        Self {
+            policies: [policy; 4],
+            settle_time: Duration::from_secs(60),
+            faults: [FaultState::default(); 4],
+            safe_state: false,
+        }
+    }
+
+    /// Returns the policy for `fault`.
+    pub fn policy(&self, fault: ProtectedFault) -> RecoveryPolicy {
+        self.policies[fault.index()]
+    }
+
+    /// Sets the policy for `fault`.
+    pub fn set_policy(&mut self, fault: ProtectedFault, policy: RecoveryPolicy) {
+        self.policies[fault.index()] = policy;
+    }
+
+    /// Returns the occurrence counts of `fault`.
+    pub fn counter(&self, fault: ProtectedFault) -> FaultCounter {
+        self.faults[fault.index()].counter
+    }
+
+    /// Returns `true` once a fault has run out of retries.
+    pub fn in_safe_state(&self) -> bool {
+        self.safe_state
+    }
+
+    /// Leaves the safe state and restarts the consecutive counts; totals are
+    /// kept.
+    pub fn reset(&mut self) {
+        self.safe_state = false;
+        for state in &mut self.faults {
+            state.counter.consecutive = 0;
+            state.clear_at = None;
+        }
+    }
+
+    /// Tracks the latched `faults` read at `now` (any monotonic time base)
+    /// and returns the faults to clear and those out of retries.
+    ///
+    /// Nothing is cleared while in the safe state.
+    pub fn update(&mut self, faults: ChargerStatusFaultFlags, now: Duration) -> RecoveryStep {
+        let mut step = RecoveryStep {
+            latched: ChargerStatusFaultFlags::empty(),
+            clear: ChargerStatusFaultFlags::empty(),
+            safe_state: ChargerStatusFaultFlags::empty(),
+        };
+        for fault in ProtectedFault::ALL {
+            let flag = fault.flag();
+            let policy = self.policies[fault.index()];
+            let settle_time = self.settle_time;
+            let state = &mut self.faults[fault.index()];
+
+            if !faults.contains(flag) {
+                if state.clear_at.take().is_some() {
+                    state.last_cleared = Some(now);
+                }
+                continue;
+            }
+            if state.clear_at.is_none() {
+                let settled = state
+                    .last_cleared
+                    .is_some_and(|t| now.saturating_sub(t) >= settle_time);
+                if settled {
+                    state.counter.consecutive = 0;
+                }
+                state.counter.total = state.counter.total.saturating_add(1);
+                state.counter.consecutive = state.counter.consecutive.saturating_add(1);
+                step.latched |= flag;
+
+                let backoff = match policy {
+                    RecoveryPolicy::Ignore => None,
+                    RecoveryPolicy::AutoClear {
+                        initial_backoff,
+                        max_backoff,
+                    } => {
+                        let doublings = u32::from(state.counter.consecutive - 1).min(31);
+                        Some(
+                            initial_backoff
+                                .saturating_mul(1 << doublings)
+                                .min(max_backoff),
+                        )
+                    }
+                    RecoveryPolicy::RetryThenSafeState { delay, max_retries } => {
+                        if state.counter.consecutive > max_retries {
+                            step.safe_state |= flag;
+                            None
+                        } else {
+                            Some(delay)
+                        }
+                    }
+                };
+                // A fault left latched stays pending with no deadline.
+                state.clear_at = Some(backoff.map_or(Duration::MAX, |b| now.saturating_add(b)));
+            }
+        }
+        if !step.safe_state.is_empty() {
+            self.safe_state = true;
+        }
+        if self.safe_state {
+            return step;
+        }
+        for fault in ProtectedFault::ALL {
+            let state = &mut self.faults[fault.index()];
+            if state.clear_at.is_some_and(|at| now >= at) {
+                state.clear_at = None;
+                state.last_cleared = Some(now);
+                step.clear |= fault.flag();
+            }
+        }
+        step
+    }
+}
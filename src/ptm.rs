@@ -0,0 +1,110 @@
+//! Pass Through Mode (PTM).
+//!
+//! In PTM the converter stops switching and connects VBUS straight to VSYS,
+//! saving the switching losses when the adapter voltage already suits the
+//! system. `Bq25730::enter_ptm()`/`exit_ptm()` set and clear EN_PTM
+//! (ChargeOption1) and `ptm_active()` reports STAT_PTM (ChargeOption4).
+//!
+//! [`PassThroughPolicy`] decides when to enter and leave PTM from the ADC
+//! readings; `Bq25730::run_pass_through_policy()` applies it and returns the
+//! transition as a [`PassThroughEvent`]. The ADC must be converting (e.g.
+//! ADC_CONV continuous) for the readings to be current.
+
+use core::fmt;
+
+use crate::data_types::AdcMeasurements;
+use crate::registers::ChargerStatusFaultFlags;
+use crate::units::{MilliAmps, MilliVolts};
+
+/// Thresholds for entering and leaving PTM.
+///
+/// PTM is entered when no fault is latched, VBUS is within
+/// `max_vbus_offset` of VSYS and the input current is at most
+/// `max_entry_current`. It is left on any fault or once the input current
+/// reaches `exit_current`, which should sit well above `max_entry_current`
+/// to avoid toggling.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PassThroughPolicy {
+    /// Largest difference between VBUS and VSYS at which PTM is entered.
+    pub max_vbus_offset: MilliVolts,
+    /// Largest input current at which PTM is entered.
+    pub max_entry_current: MilliAmps,
+    /// Input current that counts as a load step and leaves PTM.
+    pub exit_current: MilliAmps,
+}
+
+impl Default for PassThroughPolicy {
+    fn default() -> Self {
+        Self {
+            max_vbus_offset: MilliVolts(500),
+            max_entry_current: MilliAmps(1000),
+            exit_current: MilliAmps(3000),
+        }
+    }
+}
+
+impl PassThroughPolicy {
+    /// Returns the transition called for by `adc` and `faults`, given whether
+    /// PTM is currently `enabled`.
+    pub fn evaluate(
+        &self,
+        enabled: bool,
+        adc: &AdcMeasurements,
+        faults: ChargerStatusFaultFlags,
+    ) -> Option<PassThroughEvent> {
+        let iin = adc.iin.milliamps;
+        if enabled {
+            if !faults.is_empty() {
+                Some(PassThroughEvent::Exited(PassThroughExitReason::Fault(
+                    faults,
+                )))
+            } else if iin >= self.exit_current {
+                Some(PassThroughEvent::Exited(PassThroughExitReason::LoadStep(
+                    iin,
+                )))
+            } else {
+                None
+            }
+        } else {
+            let offset = MilliVolts(adc.vbus.0.0.abs_diff(adc.vsys.0.0));
+            (faults.is_empty() && offset <= self.max_vbus_offset && iin <= self.max_entry_current)
+                .then_some(PassThroughEvent::Entered)
+        }
+    }
+}
+
+/// A PTM transition made by `Bq25730::run_pass_through_policy()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PassThroughEvent {
+    /// EN_PTM was set.
+    Entered,
+    /// EN_PTM was cleared.
+    Exited(PassThroughExitReason),
+}
+
+/// Why [`PassThroughPolicy`] left PTM.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PassThroughExitReason {
+    /// ChargerStatus reported these faults.
+    Fault(ChargerStatusFaultFlags),
+    /// The input current reached `exit_current`.
+    LoadStep(MilliAmps),
+}
+
+impl fmt::Display for PassThroughEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PassThroughEvent::Entered => f.write_str("entered PTM"),
+            PassThroughEvent::Exited(PassThroughExitReason::Fault(faults)) => {
+                write!(f, "left PTM on fault: {faults}")
+            }
+            PassThroughEvent::Exited(PassThroughExitReason::LoadStep(iin)) => {
+                write!(f, "left PTM on load step: {iin}")
+            }
+        }
+    }
+}
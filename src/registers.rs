@@ -1,4 +1,5 @@
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Register {
     /// ChargeOption0 LSB Register
@@ -99,6 +100,543 @@ pub enum Register {
     VMINActiveProtectionMsb = 0x3F,
 }
 
+impl Register {
+    /// Every defined register address, in ascending order.
+    pub const ALL: [Register; 48] = [
+        Register::ChargeOption0,
+        Register::ChargeOption0Msb,
+        Register::ChargeCurrent,
+        Register::ChargeCurrentMsb,
+        Register::ChargeVoltage,
+        Register::ChargeVoltageMsb,
+        Register::OTGVoltage,
+        Register::OTGVoltageMsb,
+        Register::OTGCurrent,
+        Register::OTGCurrentMsb,
+        Register::InputVoltage,
+        Register::InputVoltageMsb,
+        Register::VsysMin,
+        Register::VsysMinMsb,
+        Register::IinHost,
+        Register::IinHostMsb,
+        Register::ChargerStatus,
+        Register::ChargerStatusMsb,
+        Register::ProchotStatus,
+        Register::ProchotStatusMsb,
+        Register::IinDpm,
+        Register::IinDpmMsb,
+        Register::ADCPSYS,
+        Register::ADCVBUS,
+        Register::ADCIDCHG,
+        Register::ADCICHG,
+        Register::ADCCMPIN,
+        Register::ADCIIN,
+        Register::ADCVBAT,
+        Register::ADCVSYS,
+        Register::ManufacturerID,
+        Register::DeviceID,
+        Register::ChargeOption1,
+        Register::ChargeOption1Msb,
+        Register::ChargeOption2,
+        Register::ChargeOption2Msb,
+        Register::ChargeOption3,
+        Register::ChargeOption3Msb,
+        Register::ProchotOption0,
+        Register::ProchotOption0Msb,
+        Register::ProchotOption1,
+        Register::ProchotOption1Msb,
+        Register::ADCOption,
+        Register::ADCOptionMsb,
+        Register::ChargeOption4,
+        Register::ChargeOption4Msb,
+        Register::VMINActiveProtection,
+        Register::VMINActiveProtectionMsb,
+    ];
+}
+
+/// Access type of a register address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Access {
+    /// All bits are read-only.
+    ReadOnly,
+    /// At least one bit can be written; see `Register::writable_mask`.
+    ReadWrite,
+}
+
+/// Power-on reset value of a register address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ResetValue {
+    /// Same reset value for every cell count.
+    Fixed(u8),
+    /// Reset value depends on the CELL_BATPRESZ setting, indexed by cell count 1S-5S.
+    PerCell([u8; 5]),
+    /// No fixed reset value (VINDPM is measured at power-up, ADC results depend on conversions).
+    Unknown,
+}
+
+impl ResetValue {
+    /// Returns the reset value for a cell count. Unsupported cell counts fall back to 4S,
+    /// matching `Config::new`.
+    pub const fn for_cells(self, cell_count: u8) -> Option<u8> {
+        match self {
+            ResetValue::Fixed(value) => Some(value),
+            ResetValue::PerCell(values) => match cell_count {
+                1..=5 => Some(values[cell_count as usize - 1]),
+                _ => Some(values[3]),
+            },
+            ResetValue::Unknown => None,
+        }
+    }
+}
+
+/// Position of a register address within a 16-bit register pair.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RegisterHalf {
+    /// Low byte; holds the address of the matching high byte.
+    Lsb(Register),
+    /// High byte; holds the address of the matching low byte.
+    Msb(Register),
+    /// Stand-alone 8-bit register (ADC results, ID registers).
+    Single,
+}
+
+/// Static metadata for one register address, taken from the datasheet register map.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterInfo {
+    pub register: Register,
+    pub access: Access,
+    pub reset: ResetValue,
+    /// Bits the host may write. Reserved bits and read-only status bits are cleared.
+    pub writable_mask: u8,
+    /// Whether the address reports device status rather than configuration.
+    pub status: bool,
+}
+
+impl RegisterInfo {
+    const fn new(
+        register: Register,
+        access: Access,
+        reset: ResetValue,
+        writable_mask: u8,
+        status: bool,
+    ) -> Self {
+        Self {
+            register,
+            access,
+            reset,
+            writable_mask,
+            status,
+        }
+    }
+}
+
+/// Metadata for every register, in `Register::ALL` order.
+pub const REGISTER_INFO: [RegisterInfo; 48] = [
+    RegisterInfo::new(
+        Register::ChargeOption0,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x0E),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption0Msb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0xE7),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeCurrent,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0xC0,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeCurrentMsb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0x1F,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeVoltage,
+        Access::ReadWrite,
+        ResetValue::PerCell([0x68, 0xD0, 0x38, 0xA0, 0x08]),
+        0xF8,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeVoltageMsb,
+        Access::ReadWrite,
+        ResetValue::PerCell([0x10, 0x20, 0x31, 0x41, 0x52]),
+        0x7F,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::OTGVoltage,
+        Access::ReadWrite,
+        ResetValue::Fixed(0xC4),
+        0xFC,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::OTGVoltageMsb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x09),
+        0x3F,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::OTGCurrent,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0x00,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::OTGCurrentMsb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x3C),
+        0x7F,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::InputVoltage,
+        Access::ReadWrite,
+        ResetValue::Unknown,
+        0xC0,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::InputVoltageMsb,
+        Access::ReadWrite,
+        ResetValue::Unknown,
+        0x3F,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::VsysMin,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0x00,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::VsysMinMsb,
+        Access::ReadWrite,
+        ResetValue::PerCell([0x24, 0x42, 0x5C, 0x7B, 0x9A]),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::IinHost,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0x00,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::IinHostMsb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x20),
+        0x7F,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargerStatus,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0x18,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ChargerStatusMsb,
+        Access::ReadOnly,
+        ResetValue::Fixed(0x00),
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ProchotStatus,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0x80,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ProchotStatusMsb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0xB8),
+        0x7B,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::IinDpm,
+        Access::ReadOnly,
+        ResetValue::Fixed(0x00),
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::IinDpmMsb,
+        Access::ReadOnly,
+        ResetValue::Fixed(0x41),
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCPSYS,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCVBUS,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCIDCHG,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCICHG,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCCMPIN,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCIIN,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCVBAT,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ADCVSYS,
+        Access::ReadOnly,
+        ResetValue::Unknown,
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ManufacturerID,
+        Access::ReadOnly,
+        ResetValue::Fixed(0x40),
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::DeviceID,
+        Access::ReadOnly,
+        ResetValue::Fixed(0xD5),
+        0x00,
+        true,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption1,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption1Msb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x3F),
+        0xFE,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption2,
+        Access::ReadWrite,
+        ResetValue::Fixed(0xB7),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption2Msb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption3,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x34),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption3Msb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x04),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ProchotOption0,
+        Access::ReadWrite,
+        ResetValue::PerCell([0x09, 0x81, 0x81, 0x81, 0x81]),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ProchotOption0Msb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x4A),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ProchotOption1,
+        Access::ReadWrite,
+        ResetValue::Fixed(0xA0),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ProchotOption1Msb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x41),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ADCOption,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ADCOptionMsb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x20),
+        0xE0,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption4,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x48),
+        0xFC,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::ChargeOption4Msb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0xFE,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::VMINActiveProtection,
+        Access::ReadWrite,
+        ResetValue::PerCell([0x04, 0x6C, 0x6C, 0x6C, 0x6C]),
+        0xFF,
+        false,
+    ),
+    RegisterInfo::new(
+        Register::VMINActiveProtectionMsb,
+        Access::ReadWrite,
+        ResetValue::Fixed(0x00),
+        0xFE,
+        false,
+    ),
+];
+
+impl Register {
+    /// Position of the register in `Register::ALL` and `REGISTER_INFO`.
+    pub(crate) const fn index(self) -> usize {
+        let addr = self as usize;
+        if addr >= 0x20 { addr - 0x10 } else { addr }
+    }
+
+    /// Returns the metadata entry for this register.
+    pub const fn info(self) -> &'static RegisterInfo {
+        &REGISTER_INFO[self.index()]
+    }
+
+    /// Returns the access type of this register.
+    pub const fn access(self) -> Access {
+        self.info().access
+    }
+
+    /// Returns the power-on reset value for a cell count, or `None` if the
+    /// register has no fixed reset value.
+    pub const fn reset_value(self, cell_count: u8) -> Option<u8> {
+        self.info().reset.for_cells(cell_count)
+    }
+
+    /// Returns the mask of bits the host may write.
+    pub const fn writable_mask(self) -> u8 {
+        self.info().writable_mask
+    }
+
+    /// Returns `true` for status, ADC and ID registers (0x20-0x2F).
+    pub const fn is_status(self) -> bool {
+        self.info().status
+    }
+
+    /// Returns which half of a 16-bit register pair this address is.
+    pub const fn pair(self) -> RegisterHalf {
+        let addr = self as u8;
+        if addr >= Register::ADCPSYS as u8 && addr <= Register::DeviceID as u8 {
+            return RegisterHalf::Single;
+        }
+        if addr & 1 == 0 {
+            RegisterHalf::Lsb(Register::ALL[self.index() + 1])
+        } else {
+            RegisterHalf::Msb(Register::ALL[self.index() - 1])
+        }
+    }
+}
+
+impl TryFrom<u8> for Register {
+    /// The address that does not map to a defined register.
+    type Error = u8;
+
+    fn try_from(addr: u8) -> Result<Self, Self::Error> {
+        let index = match addr {
+            0x00..=0x0F => addr as usize,
+            0x20..=0x3F => addr as usize - 0x10,
+            _ => return Err(addr),
+        };
+        Ok(Register::ALL[index])
+    }
+}
+
+use core::fmt;
+
 use bitflags::bitflags;
 
 /// Watchdog Timer Adjust settings (ChargeOption0 MSB bits 6:5)
@@ -137,6 +675,7 @@ bitflags! {
     /// ChargeOption0 (01h) MSB bit masks
     /// Note: WDTMR_ADJ (bits 6:5) are handled separately using the WatchdogTimerAdjust enum.
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption0MsbFlags: u8 {
         const EN_LWPWR = 1 << 7;
         // WDTMR_ADJ bits (6:5) are handled by the WatchdogTimerAdjust enum
@@ -178,9 +717,18 @@ impl defmt::Format for ChargeOption0MsbFlags {
     }
 }
 
+impl fmt::Display for ChargeOption0MsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags_part = self.bits() & !ChargeOption0MsbFlags::WDTMR_ADJ_MASK.bits();
+        let wdt_part = self.get_watchdog_timer();
+        write!(f, "Flags({:b}) WDT({:?})", flags_part, wdt_part)
+    }
+}
+
 bitflags! {
     /// ChargeOption0 (00h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption0Flags: u8 {
         const EN_CMP_LATCH = 1 << 7;
         const VSYS_UVP_ENZ = 1 << 6;
@@ -200,9 +748,16 @@ impl defmt::Format for ChargeOption0Flags {
     }
 }
 
+impl fmt::Display for ChargeOption0Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ChargerStatus (20h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargerStatusFlags: u8 {
         const STAT_AC = 1 << 7;
         const ICO_DONE = 1 << 6;
@@ -218,6 +773,7 @@ bitflags! {
 bitflags! {
     /// ChargerStatus (21h) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargerStatusFaultFlags: u8 {
         const FAULT_ACOV = 1 << 7;
         const FAULT_BATOC = 1 << 6;
@@ -233,6 +789,7 @@ bitflags! {
 bitflags! {
     /// ProchotStatus (23h) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ProchotStatusMsbFlags: u8 {
         const EN_PROCHOT_EXT = 1 << 6;
         const PROCHOT_WIDTH = 0b11 << 4;
@@ -245,6 +802,7 @@ bitflags! {
 bitflags! {
     /// ProchotStatus (22h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ProchotStatusFlags: u8 {
         const STAT_VINDPM = 1 << 7;
         const STAT_COMP = 1 << 6;
@@ -260,6 +818,7 @@ bitflags! {
 bitflags! {
     /// ChargeOption1 (31h) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption1MsbFlags: u8 {
         const EN_IBAT = 1 << 7;
         const EN_PROCHOT_LPWR = 1 << 6;
@@ -279,9 +838,110 @@ impl defmt::Format for ChargeOption1MsbFlags {
     }
 }
 
+impl fmt::Display for ChargeOption1MsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
+/// PSYS_CONFIG settings (ChargeOption1 MSB bits 5:4)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PsysMode {
+    /// PSYS = PBUS + PBAT
+    AdapterAndBattery = 0b00,
+    /// PSYS = PBUS (battery contribution blocked in forward mode, 0 in OTG mode)
+    AdapterOnly = 0b01,
+    /// Reserved, PSYS reads 0
+    Reserved = 0b10,
+    /// PSYS buffer off to minimize Iq (Default)
+    Disabled = 0b11,
+}
+
+impl PsysMode {
+    /// Returns the bit pattern for the register, shifted to the correct position.
+    pub const fn bits(self) -> u8 {
+        (self as u8) << 4
+    }
+
+    /// Creates a PsysMode from the raw register bits (shifted).
+    pub const fn from_bits(bits: u8) -> Self {
+        match (bits >> 4) & 0b11 {
+            0b00 => Self::AdapterAndBattery,
+            0b01 => Self::AdapterOnly,
+            0b10 => Self::Reserved,
+            _ => Self::Disabled,
+        }
+    }
+
+    /// Whether the PSYS output carries a power reading in this mode.
+    pub const fn is_enabled(self) -> bool {
+        matches!(self, Self::AdapterAndBattery | Self::AdapterOnly)
+    }
+}
+
+/// PSYS_RATIO settings (ChargeOption1 MSB bit 1): PSYS output current per watt of system power.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PsysRatio {
+    /// 0.25 µA/W
+    QuarterMicroampPerWatt = 0b0,
+    /// 1 µA/W (Default)
+    OneMicroampPerWatt = 0b1,
+}
+
+impl PsysRatio {
+    /// Returns the bit pattern for the register, shifted to the correct position.
+    pub const fn bits(self) -> u8 {
+        (self as u8) << 1
+    }
+
+    /// Creates a PsysRatio from the raw register bits (shifted).
+    pub const fn from_bits(bits: u8) -> Self {
+        if bits & (1 << 1) != 0 {
+            Self::OneMicroampPerWatt
+        } else {
+            Self::QuarterMicroampPerWatt
+        }
+    }
+
+    /// Gain in units of 0.25 µA/W.
+    pub const fn quarter_microamps_per_watt(self) -> u32 {
+        match self {
+            Self::QuarterMicroampPerWatt => 1,
+            Self::OneMicroampPerWatt => 4,
+        }
+    }
+}
+
+impl ChargeOption1MsbFlags {
+    /// Sets the PSYS_CONFIG bits.
+    pub fn set_psys_mode(&mut self, mode: PsysMode) {
+        self.remove(Self::PSYS_CONFIG);
+        self.insert(Self::from_bits_retain(mode.bits()));
+    }
+
+    /// Gets the PSYS_CONFIG bits.
+    pub fn get_psys_mode(&self) -> PsysMode {
+        PsysMode::from_bits(self.bits())
+    }
+
+    /// Sets the PSYS_RATIO bit.
+    pub fn set_psys_ratio(&mut self, ratio: PsysRatio) {
+        self.remove(Self::PSYS_RATIO);
+        self.insert(Self::from_bits_retain(ratio.bits()));
+    }
+
+    /// Gets the PSYS_RATIO bit.
+    pub fn get_psys_ratio(&self) -> PsysRatio {
+        PsysRatio::from_bits(self.bits())
+    }
+}
+
 bitflags! {
     /// ChargeOption1 (30h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption1Flags: u8 {
         const CMP_REF = 1 << 7;
         const CMP_POL = 1 << 6;
@@ -300,9 +960,16 @@ impl defmt::Format for ChargeOption1Flags {
     }
 }
 
+impl fmt::Display for ChargeOption1Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ChargeOption2 (33h) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption2MsbFlags: u8 {
         const PKPWR_TOVLD_DEG = 0b11 << 6;
         const EN_PKPWR_IIN_DPM = 1 << 5;
@@ -320,9 +987,16 @@ impl defmt::Format for ChargeOption2MsbFlags {
     }
 }
 
+impl fmt::Display for ChargeOption2MsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ChargeOption2 (32h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption2Flags: u8 {
         const EN_EXTILIM = 1 << 7;
         const EN_ICHG_IDCHG = 1 << 6;
@@ -342,9 +1016,16 @@ impl defmt::Format for ChargeOption2Flags {
     }
 }
 
+impl fmt::Display for ChargeOption2Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ChargeOption3 (35h) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption3MsbFlags: u8 {
         const EN_HIZ = 1 << 7;
         const RESET_REG = 1 << 6;
@@ -380,9 +1061,16 @@ impl defmt::Format for ChargeOption3MsbFlags {
     }
 }
 
+impl fmt::Display for ChargeOption3MsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ChargeOption3 (34h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption3Flags: u8 {
         const BATFET_ENZ = 1 << 7;
         const EN_VBUS_VAP = 1 << 6;
@@ -401,9 +1089,16 @@ impl defmt::Format for ChargeOption3Flags {
     }
 }
 
+impl fmt::Display for ChargeOption3Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ProchotOption0 (37h) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ProchotOption0MsbFlags: u8 {
         const ILIM2_VTH = 0b11111 << 3;
         const ICRIT_DEG = 0b11 << 1;
@@ -418,9 +1113,16 @@ impl defmt::Format for ProchotOption0MsbFlags {
     }
 }
 
+impl fmt::Display for ProchotOption0MsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ProchotOption0 (36h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ProchotOption0Flags: u8 {
         const VSYS_TH1 = 0b111111 << 2;
         const INOM_DEG = 1 << 1;
@@ -435,9 +1137,16 @@ impl defmt::Format for ProchotOption0Flags {
     }
 }
 
+impl fmt::Display for ProchotOption0Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ProchotOption1 (39h) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ProchotOption1MsbFlags: u8 {
         const IDCHG_TH1 = 0b111111 << 2;
         const IDCHG_DEG1 = 0b11;
@@ -451,9 +1160,16 @@ impl defmt::Format for ProchotOption1MsbFlags {
     }
 }
 
+impl fmt::Display for ProchotOption1MsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ProchotOption1 (38h) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ProchotOption1Flags: u8 {
         const PP_VINDPM = 1 << 7;
         const PP_COMP = 1 << 6;
@@ -473,9 +1189,16 @@ impl defmt::Format for ProchotOption1Flags {
     }
 }
 
+impl fmt::Display for ProchotOption1Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ADCOption (3Bh) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct AdcOptionMsbFlags: u8 {
         const ADC_CONV = 1 << 7;
         const ADC_START = 1 << 6;
@@ -495,9 +1218,16 @@ impl defmt::Format for AdcOptionMsbFlags {
     }
 }
 
+impl fmt::Display for AdcOptionMsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// ADCOption (3Ah) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct AdcOptionFlags: u8 {
         const EN_ADC_CMPIN = 1 << 7;
         const EN_ADC_VBUS = 1 << 6;
@@ -517,9 +1247,16 @@ impl defmt::Format for AdcOptionFlags {
     }
 }
 
+impl fmt::Display for AdcOptionFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 /// Dither Setting for ChargeOption4 (REG0x3D[4:3])
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DitherSetting {
     Disabled = 0b00,
     Dither1X = 0b01, // +/- 2%
@@ -545,6 +1282,7 @@ impl DitherSetting {
 bitflags! {
     /// ChargeOption4 (3Dh) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption4MsbFlags: u8 {
         const VSYS_UVP = 0b111 << 5;
         // EN_DITHER (bits 4:3) are handled by DitherSetting enum
@@ -585,9 +1323,18 @@ impl defmt::Format for ChargeOption4MsbFlags {
     }
 }
 
+impl fmt::Display for ChargeOption4MsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags_part = self.bits() & !ChargeOption4MsbFlags::EN_DITHER_MASK.bits();
+        let dither_part = self.get_en_dither();
+        write!(f, "Flags({:b}) EN_DITHER({:?})", flags_part, dither_part)
+    }
+}
+
 bitflags! {
     /// ChargeOption4 (3Ch) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ChargeOption4Flags: u8 {
         const IDCHG_DEG2 = 0b11 << 6;
         const IDCHG_TH2 = 0b111 << 3;
@@ -604,9 +1351,16 @@ impl defmt::Format for ChargeOption4Flags {
     }
 }
 
+impl fmt::Display for ChargeOption4Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.bits())
+    }
+}
+
 bitflags! {
     /// VminActiveProtection (3Fh) MSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct VminActiveProtectionMsbFlags: u8 {
         // VBUS_VAP_TH bits 7:1 (0xFE)
         const VBUS_VAP_TH_MASK = 0b11111110;
@@ -637,9 +1391,16 @@ impl defmt::Format for VminActiveProtectionMsbFlags {
     }
 }
 
+impl fmt::Display for VminActiveProtectionMsbFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VBUS_VAP_TH: {}", self.get_vbus_vap_th())
+    }
+}
+
 bitflags! {
     /// VminActiveProtection (3Eh) LSB bit masks
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct VminActiveProtectionFlags: u8 {
         // VSYS_TH2 bits 7:2 (0xFC)
         const VSYS_TH2_MASK = 0b11111100;
@@ -700,3 +1461,15 @@ impl defmt::Format for VminActiveProtectionFlags {
         );
     }
 }
+
+impl fmt::Display for VminActiveProtectionFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "VSYS_TH2: {}, EN_VSYSTH2_FOLLOW_VSYSTH1: {}, EN_FRS: {}",
+            self.get_vsys_th2(),
+            self.get_en_vsysth2_follow_vsysth1(),
+            self.get_en_frs()
+        )
+    }
+}
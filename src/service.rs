@@ -0,0 +1,178 @@
+//! Ready-made charger service for embassy.
+//!
+//! With the `embassy` feature, [`ChargerService`] owns the driver and runs
+//! the usual main loop: every `poll_interval` it rewrites ChargeCurrent to
+//! keep the charger watchdog from expiring, reads ChargerStatus,
+//! ProchotStatus and the ADC block, and publishes the result as a
+//! [`Telemetry`] snapshot on a [`Watch`]. In between it executes
+//! [`Command`]s received on a [`Channel`].
+//!
+//! The service is generic, so it is wrapped in a task of the application:
+//!
+//! ```ignore
+//! static COMMANDS: Channel<CriticalSectionRawMutex, Command, 4> = Channel::new();
+//! static TELEMETRY: Watch<CriticalSectionRawMutex, Telemetry, 2> = Watch::new();
+//!
+//! #[embassy_executor::task]
+//! async fn charger_task(
+//!     mut service: ChargerService<'static, I2c<'static, Async>, CriticalSectionRawMutex, 4, 2>,
+//! ) -> ! {
+//!     service.run().await
+//! }
+//!
+//! charger.init().await?;
+//! let service = ChargerService::new(charger, &COMMANDS, &TELEMETRY, Duration::from_secs(1));
+//! spawner.spawn(charger_task(service)).unwrap();
+//! ```
+//!
+//! I2C errors are logged and do not stop the service; the next tick tries
+//! again.
+
+use embassy_futures::select::{Either, select};
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::channel::{Channel, Receiver};
+use embassy_sync::watch::{Sender, Watch};
+use embassy_time::{Duration, Ticker};
+use embedded_hal_async::i2c::I2c;
+
+use crate::Bq25730;
+use crate::data_types::{
+    AdcMeasurements, ChargeCurrentSetting, ChargeVoltageSetting, ChargerStatus, ProchotStatus,
+};
+use crate::errors::Error;
+use crate::units::{MilliAmps, MilliVolts};
+
+/// A request for the charger service.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Command {
+    /// Writes ChargeCurrent; the value is also used for the watchdog keep-alive.
+    SetChargeCurrent(MilliAmps),
+    /// Writes ChargeVoltage.
+    SetChargeVoltage(MilliVolts),
+    /// Sets or clears EN_OTG.
+    SetOtg(bool),
+    /// Sets EN_SHIP_DCHG.
+    EnterShipMode,
+}
+
+/// Status and ADC readings taken on one poll.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Telemetry {
+    pub charger_status: ChargerStatus,
+    pub prochot_status: ProchotStatus,
+    pub adc: AdcMeasurements,
+}
+
+/// Owns the driver and serves commands and telemetry; see the module docs.
+///
+/// `C` is the capacity of the command channel and `W` the number of
+/// telemetry receivers.
+pub struct ChargerService<'a, I2C, M, const C: usize, const W: usize>
+where
+    I2C: I2c + 'static,
+    M: RawMutex,
+{
+    charger: Bq25730<I2C>,
+    commands: Receiver<'a, M, Command, C>,
+    telemetry: Sender<'a, M, Telemetry, W>,
+    poll_interval: Duration,
+    /// Last charge current written, rewritten on every poll.
+    charge_current: ChargeCurrentSetting,
+}
+
+impl<'a, I2C, E, M, const C: usize, const W: usize> ChargerService<'a, I2C, M, C, W>
+where
+    I2C: I2c<Error = E> + Send,
+    M: RawMutex,
+{
+    /// Creates the service. The charger is expected to be initialized; the
+    /// keep-alive starts from the charge current of its config.
+    pub fn new(
+        charger: Bq25730<I2C>,
+        commands: &'a Channel<M, Command, C>,
+        telemetry: &'a Watch<M, Telemetry, W>,
+        poll_interval: Duration,
+    ) -> Self {
+        let charge_current = charger.config().charge_current;
+        Self {
+            charger,
+            commands: commands.receiver(),
+            telemetry: telemetry.sender(),
+            poll_interval,
+            charge_current,
+        }
+    }
+
+    /// Returns a reference to the driver.
+    pub fn charger(&self) -> &Bq25730<I2C> {
+        &self.charger
+    }
+
+    /// Returns the driver.
+    pub fn release(self) -> Bq25730<I2C> {
+        self.charger
+    }
+
+    /// Rewrites ChargeCurrent, reads status and ADC, and publishes them.
+    pub async fn poll(&mut self) -> Result<Telemetry, Error<E>> {
+        self.charger
+            .set_charge_current_setting(self.charge_current)
+            .await?;
+        let telemetry = Telemetry {
+            charger_status: self.charger.read_charger_status().await?,
+            prochot_status: self.charger.read_prochot_status().await?,
+            adc: self.charger.read_adc_measurements().await?,
+        };
+        self.telemetry.send(telemetry);
+        Ok(telemetry)
+    }
+
+    /// Executes one command.
+    pub async fn handle(&mut self, command: Command) -> Result<(), Error<E>> {
+        debug!("Charger service: {:?}", command);
+        match command {
+            Command::SetChargeCurrent(milliamps) => {
+                let setting = ChargeCurrentSetting {
+                    milliamps,
+                    rsns_bat: self.charger.config().rsns_bat,
+                };
+                self.charger.set_charge_current_setting(setting).await?;
+                self.charge_current = setting;
+            }
+            Command::SetChargeVoltage(millivolts) => {
+                self.charger
+                    .set_charge_voltage_setting(ChargeVoltageSetting::from_millivolts(millivolts))
+                    .await?;
+            }
+            Command::SetOtg(true) => self.charger.enable_otg().await?,
+            Command::SetOtg(false) => self.charger.disable_otg().await?,
+            Command::EnterShipMode => self.charger.enter_ship_mode().await?,
+        }
+        Ok(())
+    }
+
+    /// Polls every `poll_interval` and executes commands as they arrive.
+    pub async fn run(&mut self) -> ! {
+        let mut ticker = Ticker::every(self.poll_interval);
+        loop {
+            match select(ticker.next(), self.commands.receive()).await {
+                Either::First(()) => {
+                    if let Err(e) = self.poll().await {
+                        warn!("Charger service: poll failed on {:?}", e.register());
+                    }
+                }
+                Either::Second(command) => {
+                    if let Err(e) = self.handle(command).await {
+                        warn!(
+                            "Charger service: {:?} failed on {:?}",
+                            command,
+                            e.register()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
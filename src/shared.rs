@@ -0,0 +1,181 @@
+//! Sharing the blocking driver between tasks and interrupts.
+//!
+//! With the `critical-section` feature (and without `async`),
+//! [`SharedBq25730`] guards the driver with a busy flag. The flag is only
+//! tested and set inside a short critical section; the I2C transfer itself
+//! runs with interrupts enabled. Each method claims the driver for a single
+//! driver call only, so a multi-register sequence can be interleaved with
+//! other contexts; use [`SharedBq25730::lock`] when it must not be.
+//!
+//! An interrupt that preempts a task in the middle of a transfer finds the
+//! flag set. The `try_*` methods then return [`Error::WouldBlock`] instead of
+//! touching the bus, which is what an interrupt handler should use, e.g. to
+//! snapshot ChargerStatus and ProchotStatus when /PROCHOT asserts:
+//!
+//! ```ignore
+//! // `charger: &'static SharedBq25730<I2c>`, also held by the polling task.
+//! #[task(binds = EXTI0, local = [charger])]
+//! fn prochot(cx: prochot::Context) {
+//!     match cx.local.charger.try_read_status() {
+//!         Ok((charger_status, prochot_status)) => { /* ... */ }
+//!         // The polling task was mid-transfer; it picks this up next poll.
+//!         Err(Error::WouldBlock) => {}
+//!         Err(e) => { /* ... */ }
+//!     }
+//! }
+//! ```
+
+use core::cell::{Cell, UnsafeCell};
+
+use critical_section::Mutex;
+use embedded_hal::i2c::I2c;
+
+use crate::Bq25730;
+use crate::data_types::{
+    AdcMeasurements, ChargeCurrentSetting, ChargeVoltageSetting, ChargerStatus, ProchotStatus,
+};
+use crate::errors::Error;
+
+/// A [`Bq25730`] that can be used from several tasks and interrupts.
+pub struct SharedBq25730<I2C>
+where
+    I2C: I2c + 'static,
+{
+    busy: Mutex<Cell<bool>>,
+    inner: UnsafeCell<Bq25730<I2C>>,
+}
+
+// SAFETY: `inner` is only accessed through `Claim`, which is handed out to
+// one context at a time by the `busy` flag.
+unsafe impl<I2C> Sync for SharedBq25730<I2C> where I2C: I2c + Send + 'static {}
+
+/// Exclusive access to the driver; clears the busy flag when dropped.
+struct Claim<'a, I2C>
+where
+    I2C: I2c + 'static,
+{
+    shared: &'a SharedBq25730<I2C>,
+}
+
+impl<I2C> Drop for Claim<'_, I2C>
+where
+    I2C: I2c + 'static,
+{
+    fn drop(&mut self) {
+        critical_section::with(|cs| self.shared.busy.borrow(cs).set(false));
+    }
+}
+
+impl<I2C, E> SharedBq25730<I2C>
+where
+    I2C: I2c<Error = E> + Send,
+{
+    pub const fn new(charger: Bq25730<I2C>) -> Self {
+        Self {
+            busy: Mutex::new(Cell::new(false)),
+            inner: UnsafeCell::new(charger),
+        }
+    }
+
+    /// Returns the driver.
+    pub fn into_inner(self) -> Bq25730<I2C> {
+        self.inner.into_inner()
+    }
+
+    fn claim(&self) -> Option<Claim<'_, I2C>> {
+        critical_section::with(|cs| {
+            let busy = self.busy.borrow(cs);
+            if busy.replace(true) {
+                None
+            } else {
+                Some(Claim { shared: self })
+            }
+        })
+    }
+
+    fn run<R>(claim: Claim<'_, I2C>, f: impl FnOnce(&mut Bq25730<I2C>) -> R) -> R {
+        // SAFETY: holding the claim means no other context can reach `inner`
+        // until it is dropped at the end of this function.
+        f(unsafe { &mut *claim.shared.inner.get() })
+    }
+
+    /// Runs `f` on the driver with exclusive access. Interrupts stay enabled
+    /// while `f` runs.
+    ///
+    /// # Panics
+    ///
+    /// If the driver is already in use, e.g. when called from inside another
+    /// `lock` closure or from an interrupt that preempted one. Interrupt
+    /// handlers should use [`try_lock`](Self::try_lock).
+    pub fn lock<R>(&self, f: impl FnOnce(&mut Bq25730<I2C>) -> R) -> R {
+        let claim = self.claim().expect("SharedBq25730 is already in use");
+        Self::run(claim, f)
+    }
+
+    /// Like [`lock`](Self::lock), but returns `Error::WouldBlock` if the
+    /// driver is already in use.
+    pub fn try_lock<R>(
+        &self,
+        f: impl FnOnce(&mut Bq25730<I2C>) -> Result<R, Error<E>>,
+    ) -> Result<R, Error<E>> {
+        let claim = self.claim().ok_or(Error::WouldBlock)?;
+        Self::run(claim, f)
+    }
+
+    /// Reads the Charger Status register.
+    pub fn read_charger_status(&self) -> Result<ChargerStatus, Error<E>> {
+        self.lock(|charger| charger.read_charger_status())
+    }
+
+    /// Reads the Prochot Status register.
+    pub fn read_prochot_status(&self) -> Result<ProchotStatus, Error<E>> {
+        self.lock(|charger| charger.read_prochot_status())
+    }
+
+    /// Reads all ADC measurement registers.
+    pub fn read_adc_measurements(&self) -> Result<AdcMeasurements, Error<E>> {
+        self.lock(|charger| charger.read_adc_measurements())
+    }
+
+    /// Writes the Charge Current register with the setting.
+    pub fn set_charge_current_setting(
+        &self,
+        current: ChargeCurrentSetting,
+    ) -> Result<(), Error<E>> {
+        self.lock(|charger| charger.set_charge_current_setting(current))
+    }
+
+    /// Writes the Charge Voltage register with the setting.
+    pub fn set_charge_voltage_setting(
+        &self,
+        voltage: ChargeVoltageSetting,
+    ) -> Result<(), Error<E>> {
+        self.lock(|charger| charger.set_charge_voltage_setting(voltage))
+    }
+
+    /// Enters ship mode.
+    pub fn enter_ship_mode(&self) -> Result<(), Error<E>> {
+        self.lock(|charger| charger.enter_ship_mode())
+    }
+
+    /// Reads the Charger Status register, or returns `Error::WouldBlock`.
+    pub fn try_read_charger_status(&self) -> Result<ChargerStatus, Error<E>> {
+        self.try_lock(|charger| charger.read_charger_status())
+    }
+
+    /// Reads the Prochot Status register, or returns `Error::WouldBlock`.
+    pub fn try_read_prochot_status(&self) -> Result<ProchotStatus, Error<E>> {
+        self.try_lock(|charger| charger.read_prochot_status())
+    }
+
+    /// Reads ChargerStatus and ProchotStatus under one claim, or
+    /// returns `Error::WouldBlock`.
+    pub fn try_read_status(&self) -> Result<(ChargerStatus, ProchotStatus), Error<E>> {
+        self.try_lock(|charger| {
+            Ok((
+                charger.read_charger_status()?,
+                charger.read_prochot_status()?,
+            ))
+        })
+    }
+}
@@ -0,0 +1,635 @@
+//! I2C transaction tracing and replay.
+//!
+//! [`TracingI2c`] wraps the bus handed to [`Bq25730`](crate::Bq25730) and
+//! records every transaction into a [`TraceSink`]: a bounded [`TraceBuffer`]
+//! ring buffer, or a [`TraceWriter`] on `std`. [`ReplayI2c`] plays a captured
+//! trace back as an I2C bus, so a field capture can be replayed against the
+//! driver in a test.
+//!
+//! # Text format
+//!
+//! [`TraceEntry`] displays as one line and parses back with `str::parse`:
+//!
+//! ```text
+//! 1500 0x6b write_read w=20 r=0080 ok  # ChargerStatus
+//! 1800 0x6b write w=020002 - err:nack_data  # ChargeCurrent
+//! ```
+//!
+//! Fields are the timestamp in microseconds, the 7-bit address, the
+//! transaction kind, written bytes (`w=`), read bytes (`r=`, or `-` when
+//! nothing was read) and the result. Everything after `#` is ignored.
+
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+use embedded_hal::i2c::{Error, ErrorKind, ErrorType, NoAcknowledgeSource, Operation};
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c;
+
+use crate::registers::Register;
+use crate::units::Micros;
+
+/// Maximum number of written or read bytes stored per transaction.
+///
+/// The driver never transfers more than 31 bytes in one direction; longer
+/// transfers are truncated in the trace.
+pub const TRACE_DATA_LEN: usize = 32;
+
+/// Bytes written or read in one traced transaction.
+pub type TraceData = heapless::Vec<u8, TRACE_DATA_LEN>;
+
+/// Shape of a traced transaction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TraceKind {
+    /// `I2c::write`, or a `transaction` with only write operations.
+    Write,
+    /// `I2c::read`, or a `transaction` with only read operations.
+    Read,
+    /// `I2c::write_read`, or a `transaction` mixing both.
+    WriteRead,
+}
+
+impl TraceKind {
+    fn name(self) -> &'static str {
+        match self {
+            TraceKind::Write => "write",
+            TraceKind::Read => "read",
+            TraceKind::WriteRead => "write_read",
+        }
+    }
+}
+
+/// One recorded I2C transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Time the transaction started, from the clock given to `TracingI2c`.
+    pub timestamp: Micros,
+    pub address: u8,
+    pub kind: TraceKind,
+    /// Bytes written, starting with the register address.
+    pub write: TraceData,
+    /// Bytes read. For failed reads this is whatever the bus left in the buffer.
+    pub read: TraceData,
+    pub result: Result<(), ErrorKind>,
+}
+
+impl TraceEntry {
+    /// Returns the register addressed by the first written byte, if any.
+    pub fn register(&self) -> Option<Register> {
+        self.write
+            .first()
+            .and_then(|&addr| Register::try_from(addr).ok())
+    }
+}
+
+/// Receives every transaction recorded by a `TracingI2c`.
+pub trait TraceSink {
+    fn record(&mut self, entry: &TraceEntry);
+}
+
+/// Ring buffer of the last `N` transactions. Older entries are dropped once it is full.
+#[derive(Debug, Default)]
+pub struct TraceBuffer<const N: usize> {
+    entries: heapless::Deque<TraceEntry, N>,
+    dropped: u32,
+}
+
+impl<const N: usize> TraceBuffer<N> {
+    pub const fn new() -> Self {
+        Self {
+            entries: heapless::Deque::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Iterates over the buffered entries, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of entries overwritten since the buffer was created or cleared.
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
+}
+
+impl<const N: usize> TraceSink for TraceBuffer<N> {
+    fn record(&mut self, entry: &TraceEntry) {
+        if self.entries.is_full() {
+            self.entries.pop_front();
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        // Cannot fail: a slot was freed above.
+        let _ = self.entries.push_back(entry.clone());
+    }
+}
+
+/// Writes each transaction as one line of the text format.
+///
+/// Write errors do not interrupt the traced transaction; the first one is kept
+/// and can be checked with `take_error()`.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct TraceWriter<W: std::io::Write> {
+    writer: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> TraceWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Returns the first write error, if any, and clears it.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> TraceSink for TraceWriter<W> {
+    fn record(&mut self, entry: &TraceEntry) {
+        if let Err(e) = writeln!(self.writer, "{entry}") {
+            self.error.get_or_insert(e);
+        }
+    }
+}
+
+/// I2C bus wrapper that records every transaction into a [`TraceSink`].
+///
+/// Timestamps come from `clock`; `TracingI2c::new` records them as zero.
+pub struct TracingI2c<I2C, S, C = fn() -> Micros> {
+    inner: I2C,
+    sink: S,
+    clock: C,
+}
+
+impl<I2C, S> TracingI2c<I2C, S> {
+    pub fn new(inner: I2C, sink: S) -> Self {
+        Self::with_clock(inner, sink, || Micros::ZERO)
+    }
+}
+
+impl<I2C, S, C> TracingI2c<I2C, S, C>
+where
+    C: FnMut() -> Micros,
+{
+    pub fn with_clock(inner: I2C, sink: S, clock: C) -> Self {
+        Self { inner, sink, clock }
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    pub fn inner_mut(&mut self) -> &mut I2C {
+        &mut self.inner
+    }
+
+    pub fn release(self) -> (I2C, S) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<I2C, S, C> TracingI2c<I2C, S, C>
+where
+    I2C: ErrorType,
+    S: TraceSink,
+    C: FnMut() -> Micros,
+{
+    fn record<T>(
+        &mut self,
+        timestamp: Micros,
+        address: u8,
+        kind: TraceKind,
+        write: TraceData,
+        read: TraceData,
+        result: &Result<T, I2C::Error>,
+    ) {
+        self.sink.record(&TraceEntry {
+            timestamp,
+            address,
+            kind,
+            write,
+            read,
+            result: result.as_ref().map(|_| ()).map_err(|e| e.kind()),
+        });
+    }
+}
+
+impl<I2C: ErrorType, S, C> ErrorType for TracingI2c<I2C, S, C> {
+    type Error = I2C::Error;
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), self = "TracingI2c",),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, S, C> I2c for TracingI2c<I2C, S, C>
+where
+    I2C: I2c,
+    S: TraceSink,
+    C: FnMut() -> Micros,
+{
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.inner.read(address, read).await;
+        self.record(
+            timestamp,
+            address,
+            TraceKind::Read,
+            TraceData::new(),
+            truncated(read),
+            &result,
+        );
+        result
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.inner.write(address, write).await;
+        self.record(
+            timestamp,
+            address,
+            TraceKind::Write,
+            truncated(write),
+            TraceData::new(),
+            &result,
+        );
+        result
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.inner.write_read(address, write, read).await;
+        self.record(
+            timestamp,
+            address,
+            TraceKind::WriteRead,
+            truncated(write),
+            truncated(read),
+            &result,
+        );
+        result
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.inner.transaction(address, operations).await;
+        let (kind, write, read) = flatten(operations);
+        self.record(timestamp, address, kind, write, read, &result);
+        result
+    }
+}
+
+/// Error returned by [`ReplayI2c`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The trace recorded this error for the transaction; it is returned again.
+    Recorded(ErrorKind),
+    /// The transaction at `index` in the trace has a different address, kind,
+    /// written bytes or read length than the one requested.
+    Mismatch { index: usize },
+    /// Every transaction in the trace has already been replayed.
+    Exhausted,
+    /// `finish()` was called with `remaining` entries not yet replayed.
+    Unconsumed { remaining: usize },
+}
+
+impl Error for ReplayError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ReplayError::Recorded(kind) => *kind,
+            ReplayError::Mismatch { .. }
+            | ReplayError::Exhausted
+            | ReplayError::Unconsumed { .. } => ErrorKind::Other,
+        }
+    }
+}
+
+/// I2C bus that answers from a captured trace instead of hardware.
+///
+/// Each transaction must match the next trace entry (address, kind, written
+/// bytes and read length); read buffers are then filled from the entry and its
+/// recorded result is returned. Timestamps are ignored.
+///
+/// `T` is anything holding the entries, e.g. a `&'static [TraceEntry]`, a
+/// `heapless::Vec` or, on `std`, a `Vec`.
+#[derive(Debug)]
+pub struct ReplayI2c<T> {
+    trace: T,
+    position: usize,
+}
+
+impl<T: AsRef<[TraceEntry]>> ReplayI2c<T> {
+    pub fn new(trace: T) -> Self {
+        Self { trace, position: 0 }
+    }
+
+    /// Number of entries not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.trace.as_ref().len() - self.position
+    }
+
+    /// Checks that the whole trace was replayed.
+    pub fn finish(&self) -> Result<(), ReplayError> {
+        match self.remaining() {
+            0 => Ok(()),
+            remaining => Err(ReplayError::Unconsumed { remaining }),
+        }
+    }
+
+    fn replay(
+        &mut self,
+        address: u8,
+        kind: TraceKind,
+        write: &[u8],
+        reads: &mut [&mut [u8]],
+    ) -> Result<(), ReplayError> {
+        let index = self.position;
+        let entry = self
+            .trace
+            .as_ref()
+            .get(index)
+            .ok_or(ReplayError::Exhausted)?;
+
+        let read_len: usize = reads.iter().map(|buf| buf.len()).sum();
+        let matches = entry.address == address
+            && entry.kind == kind
+            && entry.write.as_slice() == &write[..write.len().min(TRACE_DATA_LEN)]
+            && entry.read.len() == read_len.min(TRACE_DATA_LEN);
+        if !matches {
+            return Err(ReplayError::Mismatch { index });
+        }
+
+        let mut recorded = entry.read.iter().copied();
+        for byte in reads.iter_mut().flat_map(|buf| buf.iter_mut()) {
+            *byte = recorded.next().unwrap_or(0);
+        }
+        self.position += 1;
+        entry.result.map_err(ReplayError::Recorded)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ReplayI2c<std::vec::Vec<TraceEntry>> {
+    /// Parses a trace in the text format, skipping blank and comment-only lines.
+    pub fn from_text(text: &str) -> Result<Self, TraceParseError> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.split('#').next().unwrap_or_default().trim().is_empty())
+            .map(|(index, line)| {
+                line.parse()
+                    .map_err(|_| TraceParseError { line: index + 1 })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+}
+
+impl<T> ErrorType for ReplayI2c<T> {
+    type Error = ReplayError;
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), self = "ReplayI2c",),
+    async(feature = "async", keep_self)
+)]
+impl<T: AsRef<[TraceEntry]>> I2c for ReplayI2c<T> {
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.replay(address, TraceKind::Read, &[], &mut [read])
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.replay(address, TraceKind::Write, write, &mut [])
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.replay(address, TraceKind::WriteRead, write, &mut [read])
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let (kind, write, _) = flatten(operations);
+        let mut reads: heapless::Vec<&mut [u8], 8> = heapless::Vec::new();
+        for op in operations.iter_mut() {
+            if let Operation::Read(buf) = op {
+                reads.push(buf).map_err(|_| ReplayError::Mismatch {
+                    index: self.position,
+                })?;
+            }
+        }
+        self.replay(address, kind, &write, &mut reads)
+    }
+}
+
+fn truncated(bytes: &[u8]) -> TraceData {
+    TraceData::from_slice(&bytes[..bytes.len().min(TRACE_DATA_LEN)]).unwrap_or_default()
+}
+
+/// Concatenates the written and read bytes of a transaction.
+fn flatten(operations: &[Operation<'_>]) -> (TraceKind, TraceData, TraceData) {
+    let mut write = TraceData::new();
+    let mut read = TraceData::new();
+    let (mut has_write, mut has_read) = (false, false);
+    for op in operations {
+        let (bytes, target): (&[u8], _) = match op {
+            Operation::Write(bytes) => {
+                has_write = true;
+                (bytes, &mut write)
+            }
+            Operation::Read(bytes) => {
+                has_read = true;
+                (bytes, &mut read)
+            }
+        };
+        let room = TRACE_DATA_LEN - target.len();
+        // Cannot fail: at most `room` bytes are added.
+        let _ = target.extend_from_slice(&bytes[..bytes.len().min(room)]);
+    }
+    let kind = match (has_write, has_read) {
+        (_, false) => TraceKind::Write,
+        (false, true) => TraceKind::Read,
+        (true, true) => TraceKind::WriteRead,
+    };
+    (kind, write, read)
+}
+
+fn error_name(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Bus => "bus",
+        ErrorKind::ArbitrationLoss => "arbitration_loss",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => "nack_address",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => "nack_data",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown) => "nack",
+        ErrorKind::Overrun => "overrun",
+        _ => "other",
+    }
+}
+
+fn error_from_name(name: &str) -> Option<ErrorKind> {
+    Some(match name {
+        "bus" => ErrorKind::Bus,
+        "arbitration_loss" => ErrorKind::ArbitrationLoss,
+        "nack_address" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        "nack_data" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        "nack" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+        "overrun" => ErrorKind::Overrun,
+        "other" => ErrorKind::Other,
+        _ => return None,
+    })
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:#04x} {} ",
+            self.timestamp.0,
+            self.address,
+            self.kind.name()
+        )?;
+        match self.kind {
+            TraceKind::Read => f.write_str("-")?,
+            _ => write!(f, "w={}", Hex(&self.write))?,
+        }
+        match self.kind {
+            TraceKind::Write => f.write_str(" -")?,
+            _ => write!(f, " r={}", Hex(&self.read))?,
+        }
+        match self.result {
+            Ok(()) => f.write_str(" ok")?,
+            Err(kind) => write!(f, " err:{}", error_name(kind))?,
+        }
+        match self.register() {
+            Some(register) => write!(f, "  # {register:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Error returned when a trace line cannot be parsed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TraceParseError {
+    /// 1-based line number; always 1 when parsing a single `TraceEntry`.
+    pub line: usize,
+}
+
+impl fmt::Display for TraceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: invalid trace entry", self.line)
+    }
+}
+
+impl FromStr for TraceEntry {
+    type Err = TraceParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const ERR: TraceParseError = TraceParseError { line: 1 };
+
+        fn data(field: &str, prefix: &str) -> Option<TraceData> {
+            if field == "-" {
+                return Some(TraceData::new());
+            }
+            let hex = field.strip_prefix(prefix)?;
+            if hex.len() % 2 != 0 || hex.len() / 2 > TRACE_DATA_LEN {
+                return None;
+            }
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect()
+        }
+
+        let mut fields = s.split('#').next().unwrap_or_default().split_whitespace();
+        let mut next = || fields.next().ok_or(ERR);
+
+        let timestamp = next()?.parse().map_err(|_| ERR)?;
+        let address = next()?;
+        let address = address
+            .strip_prefix("0x")
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .ok_or(ERR)?;
+        let kind = match next()? {
+            "write" => TraceKind::Write,
+            "read" => TraceKind::Read,
+            "write_read" => TraceKind::WriteRead,
+            _ => return Err(ERR),
+        };
+        let write = data(next()?, "w=").ok_or(ERR)?;
+        let read = data(next()?, "r=").ok_or(ERR)?;
+        let result = match next()? {
+            "ok" => Ok(()),
+            other => Err(other
+                .strip_prefix("err:")
+                .and_then(error_from_name)
+                .ok_or(ERR)?),
+        };
+        if fields.next().is_some() {
+            return Err(ERR);
+        }
+
+        Ok(TraceEntry {
+            timestamp: Micros(timestamp),
+            address,
+            kind,
+            write,
+            read,
+            result,
+        })
+    }
+}
@@ -0,0 +1,263 @@
+//! Physical unit newtypes used by the register types in [`crate::data_types`].
+//!
+//! Each unit wraps the integer the chip works in (mV, mA, mW, µs), so a
+//! voltage can't be passed where a current is expected. The inner value is
+//! public for register arithmetic and interop with plain integers.
+
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+#[cfg(feature = "binrw")]
+use binrw::{BinRead, BinWrite};
+
+macro_rules! unit {
+    ($(#[$meta:meta])* $name:ident($inner:ty), $suffix:literal, $defmt:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "binrw", derive(BinRead, BinWrite))]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
+        #[repr(transparent)]
+        pub struct $name(pub $inner);
+
+        impl $name {
+            /// Zero of this unit.
+            pub const ZERO: Self = Self(0);
+            /// Largest representable value.
+            pub const MAX: Self = Self(<$inner>::MAX);
+
+            /// Wraps a raw value in this unit.
+            pub const fn new(value: $inner) -> Self {
+                Self(value)
+            }
+
+            /// Returns the raw value.
+            pub const fn get(self) -> $inner {
+                self.0
+            }
+
+            /// Adds two values, saturating at [`Self::MAX`].
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self(self.0.saturating_add(rhs.0))
+            }
+
+            /// Subtracts `rhs`, saturating at zero.
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self(self.0.saturating_sub(rhs.0))
+            }
+
+            /// Adds two values, returning `None` on overflow.
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                match self.0.checked_add(rhs.0) {
+                    Some(value) => Some(Self(value)),
+                    None => None,
+                }
+            }
+
+            /// Subtracts `rhs`, returning `None` on underflow.
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.0.checked_sub(rhs.0) {
+                    Some(value) => Some(Self(value)),
+                    None => None,
+                }
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl PartialEq<$inner> for $name {
+            fn eq(&self, other: &$inner) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialOrd<$inner> for $name {
+            fn partial_cmp(&self, other: &$inner) -> Option<core::cmp::Ordering> {
+                self.0.partial_cmp(other)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        /// Scales by a dimensionless factor.
+        impl Mul<$inner> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: $inner) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        /// Divides by a dimensionless factor.
+        impl Div<$inner> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: $inner) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        /// Ratio of two values of the same unit, e.g. the number of LSB steps.
+        impl Div for $name {
+            type Output = $inner;
+
+            fn div(self, rhs: Self) -> $inner {
+                self.0 / rhs.0
+            }
+        }
+
+        impl core::iter::Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, Add::add)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!("{} ", $suffix), self.0)
+            }
+        }
+
+        #[cfg(feature = "defmt")]
+        impl defmt::Format for $name {
+            fn format(&self, fmt: defmt::Formatter) {
+                defmt::write!(fmt, $defmt, self.0);
+            }
+        }
+    };
+}
+
+unit!(
+    /// Voltage in millivolts.
+    MilliVolts(u16),
+    "mV",
+    "{=u16} mV"
+);
+unit!(
+    /// Current in milliamps.
+    MilliAmps(u16),
+    "mA",
+    "{=u16} mA"
+);
+unit!(
+    /// Current in milliamps, for currents that can exceed `MilliAmps::MAX`,
+    /// e.g. overcurrent trip points.
+    MilliAmps32(u32),
+    "mA",
+    "{=u32} mA"
+);
+unit!(
+    /// Power in milliwatts.
+    MilliWatts(u32),
+    "mW",
+    "{=u32} mW"
+);
+unit!(
+    /// Duration in microseconds.
+    Micros(u32),
+    "us",
+    "{=u32} us"
+);
+unit!(
+    /// Temperature in whole degrees Celsius.
+    Celsius(i16),
+    "°C",
+    "{=i16} °C"
+);
+
+/// P = V * I, rounded down to the nearest milliwatt.
+impl Mul<MilliAmps> for MilliVolts {
+    type Output = MilliWatts;
+
+    fn mul(self, rhs: MilliAmps) -> MilliWatts {
+        MilliWatts(self.0 as u32 * rhs.0 as u32 / 1000)
+    }
+}
+
+/// P = I * V, rounded down to the nearest milliwatt.
+impl Mul<MilliVolts> for MilliAmps {
+    type Output = MilliWatts;
+
+    fn mul(self, rhs: MilliVolts) -> MilliWatts {
+        rhs * self
+    }
+}
+
+impl From<MilliAmps> for MilliAmps32 {
+    fn from(value: MilliAmps) -> Self {
+        MilliAmps32(value.0 as u32)
+    }
+}
+
+/// I = P / V, saturating at `MilliAmps::MAX`. Panics if `rhs` is zero.
+impl Div<MilliVolts> for MilliWatts {
+    type Output = MilliAmps;
+
+    fn div(self, rhs: MilliVolts) -> MilliAmps {
+        let milliamps = self.0 as u64 * 1000 / rhs.0 as u64;
+        MilliAmps(milliamps.min(u16::MAX as u64) as u16)
+    }
+}
+
+/// V = P / I, saturating at `MilliVolts::MAX`. Panics if `rhs` is zero.
+impl Div<MilliAmps> for MilliWatts {
+    type Output = MilliVolts;
+
+    fn div(self, rhs: MilliAmps) -> MilliVolts {
+        let millivolts = self.0 as u64 * 1000 / rhs.0 as u64;
+        MilliVolts(millivolts.min(u16::MAX as u64) as u16)
+    }
+}
+
+impl From<Micros> for core::time::Duration {
+    fn from(value: Micros) -> Self {
+        core::time::Duration::from_micros(value.0 as u64)
+    }
+}
+
+impl TryFrom<core::time::Duration> for Micros {
+    type Error = core::num::TryFromIntError;
+
+    fn try_from(value: core::time::Duration) -> Result<Self, Self::Error> {
+        u32::try_from(value.as_micros()).map(Micros)
+    }
+}
@@ -0,0 +1,96 @@
+//! Members of the BQ2573x charger family.
+//!
+//! The BQ25730 and BQ25731 share the I2C address, the register map in
+//! [`crate::registers`] and its encodings, and both charge 1S to 5S packs
+//! (see the device comparison table in `docs/bq25730.md`). The BQ25731 has a
+//! different DeviceID, a fixed 1.6 V VSYS_UVP threshold, and no BATFET power
+//! path or pre-charge LDO mode. [`ChipVariant`] supplies those differences to
+//! the driver;
+//! [`Bq25730::read_device_id`](crate::Bq25730::read_device_id) selects the
+//! variant from the ID registers.
+
+use core::ops::RangeInclusive;
+
+use crate::data_types::ChargeVoltageSetting;
+use crate::units::MilliVolts;
+use crate::{BQ25730_DEVICE_ID, BQ25730_MANUFACTURER_ID, BQ25731_DEVICE_ID};
+
+/// A supported charger of the BQ2573x family.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChipVariant {
+    /// BQ25730, 1S-5S, I2C.
+    #[default]
+    Bq25730,
+    /// BQ25731, 1S-5S, I2C, without BATFET power path or pre-charge LDO.
+    Bq25731,
+}
+
+impl ChipVariant {
+    /// Every supported variant.
+    pub const ALL: [ChipVariant; 2] = [ChipVariant::Bq25730, ChipVariant::Bq25731];
+
+    /// Returns the variant identified by the ManufacturerID and DeviceID
+    /// register values, or `None` if the chip is not supported.
+    pub fn from_ids(manufacturer_id: u8, device_id: u8) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.manufacturer_id() == manufacturer_id && v.device_id() == device_id)
+    }
+
+    /// Part number, e.g. `"BQ25730"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            ChipVariant::Bq25730 => "BQ25730",
+            ChipVariant::Bq25731 => "BQ25731",
+        }
+    }
+
+    /// ManufacturerID register value.
+    pub const fn manufacturer_id(self) -> u8 {
+        match self {
+            ChipVariant::Bq25730 | ChipVariant::Bq25731 => BQ25730_MANUFACTURER_ID,
+        }
+    }
+
+    /// DeviceID register value.
+    pub const fn device_id(self) -> u8 {
+        match self {
+            ChipVariant::Bq25730 => BQ25730_DEVICE_ID,
+            ChipVariant::Bq25731 => BQ25731_DEVICE_ID,
+        }
+    }
+
+    /// Supported number of cells in series.
+    pub const fn cell_count(self) -> RangeInclusive<u8> {
+        match self {
+            ChipVariant::Bq25730 | ChipVariant::Bq25731 => 1..=5,
+        }
+    }
+
+    /// ChargeVoltage values the chip accepts.
+    pub fn charge_voltage_range(self) -> RangeInclusive<MilliVolts> {
+        match self {
+            ChipVariant::Bq25730 | ChipVariant::Bq25731 => ChargeVoltageSetting::valid_range(),
+        }
+    }
+
+    /// Returns whether VSYS_UVP can be set in ChargeOption4. The BQ25731
+    /// fixes it at 1.6 V.
+    pub const fn has_adjustable_vsys_uvp(self) -> bool {
+        matches!(self, ChipVariant::Bq25730)
+    }
+
+    /// Returns whether the chip drives a BATFET, i.e. whether BATFET_ENZ and
+    /// BATFETOFF_HIZ in ChargeOption3 have any effect.
+    pub const fn has_batfet_power_path(self) -> bool {
+        matches!(self, ChipVariant::Bq25730)
+    }
+
+    /// Returns whether the chip supports pre-charge LDO mode (EN_LDO in
+    /// ChargeOption0).
+    pub const fn has_precharge_ldo(self) -> bool {
+        matches!(self, ChipVariant::Bq25730)
+    }
+}
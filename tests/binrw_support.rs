@@ -1,8 +1,10 @@
 #![cfg(feature = "binrw")]
 
 use binrw::{BinRead, BinWrite, Endian, io::Cursor};
+use bq25730_async_rs::RegisterDump;
 use bq25730_async_rs::data_types::*;
 use bq25730_async_rs::registers::*;
+use bq25730_async_rs::units::{MilliAmps, MilliVolts};
 
 /// Helper function to test binrw roundtrip serialization/deserialization.
 ///
@@ -60,7 +62,7 @@ fn test_prochot_status_binrw_roundtrip() {
 #[test]
 fn test_charge_current_binrw_roundtrip() {
     let original = ChargeCurrentSetting {
-        milliamps: 1024,
+        milliamps: MilliAmps(1024),
         rsns_bat: SenseResistorValue::R5mOhm,
     };
     test_binrw_roundtrip(original);
@@ -68,20 +70,24 @@ fn test_charge_current_binrw_roundtrip() {
 
 #[test]
 fn test_charge_voltage_binrw_roundtrip() {
-    let original = bq25730_async_rs::data_types::ChargeVoltageSetting { millivolts: 16000 }; // Example value in mV
+    let original = bq25730_async_rs::data_types::ChargeVoltageSetting {
+        millivolts: MilliVolts(16000),
+    }; // Example value in mV
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_otg_voltage_binrw_roundtrip() {
-    let original = bq25730_async_rs::data_types::OtgVoltageSetting { millivolts: 5000 }; // Example value in mV
+    let original = bq25730_async_rs::data_types::OtgVoltageSetting {
+        millivolts: MilliVolts(5000),
+    }; // Example value in mV
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_otg_current_binrw_roundtrip() {
     let original = OtgCurrentSetting {
-        milliamps: 2000,
+        milliamps: MilliAmps(2000),
         rsns_bat: SenseResistorValue::R5mOhm,
     };
     test_binrw_roundtrip(original);
@@ -89,20 +95,24 @@ fn test_otg_current_binrw_roundtrip() {
 
 #[test]
 fn test_input_voltage_binrw_roundtrip() {
-    let original = bq25730_async_rs::data_types::InputVoltageSetting { millivolts: 11968 }; // Example value in mV (11968 - 3200 = 8768 = 64 * 137)
+    let original = bq25730_async_rs::data_types::InputVoltageSetting {
+        millivolts: MilliVolts(11968),
+    }; // Example value in mV (11968 - 3200 = 8768 = 64 * 137)
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_vsys_min_binrw_roundtrip() {
-    let original = bq25730_async_rs::data_types::VsysMinSetting { millivolts: 3500 }; // Example value in mV
+    let original = bq25730_async_rs::data_types::VsysMinSetting {
+        millivolts: MilliVolts(3500),
+    }; // Example value in mV
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_iin_host_binrw_roundtrip() {
     let original = IinHost {
-        milliamps: 3000,
+        milliamps: MilliAmps(3000),
         rsns_ac: SenseResistorValue::R5mOhm,
     };
     test_binrw_roundtrip(original);
@@ -111,7 +121,7 @@ fn test_iin_host_binrw_roundtrip() {
 #[test]
 fn test_iin_dpm_binrw_roundtrip() {
     let original = IinDpm {
-        milliamps: 2500,
+        milliamps: MilliAmps(2500),
         rsns_ac: SenseResistorValue::R5mOhm,
     };
     test_binrw_roundtrip(original);
@@ -120,37 +130,37 @@ fn test_iin_dpm_binrw_roundtrip() {
 #[test]
 fn test_adc_measurements_binrw_roundtrip() {
     let original = AdcMeasurements {
-        vbat: AdcVbat(3776),
-        vsys: AdcVsys(3968),
+        vbat: AdcVbat(MilliVolts(3776)),
+        vsys: AdcVsys(MilliVolts(3968)),
         ichg: AdcIchg {
-            milliamps: 500,
+            milliamps: MilliAmps(500),
             rsns_bat: SenseResistorValue::R5mOhm,
         },
         idchg: AdcIdchg {
-            milliamps: 1000,
+            milliamps: MilliAmps(1000),
             rsns_bat: SenseResistorValue::R5mOhm,
         },
         iin: AdcIin {
-            milliamps: 800,
+            milliamps: MilliAmps(800),
             rsns_ac: SenseResistorValue::R5mOhm,
         }, // 8 * 100mA/LSB for 5mOhm
-        psys: AdcPsys(600),
-        vbus: AdcVbus(4992),
-        cmpin: AdcCmpin(96),
+        psys: AdcPsys(MilliVolts(600)),
+        vbus: AdcVbus(MilliVolts(4992)),
+        cmpin: AdcCmpin(MilliVolts(96)),
     };
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_adccmpin_binrw_roundtrip() {
-    let original = AdcCmpin(96); // Example scaled value (96 = 12 * 8)
+    let original = AdcCmpin(MilliVolts(96)); // Example scaled value (96 = 12 * 8)
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_adcichg_binrw_roundtrip() {
     let original = AdcIchg {
-        milliamps: 500,
+        milliamps: MilliAmps(500),
         rsns_bat: SenseResistorValue::R5mOhm,
     };
     test_binrw_roundtrip(original);
@@ -159,7 +169,7 @@ fn test_adcichg_binrw_roundtrip() {
 #[test]
 fn test_adcidchg_binrw_roundtrip() {
     let original = AdcIdchg {
-        milliamps: 1000,
+        milliamps: MilliAmps(1000),
         rsns_bat: SenseResistorValue::R5mOhm,
     };
     test_binrw_roundtrip(original);
@@ -168,7 +178,7 @@ fn test_adcidchg_binrw_roundtrip() {
 #[test]
 fn test_adciin_binrw_roundtrip() {
     let original = AdcIin {
-        milliamps: 800,
+        milliamps: MilliAmps(800),
         rsns_ac: SenseResistorValue::R5mOhm,
     }; // 8 * 100mA/LSB for 5mOhm
     test_binrw_roundtrip(original);
@@ -176,25 +186,25 @@ fn test_adciin_binrw_roundtrip() {
 
 #[test]
 fn test_adcpsys_binrw_roundtrip() {
-    let original = AdcPsys(600); // Example scaled value
+    let original = AdcPsys(MilliVolts(600)); // Example scaled value
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_adcvbus_binrw_roundtrip() {
-    let original = AdcVbus(4992); // Example value in mV (4992 = 96 * 52)
+    let original = AdcVbus(MilliVolts(4992)); // Example value in mV (4992 = 96 * 52)
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_adcvbat_binrw_roundtrip() {
-    let original = AdcVbat(3776); // Example value in mV (3776 = 64 * 59)
+    let original = AdcVbat(MilliVolts(3776)); // Example value in mV (3776 = 64 * 59)
     test_binrw_roundtrip(original);
 }
 
 #[test]
 fn test_adcvsys_binrw_roundtrip() {
-    let original = AdcVsys(3968); // Example value in mV (3968 = 64 * 62)
+    let original = AdcVsys(MilliVolts(3968)); // Example value in mV (3968 = 64 * 62)
     test_binrw_roundtrip(original);
 }
 
@@ -260,3 +270,23 @@ fn test_prochot_option1_binrw_roundtrip() {
     };
     test_binrw_roundtrip(original);
 }
+
+#[test]
+fn test_register_dump_binrw_format() {
+    let mut raw = [0u8; RegisterDump::LEN];
+    for (i, byte) in raw.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    let original = RegisterDump::from_bytes(raw);
+    test_binrw_roundtrip(original);
+
+    let mut buffer = Cursor::new(Vec::new());
+    original.write(&mut buffer).unwrap();
+    let bytes = buffer.into_inner();
+    assert_eq!(bytes.len(), 8 + RegisterDump::LEN);
+    assert_eq!(&bytes[..8], RegisterDump::MAGIC);
+    assert_eq!(&bytes[8..], &raw);
+
+    // A dump without the magic is rejected.
+    assert!(RegisterDump::read(&mut Cursor::new(&raw[..])).is_err());
+}
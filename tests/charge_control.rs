@@ -1,7 +1,7 @@
 #![allow(clippy::approx_constant)]
 
 use bq25730_async_rs::{
-    BQ25730_I2C_ADDRESS, Bq25730,
+    BQ25730_I2C_ADDRESS, Bq25730, MilliAmps, MilliVolts,
     data_types::{
         ChargeCurrentSetting, ChargeOption0, ChargeVoltageSetting, Config, SenseResistorValue,
     },
@@ -20,7 +20,7 @@ fn test_charge_control_set_charge_current()
 
     // Test case 1: 0mA
     let current_0ma = ChargeCurrentSetting {
-        milliamps: 0,
+        milliamps: MilliAmps(0),
         rsns_bat,
     };
     let (lsb_0ma, msb_0ma) = current_0ma.to_msb_lsb_bytes();
@@ -36,7 +36,7 @@ fn test_charge_control_set_charge_current()
     // Test case 2: 8192mA (Max for 5mOhm is 127*128 = 16256mA, so this should be clamped if > 7bit raw)
     // For 5mOhm, LSB = 128mA. 8192mA / 128mA = 64 (0x40)
     let current_8192ma = ChargeCurrentSetting {
-        milliamps: 8192,
+        milliamps: MilliAmps(8192),
         rsns_bat,
     };
     let (lsb_8192ma, msb_8192ma) = current_8192ma.to_msb_lsb_bytes();
@@ -52,7 +52,7 @@ fn test_charge_control_set_charge_current()
     // Test case 3: 2560mA
     // For 5mOhm, LSB = 128mA. 2560mA / 128mA = 20 (0x14)
     let current_2560ma = ChargeCurrentSetting {
-        milliamps: 2560,
+        milliamps: MilliAmps(2560),
         rsns_bat,
     };
     let (lsb_2560ma, msb_2560ma) = current_2560ma.to_msb_lsb_bytes();
@@ -86,7 +86,7 @@ fn test_charge_control_read_charge_current()
     assert_eq!(
         charger_0ma.read_charge_current_setting()?,
         ChargeCurrentSetting {
-            milliamps: 0,
+            milliamps: MilliAmps(0),
             rsns_bat
         }
     );
@@ -103,7 +103,7 @@ fn test_charge_control_read_charge_current()
     assert_eq!(
         charger_8192ma.read_charge_current_setting()?,
         ChargeCurrentSetting {
-            milliamps: 8192,
+            milliamps: MilliAmps(8192),
             rsns_bat
         }
     );
@@ -120,7 +120,7 @@ fn test_charge_control_read_charge_current()
     assert_eq!(
         charger_2560ma.read_charge_current_setting()?,
         ChargeCurrentSetting {
-            milliamps: 2560,
+            milliamps: MilliAmps(2560),
             rsns_bat
         }
     );
@@ -133,7 +133,7 @@ fn test_charge_control_read_charge_current()
 #[test]
 fn test_charge_control_set_charge_voltage()
 -> Result<(), bq25730_async_rs::errors::Error<embedded_hal::i2c::ErrorKind>> {
-    let voltage_to_set = ChargeVoltageSetting::from_millivolts(1024);
+    let voltage_to_set = ChargeVoltageSetting::from_millivolts(MilliVolts(1024));
     let (lsb, msb) = voltage_to_set.to_msb_lsb_bytes();
     let expectations = [embedded_hal_mock::eh1::i2c::Transaction::write(
         BQ25730_I2C_ADDRESS,
@@ -153,7 +153,7 @@ fn test_charge_control_set_charge_voltage()
 #[test]
 fn test_charge_control_read_charge_voltage()
 -> Result<(), bq25730_async_rs::errors::Error<embedded_hal::i2c::ErrorKind>> {
-    let expected_voltage = ChargeVoltageSetting::from_millivolts(1024);
+    let expected_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(1024));
     let (lsb, msb) = expected_voltage.to_msb_lsb_bytes();
     let expectations = [embedded_hal_mock::eh1::i2c::Transaction::write_read(
         BQ25730_I2C_ADDRESS,
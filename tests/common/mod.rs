@@ -0,0 +1,66 @@
+//! I2C mock transactions and other fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use core::time::Duration;
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS,
+    data_types::{AdcMeasurements, SenseResistorValue},
+    registers::Register,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+
+/// Reads `bytes` starting at `register`.
+pub fn read(register: Register, bytes: &[u8]) -> I2cTransaction {
+    I2cTransaction::write_read(BQ25730_I2C_ADDRESS, vec![register as u8], bytes.to_vec())
+}
+
+/// Writes a 16-bit register, LSB first.
+pub fn write(register: Register, bytes: [u8; 2]) -> I2cTransaction {
+    I2cTransaction::write(
+        BQ25730_I2C_ADDRESS,
+        vec![register as u8, bytes[0], bytes[1]],
+    )
+}
+
+/// Raw ADC result codes; channels left at their default read 0.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AdcCodes {
+    pub psys: u8,
+    pub vbus: u8,
+    pub idchg: u8,
+    pub ichg: u8,
+    pub cmpin: u8,
+    pub iin: u8,
+    pub vbat: u8,
+    pub vsys: u8,
+}
+
+impl AdcCodes {
+    /// The ADC block, ADCPSYS (0x26) through ADCVSYS (0x2D).
+    pub fn block(self) -> [u8; 8] {
+        [
+            self.psys, self.vbus, self.idchg, self.ichg, self.cmpin, self.iin, self.vbat, self.vsys,
+        ]
+    }
+
+    /// Decodes the block with 5 mOhm sense resistors.
+    pub fn measurements(self) -> AdcMeasurements {
+        AdcMeasurements::from_adc_block(
+            &self.block(),
+            SenseResistorValue::R5mOhm,
+            SenseResistorValue::R5mOhm,
+            2880,
+        )
+    }
+
+    /// Reads the block in one transfer.
+    pub fn read(self) -> I2cTransaction {
+        read(Register::ADCPSYS, &self.block())
+    }
+}
+
+/// `s` seconds.
+pub fn secs(s: u64) -> Duration {
+    Duration::from_secs(s)
+}
@@ -0,0 +1,184 @@
+#![cfg(not(feature = "async"))]
+#![allow(clippy::approx_constant)]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, ConfigChanges, MilliAmps, MilliVolts,
+    data_types::{
+        ChargeCurrentSetting, ChargeVoltageSetting, Config, IinHostSetting, SenseResistorValue,
+    },
+    errors::Error,
+    registers::{ChargeOption3MsbFlags, Register},
+};
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+mod common;
+use common::{read, write};
+
+#[test]
+fn test_config_diff_detects_changed_registers() {
+    let old = Config::default();
+    assert_eq!(old.diff(&old), ConfigChanges::empty());
+
+    let mut new = old;
+    new.charge_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(16000));
+    new.charge_option3
+        .msb_flags
+        .insert(ChargeOption3MsbFlags::EN_ICO_MODE);
+    assert_eq!(
+        old.diff(&new),
+        ConfigChanges::CHARGE_VOLTAGE | ConfigChanges::CHARGE_OPTION3
+    );
+}
+
+#[test]
+fn test_apply_config_no_changes_writes_nothing() -> Result<(), Error<ErrorKind>> {
+    let config = Config::default();
+    let i2c = I2cMock::new(&[]);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, config);
+
+    let changes = charger.apply_config(&config)?;
+    assert!(changes.is_empty());
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_apply_config_lowers_current_before_voltage() -> Result<(), Error<ErrorKind>> {
+    let rsns = SenseResistorValue::R5mOhm;
+    let mut old = Config::new(4, rsns, rsns);
+    old.charge_current = ChargeCurrentSetting::from_milliamps(MilliAmps(2048), rsns);
+
+    let mut new = old;
+    new.charge_current = ChargeCurrentSetting::from_milliamps(MilliAmps(1024), rsns);
+    new.charge_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(16000));
+
+    let expectations = [
+        write(
+            Register::ChargeCurrent,
+            new.charge_current.to_msb_lsb_bytes().into(),
+        ),
+        write(
+            Register::ChargeVoltage,
+            new.charge_voltage.to_msb_lsb_bytes().into(),
+        ),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, old);
+
+    let changes = charger.apply_config(&new)?;
+    assert_eq!(
+        changes,
+        ConfigChanges::CHARGE_CURRENT | ConfigChanges::CHARGE_VOLTAGE
+    );
+    assert_eq!(*charger.config(), new);
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_apply_config_raises_voltage_before_current() -> Result<(), Error<ErrorKind>> {
+    let rsns = SenseResistorValue::R5mOhm;
+    let mut old = Config::new(4, rsns, rsns);
+    old.charge_current = ChargeCurrentSetting::from_milliamps(MilliAmps(1024), rsns);
+    old.charge_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(16000));
+
+    let mut new = old;
+    new.charge_current = ChargeCurrentSetting::from_milliamps(MilliAmps(2048), rsns);
+    new.charge_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(16800));
+    new.iin_host = IinHostSetting::from_milliamps(MilliAmps(2000));
+
+    let expectations = [
+        // IIN_HOST goes down (3200mA -> 2000mA), so it is written first.
+        write(
+            Register::IinHost,
+            new.iin_host.to_msb_lsb_bytes(rsns).into(),
+        ),
+        write(
+            Register::ChargeVoltage,
+            new.charge_voltage.to_msb_lsb_bytes().into(),
+        ),
+        write(
+            Register::ChargeCurrent,
+            new.charge_current.to_msb_lsb_bytes().into(),
+        ),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, old);
+
+    let changes = charger.apply_config(&new)?;
+    assert_eq!(
+        changes,
+        ConfigChanges::CHARGE_CURRENT | ConfigChanges::CHARGE_VOLTAGE | ConfigChanges::IIN_HOST
+    );
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_apply_config_failed_write_keeps_cached_config() {
+    let old = Config::default();
+    let mut new = old;
+    new.charge_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(16000));
+
+    let expectations = [write(
+        Register::ChargeVoltage,
+        new.charge_voltage.to_msb_lsb_bytes().into(),
+    )
+    .with_error(ErrorKind::Other)];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, old);
+
+    assert!(charger.apply_config(&new).is_err());
+    assert_eq!(*charger.config(), old);
+    charger.i2c.done();
+}
+
+/// Builds the 0x00-0x0F and 0x30-0x3F register images for a config.
+fn register_images(config: &Config) -> (Vec<u8>, Vec<u8>) {
+    let mut low = vec![0u8; 16];
+    let mut high = vec![0u8; 16];
+    for (reg, lsb, msb) in config.register_values() {
+        let addr = reg as u8;
+        let (block, index) = if addr < 0x30 {
+            (&mut low, addr as usize)
+        } else {
+            (&mut high, (addr - 0x30) as usize)
+        };
+        block[index] = lsb;
+        block[index + 1] = msb;
+    }
+    (low, high)
+}
+
+#[test]
+fn test_read_config_round_trips_all_sense_resistor_combinations() -> Result<(), Error<ErrorKind>> {
+    let combos = [
+        (SenseResistorValue::R5mOhm, SenseResistorValue::R5mOhm),
+        (SenseResistorValue::R5mOhm, SenseResistorValue::R10mOhm),
+        (SenseResistorValue::R10mOhm, SenseResistorValue::R5mOhm),
+        (SenseResistorValue::R10mOhm, SenseResistorValue::R10mOhm),
+    ];
+    for (rsns_bat, rsns_ac) in combos {
+        let mut expected = Config::new(2, rsns_bat, rsns_ac);
+        expected.charge_current = ChargeCurrentSetting::from_milliamps(MilliAmps(1024), rsns_bat);
+        let (low, high) = register_images(&expected);
+
+        let expectations = [
+            read(Register::ChargeOption0, &low),
+            read(Register::ChargeOption1, &high),
+        ];
+        let i2c = I2cMock::new(&expectations);
+        // The cached config deliberately disagrees with the chip.
+        let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+        let config = charger.read_config()?;
+        assert_eq!(config.rsns_bat, rsns_bat);
+        assert_eq!(config.rsns_ac, rsns_ac);
+        assert_eq!(config, expected);
+        assert_eq!(*charger.config(), Config::default());
+        charger.i2c.done();
+    }
+    Ok(())
+}
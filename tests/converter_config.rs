@@ -0,0 +1,121 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, Config, ConfigChanges, ConverterConfig, Inductor,
+    InductorCurrentLimit, MilliAmps, SwitchingFrequency,
+    errors::Error,
+    registers::{ChargeOption0MsbFlags, DitherSetting, Register},
+};
+
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+fn config_with(converter: ConverterConfig) -> Config {
+    Config {
+        converter: Some(converter),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn test_defaults_match_reset_values() {
+    let converter = ConverterConfig::default();
+    assert_eq!(
+        converter,
+        ConverterConfig {
+            frequency: SwitchingFrequency::Khz400,
+            dither: DitherSetting::Disabled,
+            out_of_audio: true,
+            low_ptm_ripple: true,
+            il_avg: InductorCurrentLimit::A15,
+            inductor: None,
+        }
+    );
+    assert_eq!(Config::default().converter, None);
+    assert_eq!(Config::default().converter_config(), converter);
+    assert_eq!(
+        InductorCurrentLimit::A15.milliamps(),
+        Some(MilliAmps(15000))
+    );
+    assert_eq!(InductorCurrentLimit::Disabled.milliamps(), None);
+}
+
+#[test]
+fn test_converter_overrides_option_registers() {
+    let config = config_with(ConverterConfig {
+        frequency: SwitchingFrequency::Khz800,
+        dither: DitherSetting::Dither2X,
+        out_of_audio: false,
+        low_ptm_ripple: true,
+        il_avg: InductorCurrentLimit::A6,
+        inductor: Some(Inductor::Uh2_2),
+    });
+
+    let value = |reg| {
+        config
+            .register_values()
+            .into_iter()
+            .find(|&(r, _, _)| r == reg)
+            .map(|(_, lsb, msb)| [lsb, msb])
+            .unwrap()
+    };
+    // PWM_FREQ and EN_OOA cleared.
+    assert_eq!(value(Register::ChargeOption0), [0x0E, 0xE1]);
+    // IL_AVG 00b.
+    assert_eq!(value(Register::ChargeOption3), [0x24, 0x04]);
+    // EN_DITHER 10b.
+    assert_eq!(value(Register::ChargeOption4), [0x48, 0x10]);
+
+    assert_eq!(
+        Config::default().diff(&config),
+        ConfigChanges::CHARGE_OPTION0
+            | ConfigChanges::CHARGE_OPTION3
+            | ConfigChanges::CHARGE_OPTION4
+    );
+
+    let (option0, option3, option4) = config.option_registers();
+    assert_eq!(
+        ConverterConfig::from_options(&option0, &option3, &option4),
+        ConverterConfig {
+            inductor: None,
+            ..config.converter.unwrap()
+        }
+    );
+}
+
+#[test]
+fn test_option_register_bits_kept_without_converter() {
+    let mut config = Config::default();
+    config
+        .charge_option0
+        .msb_flags
+        .remove(ChargeOption0MsbFlags::PWM_FREQ | ChargeOption0MsbFlags::EN_OOA);
+
+    let (option0, _, _) = config.option_registers();
+    assert_eq!(option0, config.charge_option0);
+    assert_eq!(
+        config.converter_config().frequency,
+        SwitchingFrequency::Khz800
+    );
+    assert!(!config.converter_config().out_of_audio);
+}
+
+#[test]
+fn test_init_rejects_frequency_the_inductor_does_not_support() {
+    assert!(Inductor::Uh3_3.supports(SwitchingFrequency::Khz400));
+    assert!(Inductor::Uh3_3.supports(SwitchingFrequency::Khz800));
+    assert!(!Inductor::Uh4_7.supports(SwitchingFrequency::Khz800));
+
+    let config = config_with(ConverterConfig {
+        inductor: Some(Inductor::Uh2_2),
+        ..ConverterConfig::default()
+    });
+    let mut charger = Bq25730::new(I2cMock::new(&[]), BQ25730_I2C_ADDRESS, config);
+    assert!(matches!(
+        charger.init(),
+        Err(Error::UnsupportedFrequency {
+            inductor: Inductor::Uh2_2,
+            frequency: SwitchingFrequency::Khz400,
+        })
+    ));
+    charger.i2c.done();
+}
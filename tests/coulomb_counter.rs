@@ -0,0 +1,135 @@
+use core::time::Duration;
+
+use bq25730_async_rs::{
+    CoulombCounter, CoulombCounterConfig, MilliAmps, MilliVolts, OcvPoint,
+    coulomb::soc_from_ocv,
+    data_types::{AdcIchg, AdcIdchg, AdcMeasurements, SenseResistorValue},
+};
+
+mod common;
+use common::{AdcCodes, secs};
+
+const OCV: [OcvPoint; 3] = [
+    OcvPoint {
+        vbat: MilliVolts(12000),
+        soc_percent: 0,
+    },
+    OcvPoint {
+        vbat: MilliVolts(14000),
+        soc_percent: 50,
+    },
+    OcvPoint {
+        vbat: MilliVolts(16800),
+        soc_percent: 100,
+    },
+];
+
+/// ADC readings with ICHG/IDCHG codes and the same code for VBAT and VSYS.
+fn adc(ichg: u8, idchg: u8, vbat: u8) -> AdcMeasurements {
+    AdcCodes {
+        idchg,
+        ichg,
+        vbat,
+        vsys: vbat,
+        ..AdcCodes::default()
+    }
+    .measurements()
+}
+
+#[test]
+fn test_soc_from_ocv() {
+    assert_eq!(soc_from_ocv(&OCV, MilliVolts(11000)), Some(0));
+    assert_eq!(soc_from_ocv(&OCV, MilliVolts(13000)), Some(25));
+    assert_eq!(soc_from_ocv(&OCV, MilliVolts(15400)), Some(75));
+    assert_eq!(soc_from_ocv(&OCV, MilliVolts(17000)), Some(100));
+    assert_eq!(soc_from_ocv(&[], MilliVolts(13000)), None);
+}
+
+#[test]
+fn test_adc_resolution_follows_sense_resistor() {
+    assert_eq!(AdcIchg::lsb(SenseResistorValue::R5mOhm), MilliAmps(128));
+    assert_eq!(AdcIchg::lsb(SenseResistorValue::R10mOhm), MilliAmps(64));
+    assert_eq!(AdcIdchg::lsb(SenseResistorValue::R5mOhm), MilliAmps(512));
+    assert_eq!(AdcIdchg::lsb(SenseResistorValue::R10mOhm), MilliAmps(256));
+}
+
+#[test]
+fn test_trapezoidal_integration_and_estimates() {
+    let mut counter = CoulombCounter::new(CoulombCounterConfig::new(1000), 50);
+    assert_eq!(counter.remaining_capacity_mah(), 500);
+    assert_eq!(counter.time_to_empty(), None);
+
+    // 0 -> 1024 mA (8 x 128 mA) over half an hour averages 512 mA: +256 mAh.
+    counter.update(secs(0), &adc(0, 0, 150));
+    counter.update(secs(1800), &adc(8, 0, 150));
+    assert_eq!(counter.remaining_capacity_mah(), 756);
+    assert_eq!(counter.soc_percent(), 76);
+    assert_eq!(counter.net_current_ma(), Some(1024));
+    // 244 mAh at 1024 mA.
+    assert_eq!(
+        counter.time_to_full(),
+        Some(Duration::from_micros(857_812_500))
+    );
+    assert_eq!(counter.time_to_empty(), None);
+
+    // +1024 mA -> -1024 mA (2 x 512 mA) averages to zero.
+    counter.update(secs(3600), &adc(0, 2, 150));
+    assert_eq!(counter.remaining_capacity_mah(), 756);
+    // 756 mAh at 1024 mA.
+    assert_eq!(
+        counter.time_to_empty(),
+        Some(Duration::from_micros(2_657_812_500))
+    );
+    assert_eq!(counter.time_to_full(), None);
+
+    // A timestamp that does not move forward only restarts the integration.
+    counter.update(secs(10), &adc(0, 2, 150));
+    assert_eq!(counter.remaining_capacity_mah(), 756);
+
+    // Charge is clamped to the capacity.
+    counter.update(secs(20), &adc(127, 0, 150));
+    counter.update(secs(20_000), &adc(127, 0, 150));
+    assert_eq!(counter.soc_percent(), 100);
+    assert_eq!(counter.remaining_capacity_mah(), 1000);
+}
+
+#[test]
+fn test_ocv_correction_after_rest() {
+    let config = CoulombCounterConfig {
+        ocv_table: &OCV,
+        rest_time: secs(600),
+        ..CoulombCounterConfig::new(1000)
+    };
+    let mut counter = CoulombCounter::new(config, 50);
+
+    // VBAT 13120 mV (2880 + 160 x 64 mV) is 28% on the table.
+    counter.update(secs(0), &adc(0, 0, 160));
+    counter.update(secs(300), &adc(0, 0, 160));
+    assert!(!counter.is_rested());
+    assert_eq!(counter.soc_percent(), 50);
+
+    counter.update(secs(600), &adc(0, 0, 160));
+    assert!(counter.is_rested());
+    assert_eq!(counter.soc_percent(), 28);
+
+    // Any measurable current ends the rest.
+    counter.update(secs(601), &adc(1, 0, 160));
+    assert!(!counter.is_rested());
+}
+
+#[test]
+fn test_rest_current_threshold() {
+    let config = CoulombCounterConfig {
+        rest_current: MilliAmps(128),
+        rest_time: secs(60),
+        ..CoulombCounterConfig::new(1000)
+    };
+    let mut counter = CoulombCounter::new(config, 120);
+    assert_eq!(counter.soc_percent(), 100);
+
+    counter.update(secs(0), &adc(1, 0, 160));
+    counter.update(secs(60), &adc(1, 0, 160));
+    assert!(counter.is_rested());
+    counter.update(secs(61), &adc(2, 0, 160));
+    assert!(!counter.is_rested());
+}
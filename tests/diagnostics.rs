@@ -0,0 +1,182 @@
+use bq25730_async_rs::{
+    data_types::{ChargeOption0, ChargerStatus, ProchotStatus},
+    registers::{
+        ChargeOption0Flags, ChargeOption0MsbFlags, ChargerStatusFaultFlags, ChargerStatusFlags,
+        ProchotStatusFlags, ProchotStatusMsbFlags,
+    },
+};
+
+#[test]
+fn test_status_display_matches_defmt_output() {
+    let status = ChargerStatus {
+        status_flags: ChargerStatusFlags::STAT_AC | ChargerStatusFlags::IN_FCHRG,
+        fault_flags: ChargerStatusFaultFlags::empty(),
+    };
+    assert_eq!(
+        status.to_string(),
+        "ChargerStatus { status_flags: STAT_AC | IN_FCHRG, fault_flags: (empty) }"
+    );
+
+    let prochot = ProchotStatus {
+        msb_flags: ProchotStatusMsbFlags::from_bits_truncate(0x61),
+        lsb_flags: ProchotStatusFlags::STAT_VSYS,
+        prochot_width: 2,
+    };
+    assert_eq!(
+        prochot.msb_flags.to_string(),
+        "EN_PROCHOT_EXT | PROCHOT_WIDTH_VAL=2 | STAT_EXIT_VAP"
+    );
+    assert_eq!(prochot.lsb_flags.to_string(), "STAT_VSYS");
+    assert_eq!(
+        prochot.to_string(),
+        "ProchotStatus { msb_flags: 1100001, lsb_flags: 100, prochot_width: 2 }"
+    );
+}
+
+#[test]
+fn test_option_display_matches_defmt_output() {
+    let option = ChargeOption0 {
+        msb_flags: ChargeOption0MsbFlags::from_bits_truncate(0x06),
+        lsb_flags: ChargeOption0Flags::EN_IIN_DPM | ChargeOption0Flags::EN_LDO,
+    };
+    assert_eq!(
+        option.to_string(),
+        "ChargeOption0 { msb_flags: 110, lsb_flags: 110 }"
+    );
+    assert_eq!(option.lsb_flags.to_string(), "110");
+    assert_eq!(
+        option.msb_flags.to_string(),
+        format!(
+            "Flags(110) WDT({:?})",
+            option.msb_flags.get_watchdog_timer()
+        )
+    );
+}
+
+#[cfg(all(feature = "log", not(feature = "async")))]
+mod log_backend {
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    use bq25730_async_rs::{
+        BQ25730_I2C_ADDRESS, Bq25730, Config, MilliAmps, RegisterAccess, SenseResistorValue,
+        data_types::ChargeCurrentSetting, errors::Error, registers::Register,
+    };
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use log::{Level, Log, Metadata, Record};
+
+    thread_local! {
+        static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Collects records per thread so parallel tests don't see each other's output.
+    struct Capture;
+
+    impl Log for Capture {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            RECORDS.with(|r| {
+                r.borrow_mut()
+                    .push((record.level(), record.args().to_string()))
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn capture() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&Capture).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        RECORDS.with(|r| r.borrow_mut().clear());
+    }
+
+    fn records() -> Vec<(Level, String)> {
+        RECORDS.with(|r| r.borrow().clone())
+    }
+
+    #[test]
+    fn test_fault_transitions_are_logged() {
+        capture();
+        let expectations = [
+            I2cTransaction::write_read(
+                BQ25730_I2C_ADDRESS,
+                vec![Register::ChargerStatus as u8],
+                vec![0x00, 0x80],
+            ),
+            I2cTransaction::write_read(
+                BQ25730_I2C_ADDRESS,
+                vec![Register::ChargerStatus as u8],
+                vec![0xC0, 0x80],
+            ),
+            I2cTransaction::write_read(
+                BQ25730_I2C_ADDRESS,
+                vec![Register::ChargerStatus as u8],
+                vec![0x40, 0x80],
+            ),
+        ];
+        let mut charger = Bq25730::new(
+            I2cMock::new(&expectations),
+            BQ25730_I2C_ADDRESS,
+            Config::default(),
+        );
+        for _ in 0..3 {
+            charger.read_charger_status().unwrap();
+        }
+        charger.i2c.done();
+
+        assert_eq!(
+            records(),
+            [
+                (
+                    Level::Warn,
+                    "Charger fault raised: FAULT_ACOV | FAULT_BATOC".to_string()
+                ),
+                (Level::Info, "Charger fault cleared: FAULT_ACOV".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_writes_and_clamps_are_logged() {
+        capture();
+        // 16256 mA is the largest 5 mOhm charge current: code 0x7F.
+        let expectations = [I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeCurrent as u8, 0xC0, 0x1F],
+        )];
+        let mut charger = Bq25730::new(
+            I2cMock::new(&expectations),
+            BQ25730_I2C_ADDRESS,
+            Config::default(),
+        );
+        charger
+            .set_charge_current_setting(ChargeCurrentSetting::from_milliamps(
+                MilliAmps(16256),
+                SenseResistorValue::R5mOhm,
+            ))
+            .unwrap();
+        assert!(matches!(
+            charger.read_registers(Register::ChargerStatus, 0),
+            Err(Error::InvalidLength { .. })
+        ));
+        charger.i2c.done();
+
+        let clamped =
+            ChargeCurrentSetting::from_milliamps(MilliAmps(20000), SenseResistorValue::R5mOhm);
+        assert_eq!(clamped.to_raw(), 0x1FC0);
+
+        let records = records();
+        assert!(records.contains(&(
+            Level::Warn,
+            "Charge current 20000 mA out of range, clamped".to_string()
+        )));
+        assert!(records.contains(&(Level::Trace, "write ChargeCurrent = [192, 31]".to_string())));
+        assert!(records.contains(&(Level::Error, "Invalid read length: 0".to_string())));
+    }
+}
@@ -0,0 +1,164 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_DEVICE_ID, BQ25730_I2C_ADDRESS, BQ25730_MANUFACTURER_ID, Bq25730, MilliAmps,
+    MilliVolts,
+    data_types::{ChargeCurrentSetting, Config, SenseResistorValue},
+    errors::Error,
+    registers::Register,
+};
+
+use embedded_hal::i2c::{Error as _, ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+mod common;
+use common::read;
+
+/// Read transactions returning `config` the way `init()` would have written it.
+fn config_readback(config: &Config) -> [I2cTransaction; 2] {
+    let mut low = vec![0u8; 16];
+    let mut high = vec![0u8; 16];
+    for (reg, lsb, msb) in config.register_values() {
+        let (block, offset) = match reg as u8 {
+            addr @ 0x00..=0x0F => (&mut low, addr as usize),
+            addr => (&mut high, addr as usize - 0x30),
+        };
+        block[offset] = lsb;
+        block[offset + 1] = msb;
+    }
+    [
+        read(Register::ChargeOption0, &low),
+        read(Register::ChargeOption1, &high),
+    ]
+}
+
+#[test]
+fn test_setter_rejects_out_of_range_value() {
+    let mut charger = Bq25730::new(I2cMock::new(&[]), BQ25730_I2C_ADDRESS, Config::default());
+
+    let result = charger.set_charge_current_setting(ChargeCurrentSetting::from_milliamps(
+        MilliAmps(20000),
+        SenseResistorValue::R5mOhm,
+    ));
+    assert!(matches!(
+        result,
+        Err(Error::OutOfRange {
+            field: "charge_current",
+            value: 20000,
+            min: 0,
+            max: 16256,
+        })
+    ));
+    assert!(matches!(
+        charger.set_vsys_th2_mv(MilliVolts(3000)),
+        Err(Error::OutOfRange {
+            field: "vsys_th2",
+            ..
+        })
+    ));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_init_checks_config_before_writing() {
+    let mut config = Config::default();
+    config.vsys_min.millivolts = MilliVolts(30000);
+    let mut charger = Bq25730::new(I2cMock::new(&[]), BQ25730_I2C_ADDRESS, config);
+
+    let err = charger.init().unwrap_err();
+    assert_eq!(err.register(), None);
+    assert_eq!(err.to_string(), "vsys_min 30000 is outside 0..=25500");
+    charger.i2c.done();
+
+    // The 12-bit field could hold 0 V, but VBAT_RNG starts at 1.024 V.
+    let mut config = Config::default();
+    config.charge_voltage.millivolts = MilliVolts(0);
+    let mut charger = Bq25730::new(I2cMock::new(&[]), BQ25730_I2C_ADDRESS, config);
+    assert!(matches!(
+        charger.init(),
+        Err(Error::OutOfRange {
+            field: "charge_voltage",
+            value: 0,
+            min: 1024,
+            max: 23000,
+        })
+    ));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_verify_device_id() {
+    let expectations = [
+        read(
+            Register::ManufacturerID,
+            &[BQ25730_MANUFACTURER_ID, BQ25730_DEVICE_ID],
+        ),
+        read(Register::ManufacturerID, &[BQ25730_MANUFACTURER_ID, 0x89]),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    charger.verify_device_id().unwrap();
+    assert!(matches!(
+        charger.verify_device_id(),
+        Err(Error::UnexpectedDevice {
+            manufacturer_id: 0x40,
+            device_id: 0x89,
+        })
+    ));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_verify_config_reports_first_mismatch() {
+    let config = Config::default();
+    let mut expectations = config_readback(&config).to_vec();
+
+    let mut other = config;
+    other.charge_voltage.millivolts = MilliVolts(8400);
+    expectations.extend(config_readback(&other));
+
+    let mut charger = Bq25730::new(I2cMock::new(&expectations), BQ25730_I2C_ADDRESS, config);
+    charger.verify_config().unwrap();
+
+    let expected = config.charge_voltage.to_raw();
+    let actual = other.charge_voltage.to_raw();
+    let err = charger.verify_config().unwrap_err();
+    assert!(matches!(
+        err,
+        Error::VerifyFailed {
+            register: Register::ChargeVoltage,
+            expected: e,
+            actual: a,
+        } if e == expected && a == actual
+    ));
+    assert_eq!(err.register(), Some(Register::ChargeVoltage));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_i2c_error_carries_register_and_kind() {
+    let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    let expectations = [read(Register::ProchotStatus, &[0x00, 0x00]).with_error(nack)];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    let err = charger.read_prochot_status().unwrap_err();
+    assert_eq!(err.register(), Some(Register::ProchotStatus));
+    assert_eq!(err.kind(), nack);
+    assert_eq!(Error::<ErrorKind>::NotInitialized.kind(), ErrorKind::Other);
+
+    let source: &dyn core::error::Error = &err;
+    assert!(
+        source
+            .to_string()
+            .starts_with("I2C error accessing ProchotStatus")
+    );
+    charger.i2c.done();
+}
@@ -209,11 +209,14 @@ fn test_read_registers_invalid_length() -> Result<(), Error<ErrorKind>> {
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
     let result = charger.read_registers(Register::ChargeOption0, 0);
     match result {
-        Err(Error::InvalidData) => {
+        Err(Error::InvalidLength {
+            register: Register::ChargeOption0,
+            len: 0,
+        }) => {
             charger.i2c.done();
             Ok(())
         }
-        _ => panic!("Expected InvalidData error, got {:?}", result),
+        _ => panic!("Expected InvalidLength error, got {:?}", result),
     }
 }
 
@@ -227,10 +230,13 @@ fn test_write_registers_invalid_length() -> Result<(), Error<ErrorKind>> {
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
     let result = charger.write_registers(Register::ChargeOption0, &[]);
     match result {
-        Err(Error::InvalidData) => {
+        Err(Error::InvalidLength {
+            register: Register::ChargeOption0,
+            len: 0,
+        }) => {
             charger.i2c.done();
             Ok(())
         }
-        _ => panic!("Expected InvalidData error, got {:?}", result),
+        _ => panic!("Expected InvalidLength error, got {:?}", result),
     }
 }
@@ -6,7 +6,7 @@ use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction
 use bq25730_async_rs::errors::Error;
 use bq25730_async_rs::registers::Register;
 use bq25730_async_rs::{
-    BQ25730_I2C_ADDRESS,
+    BQ25730_I2C_ADDRESS, MilliAmps, MilliVolts,
     data_types::{
         Config, IinDpm, IinHostSetting, InputVoltageSetting, SenseResistorValue, VsysMinSetting,
     },
@@ -24,7 +24,7 @@ fn test_set_input_voltage() -> Result<(), Error<ErrorKind>> {
     let i2c = I2cMock::new(&expectations);
     let mut charger =
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
-    charger.set_input_voltage_setting(InputVoltageSetting::from_millivolts(3200))?;
+    charger.set_input_voltage_setting(InputVoltageSetting::from_millivolts(MilliVolts(3200)))?;
     charger.i2c.done();
 
     let expectations = [I2cTransaction::write(
@@ -34,7 +34,7 @@ fn test_set_input_voltage() -> Result<(), Error<ErrorKind>> {
     let i2c = I2cMock::new(&expectations);
     let mut charger =
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
-    charger.set_input_voltage_setting(InputVoltageSetting::from_millivolts(3264))?;
+    charger.set_input_voltage_setting(InputVoltageSetting::from_millivolts(MilliVolts(3264)))?;
     charger.i2c.done();
 
     let expectations = [I2cTransaction::write(
@@ -44,7 +44,7 @@ fn test_set_input_voltage() -> Result<(), Error<ErrorKind>> {
     let i2c = I2cMock::new(&expectations);
     let mut charger =
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
-    charger.set_input_voltage_setting(InputVoltageSetting::from_millivolts(19520))?;
+    charger.set_input_voltage_setting(InputVoltageSetting::from_millivolts(MilliVolts(19520)))?;
     charger.i2c.done();
 
     Ok(())
@@ -107,38 +107,46 @@ fn test_set_vsys_min() -> Result<(), Error<ErrorKind>> {
         BQ25730_I2C_ADDRESS,
         vec![
             Register::VsysMin as u8,
-            VsysMinSetting::from_millivolts(1000).to_raw().to_le_bytes()[0],
-            VsysMinSetting::from_millivolts(1000).to_raw().to_le_bytes()[1],
+            VsysMinSetting::from_millivolts(MilliVolts(1000))
+                .to_raw()
+                .to_le_bytes()[0],
+            VsysMinSetting::from_millivolts(MilliVolts(1000))
+                .to_raw()
+                .to_le_bytes()[1],
         ], // 1000mV (raw = 10 -> 0x0A00)
     )];
     let i2c = I2cMock::new(&expectations);
     let mut charger =
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
-    charger.set_vsys_min_setting(VsysMinSetting::from_millivolts(1000))?;
+    charger.set_vsys_min_setting(VsysMinSetting::from_millivolts(MilliVolts(1000)))?;
     charger.i2c.done();
 
     let expectations = [I2cTransaction::write(
         BQ25730_I2C_ADDRESS,
         vec![
             Register::VsysMin as u8,
-            VsysMinSetting::from_millivolts(1000).to_raw().to_le_bytes()[0],
-            VsysMinSetting::from_millivolts(1000).to_raw().to_le_bytes()[1],
+            VsysMinSetting::from_millivolts(MilliVolts(1000))
+                .to_raw()
+                .to_le_bytes()[0],
+            VsysMinSetting::from_millivolts(MilliVolts(1000))
+                .to_raw()
+                .to_le_bytes()[1],
         ], // 1000mV (raw = 10 -> 0x0A00)
     )];
     let i2c = I2cMock::new(&expectations);
     let mut charger =
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
-    charger.set_vsys_min_setting(VsysMinSetting::from_millivolts(1000))?;
+    charger.set_vsys_min_setting(VsysMinSetting::from_millivolts(MilliVolts(1000)))?;
     charger.i2c.done();
 
     let expectations = [I2cTransaction::write(
         BQ25730_I2C_ADDRESS,
         vec![
             Register::VsysMin as u8,
-            VsysMinSetting::from_millivolts(23000)
+            VsysMinSetting::from_millivolts(MilliVolts(23000))
                 .to_raw()
                 .to_le_bytes()[0],
-            VsysMinSetting::from_millivolts(23000)
+            VsysMinSetting::from_millivolts(MilliVolts(23000))
                 .to_raw()
                 .to_le_bytes()[1],
         ], // 23000mV (raw = 230 -> 0xE600)
@@ -146,7 +154,7 @@ fn test_set_vsys_min() -> Result<(), Error<ErrorKind>> {
     let i2c = I2cMock::new(&expectations);
     let mut charger =
         bq25730_async_rs::Bq25730::new(i2c, bq25730_async_rs::BQ25730_I2C_ADDRESS, config);
-    charger.set_vsys_min_setting(VsysMinSetting::from_millivolts(23000))?;
+    charger.set_vsys_min_setting(VsysMinSetting::from_millivolts(MilliVolts(23000)))?;
     charger.i2c.done();
 
     Ok(())
@@ -160,7 +168,7 @@ fn test_read_vsys_min() -> Result<(), Error<ErrorKind>> {
         embedded_hal_mock::eh1::i2c::Transaction::write_read(
             BQ25730_I2C_ADDRESS,
             vec![Register::VsysMin as u8],
-            VsysMinSetting::from_millivolts(1000)
+            VsysMinSetting::from_millivolts(MilliVolts(1000))
                 .to_raw()
                 .to_le_bytes()
                 .to_vec(),
@@ -177,7 +185,7 @@ fn test_read_vsys_min() -> Result<(), Error<ErrorKind>> {
         embedded_hal_mock::eh1::i2c::Transaction::write_read(
             BQ25730_I2C_ADDRESS,
             vec![Register::VsysMin as u8],
-            VsysMinSetting::from_millivolts(1000)
+            VsysMinSetting::from_millivolts(MilliVolts(1000))
                 .to_raw()
                 .to_le_bytes()
                 .to_vec(),
@@ -194,7 +202,7 @@ fn test_read_vsys_min() -> Result<(), Error<ErrorKind>> {
         embedded_hal_mock::eh1::i2c::Transaction::write_read(
             BQ25730_I2C_ADDRESS,
             vec![Register::VsysMin as u8],
-            VsysMinSetting::from_millivolts(23000)
+            VsysMinSetting::from_millivolts(MilliVolts(23000))
                 .to_raw()
                 .to_le_bytes()
                 .to_vec(),
@@ -217,7 +225,7 @@ fn test_set_iin_host() -> Result<(), Error<ErrorKind>> {
     let config = Config::new(4, SenseResistorValue::default(), rsns_ac);
 
     // Test case 1: 100mA (offset value for 5mOhm)
-    let current_100ma = IinHostSetting::from_milliamps(100);
+    let current_100ma = IinHostSetting::from_milliamps(MilliAmps(100));
     let raw_100ma_bytes = current_100ma.to_raw(rsns_ac).to_le_bytes();
     let expectations_100ma = [I2cTransaction::write(
         BQ25730_I2C_ADDRESS,
@@ -233,7 +241,7 @@ fn test_set_iin_host() -> Result<(), Error<ErrorKind>> {
     charger_100ma.i2c.done();
 
     // Test case 2: 200mA
-    let current_200ma = IinHostSetting::from_milliamps(200);
+    let current_200ma = IinHostSetting::from_milliamps(MilliAmps(200));
     let raw_200ma_bytes = current_200ma.to_raw(rsns_ac).to_le_bytes();
     let expectations_200ma = [I2cTransaction::write(
         BQ25730_I2C_ADDRESS,
@@ -249,7 +257,7 @@ fn test_set_iin_host() -> Result<(), Error<ErrorKind>> {
     charger_200ma.i2c.done();
 
     // Test case 3: 10000mA
-    let current_10000ma = IinHostSetting::from_milliamps(10000);
+    let current_10000ma = IinHostSetting::from_milliamps(MilliAmps(10000));
     let raw_10000ma_bytes = current_10000ma.to_raw(rsns_ac).to_le_bytes();
     let expectations_10000ma = [I2cTransaction::write(
         BQ25730_I2C_ADDRESS,
@@ -0,0 +1,130 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, Celsius, JeitaLimits, JeitaProfile, JeitaZone, MilliAmps,
+    MilliVolts, NtcDivider, data_types::Config, registers::Register,
+};
+
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+fn profile() -> JeitaProfile {
+    JeitaProfile::new(JeitaLimits {
+        charge_current: MilliAmps(2048),
+        charge_voltage: MilliVolts(16800),
+    })
+}
+
+const NTC: NtcDivider = NtcDivider {
+    vref: MilliVolts(3300),
+    pullup_ohms: 10_000,
+    r25_ohms: 10_000,
+    beta: 3435,
+};
+
+#[test]
+fn test_zones_without_history() {
+    let profile = profile();
+    for (temperature, zone) in [
+        (-1, JeitaZone::Cold),
+        (0, JeitaZone::Cool),
+        (9, JeitaZone::Cool),
+        (10, JeitaZone::Normal),
+        (44, JeitaZone::Normal),
+        (45, JeitaZone::Warm),
+        (59, JeitaZone::Warm),
+        (60, JeitaZone::Hot),
+    ] {
+        assert_eq!(
+            profile.zone(Celsius(temperature), None),
+            zone,
+            "{temperature}"
+        );
+    }
+
+    assert_eq!(profile.cool.charge_current, MilliAmps(1024));
+    assert_eq!(profile.cool.charge_voltage, MilliVolts(16800));
+    // 4.1 V per cell for a 4.2 V x 4 pack.
+    assert_eq!(profile.warm.charge_voltage, MilliVolts(16400));
+    assert_eq!(profile.limits(JeitaZone::Hot), None);
+}
+
+#[test]
+fn test_hysteresis_towards_normal() {
+    let profile = profile();
+    for (previous, temperature, zone) in [
+        (JeitaZone::Cold, 1, JeitaZone::Cold),
+        (JeitaZone::Cold, 2, JeitaZone::Cool),
+        (JeitaZone::Cool, 11, JeitaZone::Cool),
+        (JeitaZone::Cool, 12, JeitaZone::Normal),
+        (JeitaZone::Warm, 44, JeitaZone::Warm),
+        (JeitaZone::Warm, 42, JeitaZone::Normal),
+        (JeitaZone::Hot, 59, JeitaZone::Hot),
+        (JeitaZone::Hot, 57, JeitaZone::Warm),
+        // Moving away from Normal has no hysteresis.
+        (JeitaZone::Normal, 9, JeitaZone::Cool),
+        (JeitaZone::Normal, 45, JeitaZone::Warm),
+        (JeitaZone::Cold, 50, JeitaZone::Warm),
+    ] {
+        assert_eq!(
+            profile.zone(Celsius(temperature), Some(previous)),
+            zone,
+            "{previous:?} at {temperature}"
+        );
+    }
+}
+
+#[test]
+fn test_ntc_beta_model() {
+    assert_eq!(NTC.temperature(MilliVolts(1650)), Some(Celsius(25)));
+    assert_eq!(NTC.temperature(MilliVolts(2447)), Some(Celsius(0)));
+    assert_eq!(NTC.temperature(MilliVolts(758)), Some(Celsius(60)));
+    assert_eq!(NTC.temperature(MilliVolts(3000)), Some(Celsius(-25)));
+    assert_eq!(NTC.temperature(MilliVolts(300)), Some(Celsius(99)));
+    // Shorted and open thermistor.
+    assert_eq!(NTC.temperature(MilliVolts(0)), None);
+    assert_eq!(NTC.temperature(MilliVolts(3300)), None);
+}
+
+#[test]
+fn test_apply_jeita_writes_on_zone_change() {
+    let expectations = [
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ADCCMPIN as u8],
+            vec![137], // 1644 mV
+        ),
+        I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeVoltage as u8, 0xA0, 0x41],
+        ),
+        I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeCurrent as u8, 0x00, 0x04],
+        ),
+        // Hot: charging stops.
+        I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeCurrent as u8, 0x00, 0x00],
+        ),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    let profile = profile();
+
+    let temperature = charger.read_ntc_temperature(&NTC).unwrap().unwrap();
+    assert_eq!(temperature, Celsius(25));
+    let zone = charger.apply_jeita(&profile, temperature, None).unwrap();
+    assert_eq!(zone, JeitaZone::Normal);
+    // Same zone: nothing is written.
+    let zone = charger
+        .apply_jeita(&profile, Celsius(30), Some(zone))
+        .unwrap();
+    let zone = charger
+        .apply_jeita(&profile, Celsius(61), Some(zone))
+        .unwrap();
+    assert_eq!(zone, JeitaZone::Hot);
+    charger.i2c.done();
+}
@@ -0,0 +1,134 @@
+#![cfg(not(feature = "async"))]
+
+use core::time::Duration;
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, LearnCycle, LearnEndReason, LearnStatus, Micros, MilliAmps,
+    MilliVolts,
+    data_types::{AdcMeasurements, Config},
+    registers::{ChargerStatusFaultFlags, Register},
+};
+
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+mod common;
+use common::{AdcCodes, read, write};
+
+/// ADC codes for IDCHG, with the same code for VBAT and VSYS.
+fn codes(idchg: u8, vbat: u8) -> AdcCodes {
+    AdcCodes {
+        idchg,
+        vbat,
+        vsys: vbat,
+        ..AdcCodes::default()
+    }
+}
+
+fn adc(idchg: u8, vbat: u8) -> AdcMeasurements {
+    codes(idchg, vbat).measurements()
+}
+
+/// Read-modify-write of ChargeOption0 from `before` to `after` (LSB).
+fn write_option0(before: u8, after: u8) -> [I2cTransaction; 2] {
+    [
+        read(Register::ChargeOption0, &[before, 0x06]),
+        write(Register::ChargeOption0, [after, 0x06]),
+    ]
+}
+
+#[test]
+fn test_learn_cycle_integrates_idchg() {
+    // VBAT 12480 mV (2880 + 150 x 64 mV); stop at 12000 mV.
+    let mut cycle = LearnCycle::new(MilliVolts(12000), &adc(0, 150));
+    let half_hour = Micros(1_800_000_000);
+    let none = ChargerStatusFaultFlags::empty();
+
+    // 0 -> 2048 mA (4 x 512 mA) averages 1024 mA: 512 mAh.
+    assert_eq!(cycle.record(half_hour, &adc(4, 150), none), None);
+    assert_eq!(cycle.summary().discharged_mah, 512);
+
+    // 2048 mA for another half hour ends at 11840 mV.
+    assert_eq!(
+        cycle.record(half_hour, &adc(4, 140), none),
+        Some(LearnEndReason::StopVoltage)
+    );
+    let summary = cycle.summary();
+    assert_eq!(summary.discharged_mah, 1536);
+    assert_eq!(summary.duration, Duration::from_secs(3600));
+    assert_eq!(summary.start_vbat, MilliVolts(12480));
+    assert_eq!(summary.end_vbat, MilliVolts(11840));
+    assert_eq!(summary.end_reason, Some(LearnEndReason::StopVoltage));
+
+    // Ended cycles no longer accumulate.
+    cycle.record(half_hour, &adc(4, 140), none);
+    assert_eq!(cycle.summary(), summary);
+}
+
+#[test]
+fn test_learn_cycle_ends_on_fault() {
+    let mut expectations = vec![codes(0, 150).read()];
+    expectations.extend(write_option0(0x0E, 0x2E));
+    expectations.extend([
+        read(Register::ChargerStatus, &[0x00, 0x80]),
+        codes(4, 150).read(),
+    ]);
+    expectations.extend([
+        read(Register::ChargerStatus, &[0x80, 0x80]),
+        codes(4, 149).read(),
+    ]);
+    expectations.extend(write_option0(0x2E, 0x0E));
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    let mut cycle = charger.start_learn_cycle(MilliVolts(12000)).unwrap();
+    assert_eq!(
+        charger
+            .step_learn_cycle(&mut cycle, Micros(1_000_000))
+            .unwrap(),
+        LearnStatus::Running {
+            vbat: MilliVolts(12480),
+            idchg: MilliAmps(2048),
+        }
+    );
+    let LearnStatus::Finished(summary) = charger
+        .step_learn_cycle(&mut cycle, Micros(1_000_000))
+        .unwrap()
+    else {
+        panic!("learn cycle did not end on FAULT_ACOV");
+    };
+    assert_eq!(
+        summary.end_reason,
+        Some(LearnEndReason::Fault(ChargerStatusFaultFlags::FAULT_ACOV))
+    );
+    assert_eq!(summary.duration, Duration::from_secs(2));
+
+    // A finished cycle is reported again without bus traffic.
+    assert_eq!(
+        charger
+            .step_learn_cycle(&mut cycle, Micros(1_000_000))
+            .unwrap(),
+        LearnStatus::Finished(summary)
+    );
+    charger.i2c.done();
+}
+
+#[test]
+fn test_abort_learn_cycle() {
+    let mut expectations = vec![codes(0, 150).read()];
+    expectations.extend(write_option0(0x0E, 0x2E));
+    expectations.extend(write_option0(0x2E, 0x0E));
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    let mut cycle = charger.start_learn_cycle(MilliVolts(12000)).unwrap();
+    let summary = charger.abort_learn_cycle(&mut cycle).unwrap();
+    assert_eq!(summary.end_reason, Some(LearnEndReason::Aborted));
+    assert_eq!(summary.discharged_mah, 0);
+    charger.i2c.done();
+}
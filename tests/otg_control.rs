@@ -3,7 +3,7 @@
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
 
 use bq25730_async_rs::{
-    BQ25730_I2C_ADDRESS, Bq25730, Error,
+    BQ25730_I2C_ADDRESS, Bq25730, Error, MilliAmps, MilliVolts,
     data_types::{Config, OtgCurrentSetting, OtgVoltageSetting, SenseResistorValue},
     registers::Register,
 };
@@ -19,7 +19,7 @@ fn test_set_otg_voltage() -> Result<(), Error<ErrorKind>> {
     )];
     let i2c = I2cMock::new(&expectations);
     let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, config);
-    charger.set_otg_voltage_setting(OtgVoltageSetting::from_millivolts(5000))?;
+    charger.set_otg_voltage_setting(OtgVoltageSetting::from_millivolts(MilliVolts(5000)))?;
     charger.i2c.done();
 
     Ok(())
@@ -51,7 +51,7 @@ fn test_set_otg_current() -> Result<(), Error<ErrorKind>> {
 
     // Test case 1: 0mA
     let current_0ma = OtgCurrentSetting {
-        milliamps: 0,
+        milliamps: MilliAmps(0),
         rsns_bat,
     };
     let raw_0ma = current_0ma.to_raw(); // Should be 0
@@ -79,7 +79,7 @@ fn test_set_otg_current() -> Result<(), Error<ErrorKind>> {
 
     // Test case 2: 1000mA (raw = 10 for 5mOhm)
     let current_1000ma = OtgCurrentSetting {
-        milliamps: 1000,
+        milliamps: MilliAmps(1000),
         rsns_bat,
     };
     let raw_1000ma = current_1000ma.to_raw(); // Should be 10 (0x0A)
@@ -106,7 +106,7 @@ fn test_set_otg_current() -> Result<(), Error<ErrorKind>> {
 
     // Test case 3: 12700mA (raw = 127 (0x7F) for 5mOhm)
     let current_12700ma = OtgCurrentSetting {
-        milliamps: 12700,
+        milliamps: MilliAmps(12700),
         rsns_bat,
     };
     let raw_12700ma = current_12700ma.to_raw(); // Should be 127 (0x7F)
@@ -151,7 +151,7 @@ fn test_read_otg_current() -> Result<(), Error<ErrorKind>> {
     assert_eq!(
         charger_0ma.read_otg_current_setting()?,
         OtgCurrentSetting {
-            milliamps: 0,
+            milliamps: MilliAmps(0),
             rsns_bat
         }
     );
@@ -168,7 +168,7 @@ fn test_read_otg_current() -> Result<(), Error<ErrorKind>> {
     assert_eq!(
         charger_1000ma.read_otg_current_setting()?,
         OtgCurrentSetting {
-            milliamps: 1000,
+            milliamps: MilliAmps(1000),
             rsns_bat
         }
     );
@@ -185,7 +185,7 @@ fn test_read_otg_current() -> Result<(), Error<ErrorKind>> {
     assert_eq!(
         charger_12700ma.read_otg_current_setting()?,
         OtgCurrentSetting {
-            milliamps: 12700,
+            milliamps: MilliAmps(12700),
             rsns_bat
         }
     );
@@ -0,0 +1,131 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, MilliAmps, MilliAmps32, OvercurrentThreshold,
+    data_types::{ChargeOption4, Config, ProchotOption0, ProchotOption1, SenseResistorValue},
+    errors::Error,
+    registers::Register,
+};
+
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+mod common;
+use common::{read, write};
+
+#[test]
+fn test_threshold_fields() {
+    // ILIM2_VTH 01001b is the 150% default; 11010b starts the 50% steps.
+    assert_eq!(ProchotOption0::from_u16(0x4800).ilim2_percent(), Some(150));
+    assert_eq!(ProchotOption0::from_u16(0xD000).ilim2_percent(), Some(250));
+    assert_eq!(ProchotOption0::from_u16(0x0000).ilim2_percent(), None);
+    assert_eq!(ProchotOption0::from_u16(0xF800).ilim2_percent(), None);
+    assert_eq!(ProchotOption0::from_u16(0xF3FF).ilim2_vth_code(), 30);
+    assert_eq!(ProchotOption0::from_u16(0xF000).ilim2_percent(), Some(450));
+
+    let prochot1 = ProchotOption1::from_u16(0x8000);
+    assert_eq!(
+        prochot1.idchg_th1(SenseResistorValue::R5mOhm),
+        MilliAmps(16384)
+    );
+    assert_eq!(
+        prochot1.idchg_th1(SenseResistorValue::R10mOhm),
+        MilliAmps(8192)
+    );
+    assert_eq!(ChargeOption4::default().idchg_th2_percent(), 150);
+
+    assert_eq!(
+        OvercurrentThreshold::acoc_range_ma(SenseResistorValue::R5mOhm),
+        MilliAmps32(10_000)..=MilliAmps32(36_000)
+    );
+    assert_eq!(
+        OvercurrentThreshold::batoc_range_ma(SenseResistorValue::R5mOhm),
+        MilliAmps32(20_000)..=MilliAmps32(72_000)
+    );
+    assert_eq!(
+        OvercurrentThreshold::batoc_range_ma(SenseResistorValue::R10mOhm),
+        MilliAmps32(10_000)..=MilliAmps32(36_000)
+    );
+}
+
+#[test]
+fn test_set_acoc_reports_trip_current() {
+    let expectations = [
+        read(Register::ProchotOption0, &[0x00, 0x48]),
+        // IIN_DPM 12800 mA.
+        read(Register::IinDpmMsb, &[0x7F]),
+        read(Register::ChargeOption2, &[0xB7, 0x02]),
+        write(Register::ChargeOption2, [0xBB, 0x02]),
+        // IIN_DPM 3000 mA: 133% of ILIM2 is below 50 mV, nothing is written.
+        read(Register::ProchotOption0, &[0x00, 0x48]),
+        read(Register::IinDpmMsb, &[29]),
+        // Reserved ILIM2_VTH 11111b, other MSB bits set: nothing is written.
+        read(Register::ProchotOption0, &[0x00, 0xFF]),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    // 133% of 150% of 12800 mA.
+    assert_eq!(
+        charger.set_acoc(OvercurrentThreshold::Percent133).unwrap(),
+        25536
+    );
+    assert!(matches!(
+        charger.set_acoc(OvercurrentThreshold::Percent133),
+        Err(Error::OutOfRange {
+            field: "acoc",
+            value: 5985,
+            min: 10_000,
+            max: 36_000,
+        })
+    ));
+    assert!(matches!(
+        charger.set_acoc(OvercurrentThreshold::Percent133),
+        Err(Error::OutOfRange {
+            field: "ilim2_vth",
+            value: 31,
+            min: 1,
+            max: 30,
+        })
+    ));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_set_batoc_rejects_clamped_threshold() {
+    let expectations = [
+        // IDCHG_TH1 16384 mA, IDCHG_TH2 150% of it.
+        read(Register::ProchotOption1, &[0x00, 0x80]),
+        read(Register::ChargeOption4, &[0x48, 0x00]),
+        read(Register::ChargeOption2, &[0xB6, 0x02]),
+        write(Register::ChargeOption2, [0xB7, 0x02]),
+        // IDCHG_TH1 4096 mA: 133% of IDCHG_TH2 is below 100 mV.
+        read(Register::ProchotOption1, &[0x00, 0x20]),
+        read(Register::ChargeOption4, &[0x48, 0x00]),
+        read(Register::ChargeOption2, &[0xB7, 0x02]),
+        write(Register::ChargeOption2, [0xB5, 0x02]),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    assert_eq!(
+        charger.set_batoc(OvercurrentThreshold::Percent200).unwrap(),
+        49152
+    );
+    assert!(matches!(
+        charger.set_batoc(OvercurrentThreshold::Percent133),
+        Err(Error::OutOfRange {
+            field: "batoc",
+            value: 8171,
+            min: 20_000,
+            max: 72_000,
+        })
+    ));
+    charger.disable_batoc().unwrap();
+    charger.i2c.done();
+}
@@ -0,0 +1,113 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, MilliAmps, PassThroughEvent, PassThroughExitReason,
+    PassThroughPolicy,
+    data_types::{AdcMeasurements, Config},
+    registers::{ChargerStatusFaultFlags, Register},
+};
+
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+mod common;
+use common::{AdcCodes, read, write};
+
+/// ADC codes for VBUS, VSYS and IIN.
+fn codes(vbus: u8, vsys: u8, iin: u8) -> AdcCodes {
+    AdcCodes {
+        vbus,
+        vsys,
+        iin,
+        ..AdcCodes::default()
+    }
+}
+
+fn adc(vbus: u8, vsys: u8, iin: u8) -> AdcMeasurements {
+    codes(vbus, vsys, iin).measurements()
+}
+
+#[test]
+fn test_policy_decisions() {
+    let policy = PassThroughPolicy::default();
+    let none = ChargerStatusFaultFlags::empty();
+    let acov = ChargerStatusFaultFlags::FAULT_ACOV;
+
+    // VBUS 19200 mV (200 x 96 mV) against VSYS 19200 mV (2880 + 255 x 64 mV), IIN 500 mA.
+    let matched = adc(200, 255, 5);
+    assert_eq!(
+        policy.evaluate(false, &matched, none),
+        Some(PassThroughEvent::Entered)
+    );
+    assert_eq!(policy.evaluate(false, &matched, acov), None);
+    assert_eq!(policy.evaluate(true, &matched, none), None);
+    assert_eq!(
+        policy.evaluate(true, &matched, acov),
+        Some(PassThroughEvent::Exited(PassThroughExitReason::Fault(acov)))
+    );
+
+    // VBUS 20160 mV is 960 mV above VSYS.
+    assert_eq!(policy.evaluate(false, &adc(210, 255, 5), none), None);
+    // 1500 mA is too much load to enter, but not enough to leave.
+    assert_eq!(policy.evaluate(false, &adc(200, 255, 15), none), None);
+    assert_eq!(policy.evaluate(true, &adc(200, 255, 15), none), None);
+    assert_eq!(
+        policy.evaluate(true, &adc(200, 255, 30), none),
+        Some(PassThroughEvent::Exited(PassThroughExitReason::LoadStep(
+            MilliAmps(3000)
+        )))
+    );
+}
+
+#[test]
+fn test_enter_exit_and_status() {
+    let expectations = [
+        read(Register::ChargeOption1, &[0x00, 0x32]),
+        write(Register::ChargeOption1, [0x04, 0x32]),
+        read(Register::ChargeOption4, &[0x49, 0x00]),
+        read(Register::ChargeOption1, &[0x04, 0x32]),
+        write(Register::ChargeOption1, [0x00, 0x32]),
+        read(Register::ChargeOption4, &[0x48, 0x00]),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    charger.enter_ptm().unwrap();
+    assert!(charger.ptm_active().unwrap());
+    charger.exit_ptm().unwrap();
+    assert!(!charger.ptm_active().unwrap());
+    charger.i2c.done();
+}
+
+#[test]
+fn test_run_policy_enters_and_leaves_on_fault() {
+    let mut expectations = Vec::new();
+    for (fault, en_ptm, written) in [(0x00, 0x00, 0x04), (0x80, 0x04, 0x00)] {
+        expectations.extend([
+            read(Register::ChargerStatus, &[fault, 0x80]),
+            codes(200, 255, 5).read(),
+            read(Register::ChargeOption1, &[en_ptm, 0x32]),
+            write(Register::ChargeOption1, [written, 0x32]),
+        ]);
+    }
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    let policy = PassThroughPolicy::default();
+
+    assert_eq!(
+        charger.run_pass_through_policy(&policy).unwrap(),
+        Some(PassThroughEvent::Entered)
+    );
+    assert_eq!(
+        charger.run_pass_through_policy(&policy).unwrap(),
+        Some(PassThroughEvent::Exited(PassThroughExitReason::Fault(
+            ChargerStatusFaultFlags::FAULT_ACOV
+        )))
+    );
+    charger.i2c.done();
+}
@@ -0,0 +1,103 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, MilliAmps,
+    data_types::{ChargeCurrentSetting, Config, SenseResistorValue},
+    errors::Error,
+    pec::{Pec, pec},
+    registers::{ChargerStatusFlags, Register},
+};
+
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+#[test]
+fn test_pec_known_answers() {
+    // CRC-8/SMBUS check value.
+    assert_eq!(pec(b"123456789"), 0xF4);
+    assert_eq!(pec(&[]), 0x00);
+    // ChargeCurrent = 0x0400 at 0x6B: 0xD6 0x02 0x00 0x04.
+    assert_eq!(pec(&[0xD6, 0x02, 0x00, 0x04]), 0x73);
+
+    let mut incremental = Pec::new();
+    incremental.update(b"1234").update(b"56789");
+    assert_eq!(incremental.value(), 0xF4);
+}
+
+#[test]
+fn test_pec_appended_to_writes() {
+    let expectations = [I2cTransaction::write(
+        BQ25730_I2C_ADDRESS,
+        vec![Register::ChargeCurrent as u8, 0x00, 0x04, 0x73],
+    )];
+    let mut charger = Bq25730::new_with_pec(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    assert!(charger.pec_enabled());
+
+    charger
+        .set_charge_current_setting(ChargeCurrentSetting::from_milliamps(
+            MilliAmps(2048),
+            SenseResistorValue::R5mOhm,
+        ))
+        .unwrap();
+    charger.i2c.done();
+}
+
+#[test]
+fn test_pec_checked_on_reads() {
+    let expectations = [
+        // PEC over 0xD6 0x20 0xD7 0x00 0x80.
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargerStatus as u8],
+            vec![0x00, 0x80, 0x58],
+        ),
+        // Same data, corrupted PEC.
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargerStatus as u8],
+            vec![0x00, 0x80, 0x59],
+        ),
+        // PEC over 0xD6 0x2E 0xD7 0x40 0xD5.
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ManufacturerID as u8],
+            vec![0x40, 0xD5, 0x6B],
+        ),
+    ];
+    let mut charger = Bq25730::new_with_pec(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    let status = charger.read_charger_status().unwrap();
+    assert_eq!(status.status_flags, ChargerStatusFlags::STAT_AC);
+    assert!(matches!(
+        charger.read_charger_status(),
+        Err(Error::CrcError {
+            register: Register::ChargerStatus
+        })
+    ));
+    charger.verify_device_id().unwrap();
+    charger.i2c.done();
+}
+
+#[test]
+fn test_pec_disabled_by_default() {
+    let expectations = [I2cTransaction::write_read(
+        BQ25730_I2C_ADDRESS,
+        vec![Register::ChargerStatus as u8],
+        vec![0x00, 0x80],
+    )];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    assert!(!charger.pec_enabled());
+    charger.read_charger_status().unwrap();
+    charger.i2c.done();
+}
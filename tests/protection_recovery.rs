@@ -0,0 +1,194 @@
+#![cfg(not(feature = "async"))]
+
+use core::time::Duration;
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, ChipVariant, FaultCounter, MilliVolts, OvercurrentThreshold,
+    ProtectedFault, ProtectionRecovery, ProtectionSettings, RecoveryPolicy,
+    data_types::{ChargeOption0, ChargeOption2, ChargeOption4, Config},
+    errors::Error,
+    registers::{ChargerStatusFaultFlags, Register},
+};
+
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+mod common;
+use common::{read, secs, write};
+
+const VSYS_UVP: ChargerStatusFaultFlags = ChargerStatusFaultFlags::FAULT_VSYS_UVP;
+
+#[test]
+fn test_settings_register_round_trip() {
+    let defaults = ProtectionSettings::from_registers(
+        ChipVariant::Bq25730,
+        &ChargeOption0::from_u16(0xE70E),
+        &ChargeOption2::from_u16(0x02B7),
+        &ChargeOption4::default(),
+    );
+    assert_eq!(defaults, ProtectionSettings::default());
+
+    let settings = ProtectionSettings {
+        vsys_uvp_enabled: false,
+        vsys_uvp: MilliVolts(8000),
+        hiccup: false,
+        acoc: Some(OvercurrentThreshold::Percent133),
+        batoc: None,
+    };
+    let mut option0 = ChargeOption0::from_u16(0xE70E);
+    let mut option2 = ChargeOption2::from_u16(0x02B7);
+    let mut option4 = ChargeOption4::default();
+    settings.apply_to(
+        ChipVariant::Bq25730,
+        &mut option0,
+        &mut option2,
+        &mut option4,
+    );
+    assert_eq!(option0.to_u16(), 0xE74E);
+    assert_eq!(option2.to_u16(), 0x02B9);
+    assert_eq!(option4.to_u16(), 0xE448);
+    assert_eq!(
+        ProtectionSettings::from_registers(ChipVariant::Bq25730, &option0, &option2, &option4),
+        settings
+    );
+}
+
+#[test]
+fn test_set_protection_settings() {
+    let expectations = [
+        read(Register::ChargeOption0, &[0x0E, 0x06]),
+        read(Register::ChargeOption2, &[0xB7, 0x02]),
+        read(Register::ChargeOption4, &[0x48, 0x00]),
+        write(Register::ChargeOption0, [0x0E, 0x06]),
+        write(Register::ChargeOption2, [0xBF, 0x02]),
+        // 4000 mV is code 2; hiccup off.
+        write(Register::ChargeOption4, [0x48, 0x44]),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    let settings = ProtectionSettings {
+        vsys_uvp: MilliVolts(4000),
+        hiccup: false,
+        acoc: Some(OvercurrentThreshold::Percent200),
+        ..ProtectionSettings::default()
+    };
+    charger.set_protection_settings(&settings).unwrap();
+
+    let too_low = ProtectionSettings {
+        vsys_uvp: MilliVolts(2000),
+        ..settings
+    };
+    assert!(matches!(
+        charger.set_protection_settings(&too_low),
+        Err(Error::OutOfRange {
+            field: "vsys_uvp",
+            ..
+        })
+    ));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_auto_clear_backs_off() {
+    let mut recovery = ProtectionRecovery::new(RecoveryPolicy::AutoClear {
+        initial_backoff: secs(1),
+        max_backoff: secs(4),
+    });
+    recovery.set_policy(ProtectedFault::SysOvp, RecoveryPolicy::Ignore);
+
+    let step = recovery.update(VSYS_UVP, secs(0));
+    assert_eq!(step.latched, VSYS_UVP);
+    assert!(step.clear.is_empty());
+    assert_eq!(recovery.update(VSYS_UVP, secs(1)).clear, VSYS_UVP);
+
+    // Each consecutive occurrence doubles the wait, up to 4 s.
+    for (latched_at, cleared_at) in [(2, 4), (5, 9), (10, 14)] {
+        assert_eq!(
+            recovery.update(VSYS_UVP, secs(latched_at)).latched,
+            VSYS_UVP
+        );
+        assert!(recovery.update(VSYS_UVP, secs(cleared_at - 1)).is_empty());
+        assert_eq!(recovery.update(VSYS_UVP, secs(cleared_at)).clear, VSYS_UVP);
+    }
+    assert_eq!(
+        recovery.counter(ProtectedFault::VsysUvp),
+        FaultCounter {
+            total: 4,
+            consecutive: 4,
+        }
+    );
+
+    // A fault-free settle_time restarts the backoff.
+    assert!(
+        recovery
+            .update(ChargerStatusFaultFlags::empty(), secs(15))
+            .is_empty()
+    );
+    recovery.update(VSYS_UVP, secs(100));
+    assert_eq!(recovery.update(VSYS_UVP, secs(101)).clear, VSYS_UVP);
+    assert_eq!(recovery.counter(ProtectedFault::VsysUvp).consecutive, 1);
+
+    // Ignored faults are counted but never cleared.
+    let sysovp = ChargerStatusFaultFlags::FAULT_SYSOVP;
+    assert_eq!(recovery.update(sysovp, secs(200)).latched, sysovp);
+    assert!(recovery.update(sysovp, secs(10_000)).is_empty());
+    assert_eq!(recovery.counter(ProtectedFault::SysOvp).total, 1);
+}
+
+#[test]
+fn test_retries_then_safe_state() {
+    let expectations = [
+        // First VSYS_UVP latch-off: cleared right away.
+        read(Register::ChargerStatus, &[0x08, 0x80]),
+        write(Register::ChargerStatus, [0x00, 0x80]),
+        // Second: out of retries, CHRG_INHIBIT set.
+        read(Register::ChargerStatus, &[0x08, 0x80]),
+        read(Register::ChargeOption0, &[0x0E, 0x06]),
+        write(Register::ChargeOption0, [0x0F, 0x06]),
+        // Safe state: left latched.
+        read(Register::ChargerStatus, &[0x08, 0x80]),
+        // Reset.
+        read(Register::ChargerStatus, &[0x08, 0x80]),
+        write(Register::ChargerStatus, [0x00, 0x80]),
+        read(Register::ChargeOption0, &[0x0F, 0x06]),
+        write(Register::ChargeOption0, [0x0E, 0x06]),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    let mut recovery = ProtectionRecovery::new(RecoveryPolicy::RetryThenSafeState {
+        delay: Duration::ZERO,
+        max_retries: 1,
+    });
+
+    let step = charger
+        .run_protection_recovery(&mut recovery, secs(0))
+        .unwrap();
+    assert_eq!(step.clear, VSYS_UVP);
+    let step = charger
+        .run_protection_recovery(&mut recovery, secs(1))
+        .unwrap();
+    assert_eq!(step.safe_state, VSYS_UVP);
+    assert!(step.clear.is_empty());
+    assert!(recovery.in_safe_state());
+    let step = charger
+        .run_protection_recovery(&mut recovery, secs(2))
+        .unwrap();
+    assert!(step.is_empty());
+
+    charger.reset_protection_recovery(&mut recovery).unwrap();
+    assert!(!recovery.in_safe_state());
+    assert_eq!(
+        recovery.counter(ProtectedFault::VsysUvp),
+        FaultCounter {
+            total: 2,
+            consecutive: 0,
+        }
+    );
+    charger.i2c.done();
+}
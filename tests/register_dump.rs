@@ -0,0 +1,262 @@
+#![cfg(not(feature = "async"))]
+#![allow(clippy::approx_constant)]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, DumpParseError, DumpWarning, MilliVolts, RegisterDump,
+    data_types::Config,
+    errors::Error,
+    registers::{ChargeOption0MsbFlags, ChargerStatusFaultFlags, ChargerStatusFlags, Register},
+};
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+/// Register blocks 0x00-0x0F, 0x20-0x2F and 0x30-0x3F with datasheet reset values.
+fn reset_blocks() -> ([u8; 16], [u8; 16], [u8; 16]) {
+    let low = [
+        0x0E, 0xE7, // ChargeOption0
+        0x00, 0x00, // ChargeCurrent
+        0xA0, 0x41, // ChargeVoltage (16.8V)
+        0xC4, 0x09, // OTGVoltage
+        0x00, 0x3C, // OTGCurrent
+        0xC8, 0x00, // InputVoltage
+        0x00, 0x7B, // VsysMin (12.3V)
+        0x00, 0x1F, // IinHost (3.2A)
+    ];
+    let status = [
+        0x00, 0x80, // ChargerStatus (STAT_AC)
+        0x00, 0xB8, // ProchotStatus
+        0x00, 0x41, // IinDpm
+        0x0A, // ADCPSYS
+        0x64, // ADCVBUS
+        0x00, // ADCIDCHG
+        0x08, // ADCICHG
+        0x00, // ADCCMPIN
+        0x14, // ADCIIN
+        0x2E, // ADCVBAT
+        0x2E, // ADCVSYS
+        0x40, // ManufacturerID
+        0xD5, // DeviceID
+    ];
+    let high = [
+        0x00, 0x3F, // ChargeOption1
+        0xB7, 0x00, // ChargeOption2
+        0x34, 0x04, // ChargeOption3
+        0x81, 0x4A, // ProchotOption0
+        0xA0, 0x41, // ProchotOption1
+        0x00, 0x20, // ADCOption
+        0x48, 0x00, // ChargeOption4
+        0x6C, 0x00, // VMINActiveProtection
+    ];
+    (low, status, high)
+}
+
+#[test]
+fn test_dump_registers_reads_all_blocks() -> Result<(), Error<ErrorKind>> {
+    let (low, status, high) = reset_blocks();
+    let expectations = [
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption0 as u8],
+            low.to_vec(),
+        ),
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargerStatus as u8],
+            status.to_vec(),
+        ),
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption1 as u8],
+            high.to_vec(),
+        ),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    let dump = charger.dump_registers()?;
+    assert_eq!(dump, RegisterDump::from_blocks(&low, &status, &high));
+    assert_eq!(dump.get(Register::DeviceID), 0xD5);
+    assert_eq!(dump.word(Register::ChargeOption0), 0xE70E);
+    assert_eq!(dump.iter().count(), Register::ALL.len());
+
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_register_dump_decode() {
+    let (low, status, high) = reset_blocks();
+    let decoded = RegisterDump::from_blocks(&low, &status, &high).decode();
+
+    assert_eq!(
+        decoded.config,
+        Config::new(4, Default::default(), Default::default())
+    );
+    assert!(
+        decoded
+            .charger_status
+            .status_flags
+            .contains(ChargerStatusFlags::STAT_AC)
+    );
+    assert_eq!(decoded.iin_dpm.milliamps, 0x41 * 100 + 100);
+    assert_eq!(decoded.adc.vbus.0, 0x64 * 96);
+    assert_eq!(decoded.adc.ichg.milliamps, 8 * 128);
+    assert_eq!(decoded.manufacturer_id, 0x40);
+    assert_eq!(decoded.device_id, 0xD5);
+}
+
+#[test]
+fn test_register_dump_display_names_fields() {
+    let (low, status, high) = reset_blocks();
+    let dump = RegisterDump::from_blocks(&low, &status, &high);
+    let text = format!("{dump}");
+
+    assert_eq!(text.lines().count(), 29);
+    assert!(text.contains("ChargeOption0"));
+    assert!(text.contains("EN_LWPWR"));
+    assert!(text.contains("16800 mV"));
+    assert!(text.contains("3200 mA"));
+    assert!(text.contains("STAT_AC"));
+    assert!(text.contains("0xd5"));
+}
+
+#[test]
+fn test_register_dump_diff() {
+    let (low, status, high) = reset_blocks();
+    let before = RegisterDump::from_blocks(&low, &status, &high);
+    assert_eq!(before.diff(&before).count(), 0);
+
+    let mut changed_low = low;
+    changed_low[1] &= !ChargeOption0MsbFlags::EN_LWPWR.bits();
+    let mut changed_status = status;
+    changed_status[1] = 0x00;
+    let after = RegisterDump::from_blocks(&changed_low, &changed_status, &high);
+
+    let diffs: Vec<_> = before.diff(&after).collect();
+    assert_eq!(diffs.len(), 2);
+    assert_eq!(diffs[0].register, Register::ChargeOption0Msb);
+    assert_eq!(diffs[0].before, 0xE7);
+    assert_eq!(diffs[0].after, 0x67);
+    assert_eq!(diffs[1].register, Register::ChargerStatusMsb);
+    assert_eq!(
+        format!("{}", diffs[1]),
+        "0x21 ChargerStatusMsb: 0x80 -> 0x00"
+    );
+}
+
+/// Formats the blocks the way `i2cdump -y 1 0x6b` prints them.
+fn i2cdump_text(low: &[u8; 16], status: &[u8; 16], high: &[u8; 16]) -> String {
+    let mut text =
+        String::from("     0  1  2  3  4  5  6  7  8  9  a  b  c  d  e  f    0123456789abcdef\n");
+    let unused = [0xFFu8; 16];
+    for (base, row) in [(0x00, low), (0x10, &unused), (0x20, status), (0x30, high)] {
+        let cells: Vec<String> = row.iter().map(|b| format!("{b:02x}")).collect();
+        text += &format!("{base:02x}: {}    ..?.............\n", cells.join(" "));
+    }
+    text += "40: XX XX XX XX XX XX XX XX XX XX XX XX XX XX XX XX    XXXXXXXXXXXXXXXX\n";
+    text
+}
+
+#[test]
+fn test_register_dump_text_round_trip() {
+    let (low, status, high) = reset_blocks();
+    let dump = RegisterDump::from_blocks(&low, &status, &high);
+    let text = format!("{}", dump.text());
+
+    assert_eq!(text.lines().count(), RegisterDump::LEN);
+    assert!(text.starts_with("0x00=0x0e  # ChargeOption0\n"));
+    assert_eq!(text.parse::<RegisterDump>(), Ok(dump));
+}
+
+#[test]
+fn test_register_dump_parses_i2cdump_output() {
+    let (low, status, high) = reset_blocks();
+    let text = i2cdump_text(&low, &status, &high);
+
+    assert_eq!(
+        text.parse::<RegisterDump>(),
+        Ok(RegisterDump::from_blocks(&low, &status, &high))
+    );
+}
+
+#[test]
+fn test_register_dump_parse_errors() {
+    assert_eq!(
+        "# header only\n\n0x00=0x0e".parse::<RegisterDump>(),
+        Err(DumpParseError::MissingRegister(Register::ChargeOption0Msb))
+    );
+    assert_eq!(
+        "0x00=0x0e\n0x10=0x01".parse::<RegisterDump>(),
+        Err(DumpParseError::UnknownRegister {
+            line: 2,
+            addr: 0x10
+        })
+    );
+    assert_eq!(
+        "0x00=0x100".parse::<RegisterDump>(),
+        Err(DumpParseError::Syntax { line: 1 })
+    );
+    assert_eq!(
+        "05: 00 00".parse::<RegisterDump>(),
+        Err(DumpParseError::Syntax { line: 1 })
+    );
+    assert_eq!(
+        "ChargeOption0 0x0e".parse::<RegisterDump>(),
+        Err(DumpParseError::Syntax { line: 1 })
+    );
+}
+
+#[test]
+fn test_register_dump_warnings() {
+    let (low, status, high) = reset_blocks();
+    let dump = RegisterDump::from_blocks(&low, &status, &high);
+    assert_eq!(dump.warnings().count(), 0);
+
+    let mut bad_low = low;
+    bad_low[4..6].copy_from_slice(&0x1770u16.to_le_bytes()); // ChargeVoltage 6V < VSYS_MIN 12.3V
+    let mut bad_status = status;
+    bad_status[0] = ChargerStatusFaultFlags::FAULT_ACOV.bits();
+    bad_status[12..14].copy_from_slice(&[0x3F, 0x3F]); // ADCVBAT/ADCVSYS 6912 mV
+    bad_status[15] = 0x89; // DeviceID of another part
+    let mut bad_high = high;
+    bad_high[5] |= 1 << 4; // EN_OTG with STAT_AC set
+    let dump = RegisterDump::from_blocks(&bad_low, &bad_status, &bad_high);
+
+    let warnings: Vec<_> = dump.warnings().collect();
+    assert_eq!(
+        warnings,
+        [
+            DumpWarning::UnexpectedId {
+                manufacturer: 0x40,
+                device: 0x89
+            },
+            DumpWarning::Faults(ChargerStatusFaultFlags::FAULT_ACOV),
+            DumpWarning::OtgWithAdapter,
+            DumpWarning::ChargeVoltageBelowVsysMin {
+                charge_voltage: MilliVolts(6000),
+                vsys_min: MilliVolts(12300)
+            },
+            DumpWarning::VbatAboveChargeVoltage {
+                vbat: MilliVolts(6912),
+                charge_voltage: MilliVolts(6000)
+            },
+        ]
+    );
+    assert_eq!(format!("{}", warnings[1]), "faults latched: FAULT_ACOV");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_register_dump_report() {
+    let (low, status, mut high) = reset_blocks();
+    let text = i2cdump_text(&low, &status, &high);
+    let report = RegisterDump::parse(text.as_bytes()).unwrap().report();
+    assert!(report.contains("16800 mV"));
+    assert!(report.ends_with("\nNo warnings.\n"));
+
+    high[0] |= 1; // CHRG_INHIBIT with 0 mA ChargeCurrent is not a warning
+    high[5] |= 1 << 4;
+    let report = RegisterDump::from_blocks(&low, &status, &high).report();
+    assert!(report.ends_with("\nWarnings:\n- EN_OTG is set while an adapter is present\n"));
+}
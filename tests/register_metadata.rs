@@ -0,0 +1,85 @@
+#![allow(clippy::approx_constant)]
+
+use bq25730_async_rs::{
+    data_types::{Config, SenseResistorValue},
+    registers::{Access, REGISTER_INFO, Register, RegisterHalf},
+};
+
+#[test]
+fn test_register_info_table_matches_register_order() {
+    for (info, register) in REGISTER_INFO.iter().zip(Register::ALL) {
+        assert_eq!(info.register, register);
+        assert_eq!(register.info(), info);
+    }
+}
+
+#[test]
+fn test_register_try_from_address() {
+    for register in Register::ALL {
+        assert_eq!(Register::try_from(register as u8), Ok(register));
+    }
+    assert_eq!(Register::try_from(0x10), Err(0x10));
+    assert_eq!(Register::try_from(0x1F), Err(0x1F));
+    assert_eq!(Register::try_from(0x40), Err(0x40));
+}
+
+#[test]
+fn test_register_pair() {
+    assert_eq!(
+        Register::ChargeVoltage.pair(),
+        RegisterHalf::Lsb(Register::ChargeVoltageMsb)
+    );
+    assert_eq!(
+        Register::VMINActiveProtectionMsb.pair(),
+        RegisterHalf::Msb(Register::VMINActiveProtection)
+    );
+    assert_eq!(
+        Register::IinDpmMsb.pair(),
+        RegisterHalf::Msb(Register::IinDpm)
+    );
+    assert_eq!(Register::ADCVBUS.pair(), RegisterHalf::Single);
+    assert_eq!(Register::DeviceID.pair(), RegisterHalf::Single);
+}
+
+#[test]
+fn test_register_access_and_status() {
+    assert_eq!(Register::ChargeOption0.access(), Access::ReadWrite);
+    assert_eq!(Register::ADCVBAT.access(), Access::ReadOnly);
+    assert!(Register::ChargerStatus.is_status());
+    assert!(!Register::ChargeOption3.is_status());
+    // Only FAULT_SYSOVP and FAULT_VSYS_UVP can be cleared by the host.
+    assert_eq!(Register::ChargerStatus.writable_mask(), 0x18);
+    assert_eq!(Register::ChargerStatusMsb.writable_mask(), 0x00);
+    // STAT_IDCHG2 and STAT_PTM are read-only.
+    assert_eq!(Register::ChargeOption4.writable_mask(), 0xFC);
+    for info in REGISTER_INFO.iter() {
+        if info.access == Access::ReadOnly {
+            assert_eq!(info.writable_mask, 0, "{:?}", info.register);
+        }
+    }
+}
+
+#[test]
+fn test_register_reset_values_depend_on_cell_count() {
+    assert_eq!(Register::VMINActiveProtection.reset_value(1), Some(0x04));
+    assert_eq!(Register::VMINActiveProtection.reset_value(3), Some(0x6C));
+    assert_eq!(Register::ProchotOption0.reset_value(1), Some(0x09));
+    assert_eq!(Register::ProchotOption0.reset_value(2), Some(0x81));
+    assert_eq!(Register::ChargeOption0Msb.reset_value(5), Some(0xE7));
+    assert_eq!(Register::InputVoltage.reset_value(4), None);
+    assert_eq!(Register::ADCVBUS.reset_value(4), None);
+
+    // Cell-dependent defaults agree with the values Config::new() programs.
+    for cells in 1..=5 {
+        let config = Config::new(
+            cells,
+            SenseResistorValue::R5mOhm,
+            SenseResistorValue::R5mOhm,
+        );
+        let (cv_lsb, cv_msb) = config.charge_voltage.to_msb_lsb_bytes();
+        assert_eq!(Register::ChargeVoltage.reset_value(cells), Some(cv_lsb));
+        assert_eq!(Register::ChargeVoltageMsb.reset_value(cells), Some(cv_msb));
+        let (_, vsys_msb) = config.vsys_min.to_msb_lsb_bytes();
+        assert_eq!(Register::VsysMinMsb.reset_value(cells), Some(vsys_msb));
+    }
+}
@@ -0,0 +1,213 @@
+#![cfg(not(feature = "async"))]
+#![allow(clippy::approx_constant)]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, MilliAmps, MilliVolts, PostResetAction,
+    data_types::{ChargeCurrentSetting, ChargeVoltageSetting, Config, InputVoltageSetting},
+    errors::Error,
+    registers::{ChargeOption3MsbFlags, Register},
+};
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+mod common;
+use common::{read, write};
+
+const CO3_LSB: u8 = 0x34;
+const CO3_MSB: u8 = 0x04;
+
+fn read_co3(msb: u8) -> I2cTransaction {
+    read(Register::ChargeOption3, &[CO3_LSB, msb])
+}
+
+fn write_co3(msb: u8) -> I2cTransaction {
+    write(Register::ChargeOption3, [CO3_LSB, msb])
+}
+
+#[test]
+fn test_reset_registers_invalidates_config() -> Result<(), Error<ErrorKind>> {
+    let reset = ChargeOption3MsbFlags::RESET_REG.bits();
+    let expectations = [
+        read_co3(CO3_MSB),
+        write_co3(CO3_MSB | reset),
+        // Still resetting on the first poll, cleared on the second.
+        read_co3(CO3_MSB | reset),
+        read_co3(CO3_MSB),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+    assert!(!charger.is_config_stale());
+
+    charger.reset_registers(PostResetAction::InvalidateConfig, &mut NoopDelay)?;
+    assert!(charger.is_config_stale());
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_apply_config_after_reset_writes_everything() -> Result<(), Error<ErrorKind>> {
+    let reset = ChargeOption3MsbFlags::RESET_REG.bits();
+    let mut config = Config::default();
+    config.charge_current = ChargeCurrentSetting::from_milliamps(MilliAmps(2048), config.rsns_bat);
+    let mut new_config = config;
+    new_config.charge_voltage = ChargeVoltageSetting::from_millivolts(MilliVolts(16000));
+    // Lower than the stale cached value, but still a raise from the reset value.
+    new_config.charge_current =
+        ChargeCurrentSetting::from_milliamps(MilliAmps(1024), config.rsns_bat);
+
+    let mut expectations = vec![
+        read_co3(CO3_MSB),
+        write_co3(CO3_MSB | reset),
+        read_co3(CO3_MSB),
+    ];
+    // Registers are at reset values, so current limits go last, after the voltages.
+    let is_current_limit = |reg: Register| {
+        matches!(
+            reg,
+            Register::ChargeCurrent | Register::OTGCurrent | Register::IinHost
+        )
+    };
+    let values = new_config.register_values();
+    let ordered = values
+        .iter()
+        .filter(|(reg, _, _)| !is_current_limit(*reg))
+        .chain(values.iter().filter(|(reg, _, _)| is_current_limit(*reg)));
+    for &(reg, lsb, msb) in ordered {
+        expectations.push(write(reg, [lsb, msb]));
+    }
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, config);
+
+    charger.reset_registers(PostResetAction::InvalidateConfig, &mut NoopDelay)?;
+    let changes = charger.apply_config(&new_config)?;
+    assert!(changes.is_all());
+    assert!(!charger.is_config_stale());
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_reset_registers_reinitializes() -> Result<(), Error<ErrorKind>> {
+    let reset = ChargeOption3MsbFlags::RESET_REG.bits();
+    let config = Config::default();
+    let co0 = config.charge_option0.to_msb_lsb_bytes();
+    let cc = config.charge_current.to_msb_lsb_bytes();
+    let cv = config.charge_voltage.to_msb_lsb_bytes();
+    let otg_v = config.otg_voltage.to_msb_lsb_bytes();
+    let otg_c = config.otg_current.to_msb_lsb_bytes();
+    let co1 = config.charge_option1.to_msb_lsb_bytes();
+    let iv = config.input_voltage.to_msb_lsb_bytes();
+    let vm = config.vsys_min.to_msb_lsb_bytes();
+    let ih = config.iin_host.to_msb_lsb_bytes(config.rsns_ac);
+    let co3 = config.charge_option3.to_msb_lsb_bytes();
+    let co4 = config.charge_option4.to_msb_lsb_bytes();
+    let vmin = config.vmin_active_protection.to_msb_lsb_bytes();
+
+    let expectations = [
+        read_co3(CO3_MSB),
+        write_co3(CO3_MSB | reset),
+        read_co3(CO3_MSB),
+        // init() sequence
+        I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![
+                Register::ChargeOption0 as u8,
+                co0.0,
+                co0.1,
+                cc.0,
+                cc.1,
+                cv.0,
+                cv.1,
+                otg_v.0,
+                otg_v.1,
+                otg_c.0,
+                otg_c.1,
+            ],
+        ),
+        write(Register::ChargeOption1, [co1.0, co1.1]),
+        I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![
+                Register::InputVoltage as u8,
+                iv.0,
+                iv.1,
+                vm.0,
+                vm.1,
+                ih.0,
+                ih.1,
+            ],
+        ),
+        write(Register::ChargeOption3, [co3.0, co3.1]),
+        I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption4 as u8, co4.0, co4.1, vmin.0, vmin.1],
+        ),
+        read(Register::ChargerStatus, &[0x00, 0x00]),
+        write(Register::ChargerStatus, [0x00, 0x00]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, config);
+
+    charger.reset_registers(PostResetAction::Reinitialize, &mut NoopDelay)?;
+    assert!(!charger.is_config_stale());
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_reset_registers_gives_up_when_bit_never_clears() {
+    let reset = ChargeOption3MsbFlags::RESET_REG.bits();
+    let mut expectations = vec![read_co3(CO3_MSB), write_co3(CO3_MSB | reset)];
+    // Polled every 1 ms for 100 ms, plus the read right after the write.
+    expectations.extend((0..=100).map(|_| read_co3(CO3_MSB | reset)));
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    assert!(matches!(
+        charger.reset_registers(PostResetAction::InvalidateConfig, &mut NoopDelay),
+        Err(Error::Timeout {
+            register: Register::ChargeOption3
+        })
+    ));
+    assert!(!charger.is_config_stale());
+    charger.i2c.done();
+}
+
+#[test]
+fn test_remeasure_vindpm() -> Result<(), Error<ErrorKind>> {
+    let reset_vindpm = ChargeOption3MsbFlags::RESET_VINDPM.bits();
+    // VINDPM re-detected at 18.048V: (18048 - 3200) / 64 = 232 = 0xE8
+    let detected = InputVoltageSetting::from_millivolts(MilliVolts(18048));
+    let (iv_lsb, iv_msb) = detected.to_msb_lsb_bytes();
+    let expectations = [
+        read_co3(CO3_MSB),
+        write_co3(CO3_MSB | reset_vindpm),
+        read_co3(CO3_MSB),
+        read(Register::InputVoltage, &[iv_lsb, iv_msb]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    let vindpm = charger.remeasure_vindpm(&mut NoopDelay)?;
+    assert_eq!(vindpm.millivolts, 18048);
+    assert_eq!(charger.config().input_voltage, vindpm);
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_remeasure_vindpm_rejected_in_otg() {
+    let en_otg = ChargeOption3MsbFlags::EN_OTG.bits();
+    let i2c = I2cMock::new(&[read_co3(CO3_MSB | en_otg)]);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    assert!(matches!(
+        charger.remeasure_vindpm(&mut NoopDelay),
+        Err(Error::ConflictingMode {
+            register: Register::ChargeOption3
+        })
+    ));
+    charger.i2c.done();
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "serde")]
+
+use bq25730_async_rs::data_types::*;
+use bq25730_async_rs::registers::*;
+use bq25730_async_rs::units::{MilliAmps, MilliVolts};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Round-trips `original` through serde-json-core and postcard, both without an allocator.
+fn test_serde_roundtrip<S>(original: S)
+where
+    S: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug,
+{
+    let mut json = [0u8; 2048];
+    let len = serde_json_core::to_slice(&original, &mut json).unwrap();
+    let (decoded, _): (S, usize) = serde_json_core::from_slice(&json[..len]).unwrap();
+    assert_eq!(original, decoded);
+
+    let mut bytes = [0u8; 512];
+    let encoded = postcard::to_slice(&original, &mut bytes).unwrap();
+    let decoded: S = postcard::from_bytes(encoded).unwrap();
+    assert_eq!(original, decoded);
+}
+
+fn to_json<S: Serialize>(value: &S) -> heapless::String<1024> {
+    serde_json_core::to_string(value).unwrap()
+}
+
+fn sample_measurements() -> AdcMeasurements {
+    AdcMeasurements {
+        vbat: AdcVbat(MilliVolts(16000)),
+        vsys: AdcVsys(MilliVolts(16200)),
+        ichg: AdcIchg {
+            milliamps: MilliAmps(2048),
+            rsns_bat: SenseResistorValue::R5mOhm,
+        },
+        idchg: AdcIdchg {
+            milliamps: MilliAmps(0),
+            rsns_bat: SenseResistorValue::R5mOhm,
+        },
+        iin: AdcIin {
+            milliamps: MilliAmps(1500),
+            rsns_ac: SenseResistorValue::R10mOhm,
+        },
+        psys: AdcPsys(MilliVolts(1500)),
+        vbus: AdcVbus(MilliVolts(20000)),
+        cmpin: AdcCmpin(MilliVolts(1200)),
+    }
+}
+
+#[test]
+fn test_serde_roundtrip_config() {
+    test_serde_roundtrip(Config::default());
+    test_serde_roundtrip(Config {
+        psys: PsysConfig::new(30_000),
+        ..Config::new(2, SenseResistorValue::R10mOhm, SenseResistorValue::R5mOhm)
+    });
+}
+
+#[test]
+fn test_serde_roundtrip_measurements_and_status() {
+    test_serde_roundtrip(sample_measurements());
+    test_serde_roundtrip(ChargerStatus {
+        status_flags: ChargerStatusFlags::STAT_AC | ChargerStatusFlags::IN_FCHRG,
+        fault_flags: ChargerStatusFaultFlags::empty(),
+    });
+    test_serde_roundtrip(ProchotStatus {
+        msb_flags: ProchotStatusMsbFlags::empty(),
+        lsb_flags: ProchotStatusFlags::all(),
+        prochot_width: 2,
+    });
+}
+
+#[test]
+fn test_serde_flags_and_units_are_readable() {
+    let status = ChargerStatus {
+        status_flags: ChargerStatusFlags::STAT_AC | ChargerStatusFlags::IN_FCHRG,
+        fault_flags: ChargerStatusFaultFlags::empty(),
+    };
+    assert_eq!(
+        to_json(&status).as_str(),
+        r#"{"status_flags":"STAT_AC | IN_FCHRG","fault_flags":""}"#
+    );
+
+    // Unit newtypes serialize as the bare number in physical units, not as raw register values.
+    let json = to_json(&sample_measurements());
+    assert!(json.starts_with(r#"{"vbat":16000,"vsys":16200,"ichg":{"milliamps":2048,"#));
+
+    let setting: ChargeVoltageSetting = serde_json_core::from_str(r#"{"millivolts":16800}"#)
+        .unwrap()
+        .0;
+    assert_eq!(setting.millivolts, MilliVolts(16800));
+}
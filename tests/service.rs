@@ -0,0 +1,166 @@
+#![cfg(feature = "embassy")]
+
+use std::thread;
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, ChargerService, Command, MilliAmps, MilliVolts, ReplayI2c,
+    Telemetry,
+    data_types::Config,
+    registers::{ChargerStatusFlags, Register},
+    trace::{TraceData, TraceEntry, TraceKind},
+};
+
+use embassy_executor::Executor;
+use embassy_futures::block_on;
+use embassy_futures::select::select;
+use embassy_sync::blocking_mutex::raw::{CriticalSectionRawMutex, NoopRawMutex};
+use embassy_sync::channel::Channel;
+use embassy_sync::watch::Watch;
+use embassy_time::{Duration, with_timeout};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+mod common;
+use common::{read, write};
+
+/// Keep-alive write of `charge_current`, then the status and ADC reads.
+fn poll(charge_current: [u8; 2]) -> [I2cTransaction; 4] {
+    [
+        write(Register::ChargeCurrent, charge_current),
+        read(Register::ChargerStatus, &[0x00, 0x80]),
+        read(Register::ProchotStatus, &[0x00, 0x00]),
+        read(Register::ADCPSYS, &[0; 8]),
+    ]
+}
+
+fn charger<I2C: embedded_hal_async::i2c::I2c + Send>(i2c: I2C) -> Bq25730<I2C> {
+    Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default())
+}
+
+#[test]
+fn test_commands_and_poll() {
+    let mut expectations = vec![
+        // 1024 mA.
+        write(Register::ChargeCurrent, [0x00, 0x02]),
+        read(Register::ChargeOption3, &[0x34, 0x04]),
+        write(Register::ChargeOption3, [0x34, 0x14]),
+    ];
+    expectations.extend(poll([0x00, 0x02]));
+    let commands = Channel::<NoopRawMutex, Command, 2>::new();
+    let telemetry = Watch::<NoopRawMutex, Telemetry, 1>::new();
+    let mut receiver = telemetry.receiver().unwrap();
+    let mut service = ChargerService::new(
+        charger(I2cMock::new(&expectations)),
+        &commands,
+        &telemetry,
+        Duration::from_secs(1),
+    );
+
+    block_on(service.handle(Command::SetChargeCurrent(MilliAmps(1024)))).unwrap();
+    block_on(service.handle(Command::SetOtg(true))).unwrap();
+    let snapshot = block_on(service.poll()).unwrap();
+    assert!(
+        snapshot
+            .charger_status
+            .status_flags
+            .contains(ChargerStatusFlags::STAT_AC)
+    );
+    assert_eq!(receiver.try_get(), Some(snapshot));
+
+    service.release().i2c.done();
+}
+
+#[test]
+fn test_run_serves_commands_between_polls() {
+    let mut expectations = vec![write(Register::ChargeCurrent, [0x00, 0x04])];
+    expectations.extend(poll([0x00, 0x04]));
+    let commands = Channel::<NoopRawMutex, Command, 2>::new();
+    let telemetry = Watch::<NoopRawMutex, Telemetry, 1>::new();
+    let mut receiver = telemetry.receiver().unwrap();
+    let mut service = ChargerService::new(
+        charger(I2cMock::new(&expectations)),
+        &commands,
+        &telemetry,
+        Duration::from_millis(10),
+    );
+
+    // Queued before the first tick, so it is handled first.
+    commands
+        .try_send(Command::SetChargeCurrent(MilliAmps(2048)))
+        .unwrap();
+    block_on(select(service.run(), receiver.changed()));
+
+    service.release().i2c.done();
+}
+
+static COMMANDS: Channel<CriticalSectionRawMutex, Command, 2> = Channel::new();
+static TELEMETRY: Watch<CriticalSectionRawMutex, Telemetry, 1> = Watch::new();
+
+type ReplayService =
+    ChargerService<'static, ReplayI2c<Vec<TraceEntry>>, CriticalSectionRawMutex, 2, 1>;
+
+#[embassy_executor::task]
+async fn charger_task(mut service: ReplayService) -> ! {
+    service.run().await
+}
+
+fn entry(kind: TraceKind, write: &[u8], read: &[u8]) -> TraceEntry {
+    TraceEntry {
+        timestamp: Default::default(),
+        address: BQ25730_I2C_ADDRESS,
+        kind,
+        write: TraceData::from_slice(write).unwrap(),
+        read: TraceData::from_slice(read).unwrap(),
+        result: Ok(()),
+    }
+}
+
+#[test]
+fn test_runs_on_std_executor() {
+    // Once the trace is used up, later polls fail and are only logged.
+    let trace = vec![
+        entry(
+            TraceKind::Write,
+            &[Register::ChargeVoltage as u8, 0x68, 0x10],
+            &[],
+        ),
+        entry(
+            TraceKind::Write,
+            &[Register::ChargeCurrent as u8, 0x00, 0x00],
+            &[],
+        ),
+        entry(
+            TraceKind::WriteRead,
+            &[Register::ChargerStatus as u8],
+            &[0x00, 0x80],
+        ),
+        entry(
+            TraceKind::WriteRead,
+            &[Register::ProchotStatus as u8],
+            &[0x00, 0x00],
+        ),
+        entry(TraceKind::WriteRead, &[Register::ADCPSYS as u8], &[0; 8]),
+    ];
+    let service = ChargerService::new(
+        charger(ReplayI2c::new(trace)),
+        &COMMANDS,
+        &TELEMETRY,
+        Duration::from_millis(20),
+    );
+    let mut receiver = TELEMETRY.receiver().unwrap();
+    COMMANDS
+        .try_send(Command::SetChargeVoltage(MilliVolts(4200)))
+        .unwrap();
+
+    thread::spawn(move || {
+        let executor: &'static mut Executor = Box::leak(Box::new(Executor::new()));
+        executor.run(|spawner| spawner.spawn(charger_task(service)).unwrap());
+    });
+
+    let snapshot = block_on(with_timeout(Duration::from_secs(5), receiver.changed())).unwrap();
+    assert!(
+        snapshot
+            .charger_status
+            .status_flags
+            .contains(ChargerStatusFlags::STAT_AC)
+    );
+}
@@ -0,0 +1,129 @@
+#![cfg(all(feature = "critical-section", not(feature = "async")))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, MilliAmps, SharedBq25730,
+    data_types::{ChargeCurrentSetting, Config, SenseResistorValue},
+    errors::Error,
+    registers::{ChargerStatusFlags, ProchotStatusFlags, Register},
+};
+
+use std::sync::{Mutex, OnceLock};
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+mod common;
+use common::{read, write};
+
+/// Runs `interrupt` once, in the middle of the first transfer, the way an
+/// ISR preempts a task that is using the bus.
+struct InterruptingI2c {
+    i2c: I2cMock,
+    interrupt: Option<fn()>,
+}
+
+impl InterruptingI2c {
+    fn fire(&mut self) {
+        if let Some(interrupt) = self.interrupt.take() {
+            interrupt();
+        }
+    }
+}
+
+impl ErrorType for InterruptingI2c {
+    type Error = ErrorKind;
+}
+
+impl I2c for InterruptingI2c {
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.fire();
+        self.i2c.write(address, bytes)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.fire();
+        self.i2c.write_read(address, bytes, buffer)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.fire();
+        self.i2c.transaction(address, operations)
+    }
+}
+
+fn shared(expectations: &[I2cTransaction]) -> SharedBq25730<I2cMock> {
+    SharedBq25730::new(Bq25730::new(
+        I2cMock::new(expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    ))
+}
+
+#[test]
+fn test_methods_forward_to_driver() {
+    let charger = shared(&[
+        write(Register::ChargeCurrent, [0x00, 0x04]),
+        read(Register::ChargerStatus, &[0x00, 0x80]),
+        read(Register::ProchotStatus, &[0x80, 0x00]),
+    ]);
+
+    charger
+        .set_charge_current_setting(ChargeCurrentSetting::from_milliamps(
+            MilliAmps(2048),
+            SenseResistorValue::R5mOhm,
+        ))
+        .unwrap();
+    let (charger_status, prochot_status) = charger.try_read_status().unwrap();
+    assert!(
+        charger_status
+            .status_flags
+            .contains(ChargerStatusFlags::STAT_AC)
+    );
+    assert_eq!(prochot_status.lsb_flags, ProchotStatusFlags::STAT_VINDPM);
+
+    charger.into_inner().i2c.done();
+}
+
+#[test]
+fn test_try_read_would_block_during_transfer() {
+    static CHARGER: OnceLock<SharedBq25730<InterruptingI2c>> = OnceLock::new();
+    static ISR_RESULT: Mutex<Option<bool>> = Mutex::new(None);
+
+    fn prochot_isr() {
+        let result = CHARGER.get().unwrap().try_read_charger_status();
+        *ISR_RESULT.lock().unwrap() = Some(matches!(result, Err(Error::WouldBlock)));
+    }
+
+    let mock = I2cMock::new(&[
+        read(Register::ChargerStatus, &[0x00, 0x80]),
+        read(Register::ChargerStatus, &[0x00, 0x00]),
+    ]);
+    let charger = CHARGER.get_or_init(|| {
+        SharedBq25730::new(Bq25730::new(
+            InterruptingI2c {
+                i2c: mock.clone(),
+                interrupt: Some(prochot_isr),
+            },
+            BQ25730_I2C_ADDRESS,
+            Config::default(),
+        ))
+    });
+
+    // The interrupt fires while the task's transfer is on the bus and must
+    // not touch the driver.
+    charger.read_charger_status().unwrap();
+    assert_eq!(*ISR_RESULT.lock().unwrap(), Some(true));
+    // Released again once the task's call returns.
+    assert!(charger.try_read_charger_status().is_ok());
+
+    mock.clone().done();
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "async")]
+
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, Bq25730Pins, NoPin, PinEventKind, StatusPin,
+    data_types::Config,
+    errors::Error,
+    registers::{ChargerStatusFlags, ProchotStatusFlags, Register},
+};
+
+use embedded_hal::digital::ErrorKind;
+use embedded_hal_mock::eh1::MockError;
+use embedded_hal_mock::eh1::digital::{
+    Edge, Mock as PinMock, State, Transaction as PinTransaction,
+};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+mod common;
+use common::read;
+
+/// Polls `future` to completion; the mocks never return `Pending`.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn read_status(charger: [u8; 2], prochot: [u8; 2]) -> [I2cTransaction; 2] {
+    [
+        read(Register::ChargerStatus, &charger),
+        read(Register::ProchotStatus, &prochot),
+    ]
+}
+
+fn charger(expectations: &[I2cTransaction]) -> Bq25730<I2cMock> {
+    Bq25730::new(
+        I2cMock::new(expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    )
+}
+
+#[test]
+fn test_chrg_ok_edge_reports_adapter() {
+    let chrg_ok = PinMock::new(&[
+        PinTransaction::wait_for_edge(Edge::Any),
+        PinTransaction::get(State::High),
+    ]);
+    let mut pins = Bq25730Pins::new(
+        charger(&read_status([0x00, 0x80], [0x00, 0x00])),
+        chrg_ok,
+        NoPin,
+        NoPin,
+    );
+
+    let event = block_on(pins.wait_for_event()).unwrap();
+    assert_eq!(event.pin, StatusPin::ChrgOk);
+    assert_eq!(event.kind, PinEventKind::AdapterPresent);
+    assert!(
+        event
+            .charger_status
+            .status_flags
+            .contains(ChargerStatusFlags::STAT_AC)
+    );
+
+    let (mut charger, mut chrg_ok, _, _) = pins.release();
+    charger.i2c.done();
+    chrg_ok.done();
+}
+
+#[test]
+fn test_prochot_edge_reports_sources() {
+    let prochot = PinMock::new(&[
+        PinTransaction::wait_for_edge(Edge::Any),
+        PinTransaction::get(State::Low),
+    ]);
+    let mut pins = Bq25730Pins::new(
+        charger(&read_status([0x00, 0x80], [0x80, 0x00])),
+        NoPin,
+        prochot,
+        NoPin,
+    );
+
+    let event = block_on(pins.wait_for_event()).unwrap();
+    assert_eq!(
+        event.kind,
+        PinEventKind::ProchotAsserted(ProchotStatusFlags::STAT_VINDPM)
+    );
+
+    let (mut charger, _, mut prochot, _) = pins.release();
+    charger.i2c.done();
+    prochot.done();
+}
+
+#[test]
+fn test_pin_error_skips_status_read() {
+    let cmpout = PinMock::new(&[PinTransaction::wait_for_edge(Edge::Any)
+        .with_error(MockError::Io(std::io::ErrorKind::NotConnected))]);
+    let mut pins = Bq25730Pins::new(charger(&[]), NoPin, NoPin, cmpout);
+
+    assert!(matches!(
+        block_on(pins.wait_for_event()),
+        Err(Error::Pin {
+            pin: StatusPin::Cmpout,
+            kind: ErrorKind::Other,
+        })
+    ));
+
+    let (mut charger, _, _, mut cmpout) = pins.release();
+    charger.i2c.done();
+    cmpout.done();
+}
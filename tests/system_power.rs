@@ -0,0 +1,134 @@
+#![cfg(not(feature = "async"))]
+#![allow(clippy::approx_constant)]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, MilliVolts, MilliWatts, PsysConfig,
+    data_types::Config,
+    errors::Error,
+    registers::{PsysMode, PsysRatio, Register},
+};
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+mod common;
+use common::{read, write};
+
+fn config_with_psys(resistor_ohms: u32) -> Config {
+    Config {
+        psys: PsysConfig::new(resistor_ohms),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_psys_power_from_voltage() {
+    let psys = PsysConfig::new(30_000);
+    // 1.5V / (30k * 1uA/W) = 50W
+    assert_eq!(
+        psys.power_from_voltage(MilliVolts(1500), PsysRatio::OneMicroampPerWatt),
+        Some(MilliWatts(50_000))
+    );
+    // Same voltage at a quarter of the gain means four times the power.
+    assert_eq!(
+        psys.power_from_voltage(MilliVolts(1500), PsysRatio::QuarterMicroampPerWatt),
+        Some(MilliWatts(200_000))
+    );
+    assert_eq!(
+        PsysConfig::default().power_from_voltage(MilliVolts(1500), PsysRatio::OneMicroampPerWatt),
+        None
+    );
+}
+
+#[test]
+fn test_read_system_power_mw() -> Result<(), Error<ErrorKind>> {
+    let expectations = [
+        // PSYS_CONFIG = 00b (PBUS + PBAT), PSYS_RATIO = 1 uA/W
+        read(Register::ChargeOption1, &[0x00, 0x0F]),
+        // ADC_FULLSCALE = 1 (12 mV/LSB)
+        read(Register::ADCOption, &[0x00, 0x20]),
+        read(Register::ADCPSYS, &[125]), // 1500 mV
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, config_with_psys(30_000));
+
+    assert_eq!(charger.read_system_power_mw()?, MilliWatts(50_000));
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_read_system_power_mw_low_gain_and_fullscale() -> Result<(), Error<ErrorKind>> {
+    let expectations = [
+        // PSYS_CONFIG = 01b (PBUS only), PSYS_RATIO = 0.25 uA/W
+        read(Register::ChargeOption1, &[0x00, 0x1D]),
+        // ADC_FULLSCALE = 0 (8 mV/LSB)
+        read(Register::ADCOption, &[0x00, 0x00]),
+        read(Register::ADCPSYS, &[100]), // 800 mV
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, config_with_psys(10_000));
+
+    // 0.8V / (10k * 0.25uA/W) = 320W
+    assert_eq!(charger.read_system_power_mw()?, MilliWatts(320_000));
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_read_system_power_mw_disabled() -> Result<(), Error<ErrorKind>> {
+    // Reset value: PSYS_CONFIG = 11b, PSYS off.
+    let expectations = [read(Register::ChargeOption1, &[0x00, 0x33])];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, config_with_psys(30_000));
+
+    assert_eq!(charger.read_system_power_mw()?, MilliWatts::ZERO);
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_read_system_power_mw_requires_resistor() {
+    let i2c = I2cMock::new(&[]);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    assert!(matches!(
+        charger.read_system_power_mw(),
+        Err(Error::MissingConfig {
+            field: "psys.resistor_ohms"
+        })
+    ));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_set_psys_mode_and_ratio() -> Result<(), Error<ErrorKind>> {
+    let expectations = [
+        read(Register::ChargeOption1, &[0x00, 0x33]),
+        // The reserved bit 0 is not part of ChargeOption1MsbFlags and reads back as 0.
+        write(Register::ChargeOption1, [0x00, 0x02]),
+        read(Register::ChargeOption1, &[0x00, 0x02]),
+        write(Register::ChargeOption1, [0x00, 0x00]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    charger.set_psys_mode(PsysMode::AdapterAndBattery)?;
+    charger.set_psys_ratio(PsysRatio::QuarterMicroampPerWatt)?;
+    charger.i2c.done();
+    Ok(())
+}
+
+#[test]
+fn test_set_psys_otg_idchg() -> Result<(), Error<ErrorKind>> {
+    let expectations = [
+        read(Register::ChargeOption3, &[0x34, 0x04]),
+        write(Register::ChargeOption3, [0x35, 0x04]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    charger.set_psys_otg_idchg(true)?;
+    charger.i2c.done();
+    Ok(())
+}
@@ -0,0 +1,258 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, Bq25730, Micros, MilliAmps, ReplayI2c, TracingI2c,
+    data_types::{ChargeCurrentSetting, Config, SenseResistorValue},
+    errors::Error,
+    registers::{ChargerStatusFlags, Register},
+    trace::{ReplayError, TraceBuffer, TraceEntry, TraceKind},
+};
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+/// Expectations for a status read followed by a 2048 mA charge current write.
+fn session_expectations() -> [I2cTransaction; 2] {
+    [
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargerStatus as u8],
+            vec![0x00, 0x80],
+        ),
+        I2cTransaction::write(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeCurrent as u8, 0x00, 0x04],
+        ),
+    ]
+}
+
+fn charge_current() -> ChargeCurrentSetting {
+    ChargeCurrentSetting::from_milliamps(MilliAmps(2048), SenseResistorValue::R5mOhm)
+}
+
+/// Records the session from `session_expectations()` and returns the trace.
+fn record_session() -> TraceBuffer<8> {
+    let mut now = 0;
+    let clock = move || {
+        now += 250;
+        Micros(now)
+    };
+    let i2c = TracingI2c::with_clock(
+        I2cMock::new(&session_expectations()),
+        TraceBuffer::<8>::new(),
+        clock,
+    );
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    charger.read_charger_status().unwrap();
+    charger
+        .set_charge_current_setting(charge_current())
+        .unwrap();
+
+    let (mut mock, trace) = charger.i2c.release();
+    mock.done();
+    trace
+}
+
+#[test]
+fn test_tracing_i2c_records_transactions() {
+    let trace = record_session();
+    let entries: Vec<&TraceEntry> = trace.iter().collect();
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0].timestamp, Micros(250));
+    assert_eq!(entries[0].address, BQ25730_I2C_ADDRESS);
+    assert_eq!(entries[0].kind, TraceKind::WriteRead);
+    assert_eq!(entries[0].register(), Some(Register::ChargerStatus));
+    assert_eq!(entries[0].write.as_slice(), &[0x20]);
+    assert_eq!(entries[0].read.as_slice(), &[0x00, 0x80]);
+    assert_eq!(entries[0].result, Ok(()));
+
+    assert_eq!(entries[1].timestamp, Micros(500));
+    assert_eq!(entries[1].kind, TraceKind::Write);
+    assert_eq!(entries[1].write.as_slice(), &[0x02, 0x00, 0x04]);
+    assert!(entries[1].read.is_empty());
+}
+
+#[test]
+fn test_tracing_i2c_records_errors() {
+    let expectations = [I2cTransaction::write_read(
+        BQ25730_I2C_ADDRESS,
+        vec![Register::ChargerStatus as u8],
+        vec![0x00, 0x00],
+    )
+    .with_error(ErrorKind::Other)];
+    let i2c = TracingI2c::new(I2cMock::new(&expectations), TraceBuffer::<4>::new());
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    assert!(matches!(
+        charger.read_charger_status(),
+        Err(Error::I2c {
+            register: Register::ChargerStatus,
+            source: ErrorKind::Other
+        })
+    ));
+    let entry = charger.i2c.sink().iter().next().unwrap();
+    assert_eq!(entry.timestamp, Micros::ZERO);
+    assert_eq!(entry.result, Err(ErrorKind::Other));
+    charger.i2c.inner_mut().done();
+}
+
+#[test]
+fn test_trace_buffer_keeps_latest_entries() {
+    // 128, 256 and 384 mA: ChargeCurrent holds 128 mA steps in bits 12:6.
+    let raw = [0x0040u16, 0x0080, 0x00C0];
+    let expectations: Vec<_> = raw
+        .iter()
+        .map(|value| {
+            let [lsb, msb] = value.to_le_bytes();
+            I2cTransaction::write(
+                BQ25730_I2C_ADDRESS,
+                vec![Register::ChargeCurrent as u8, lsb, msb],
+            )
+        })
+        .collect();
+    let i2c = TracingI2c::new(I2cMock::new(&expectations), TraceBuffer::<2>::new());
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+
+    for milliamps in [128, 256, 384] {
+        charger
+            .set_charge_current_setting(ChargeCurrentSetting::from_milliamps(
+                MilliAmps(milliamps),
+                SenseResistorValue::R5mOhm,
+            ))
+            .unwrap();
+    }
+
+    let trace = charger.i2c.sink();
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace.dropped(), 1);
+    let written: Vec<&[u8]> = trace.iter().map(|e| &e.write[1..]).collect();
+    assert_eq!(written, [[0x80, 0x00], [0xC0, 0x00]]);
+    charger.i2c.inner_mut().done();
+}
+
+#[test]
+fn test_replay_i2c_reproduces_session() {
+    let trace: Vec<TraceEntry> = record_session().iter().cloned().collect();
+    let mut charger = Bq25730::new(
+        ReplayI2c::new(trace),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+
+    let status = charger.read_charger_status().unwrap();
+    assert!(status.status_flags.contains(ChargerStatusFlags::STAT_AC));
+    assert_eq!(charger.i2c.remaining(), 1);
+    charger
+        .set_charge_current_setting(charge_current())
+        .unwrap();
+    assert_eq!(charger.i2c.finish(), Ok(()));
+
+    // Nothing left to answer with.
+    assert!(matches!(
+        charger.read_charger_status(),
+        Err(Error::I2c {
+            source: ReplayError::Exhausted,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_replay_i2c_reports_divergence_and_recorded_errors() {
+    let trace: Vec<TraceEntry> = record_session().iter().cloned().collect();
+    let mut charger = Bq25730::new(
+        ReplayI2c::new(trace.clone()),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    // The driver reads ProchotStatus where the capture read ChargerStatus.
+    assert!(matches!(
+        charger.read_prochot_status(),
+        Err(Error::I2c {
+            source: ReplayError::Mismatch { index: 0 },
+            ..
+        })
+    ));
+    assert_eq!(
+        charger.i2c.finish(),
+        Err(ReplayError::Unconsumed { remaining: 2 })
+    );
+
+    let mut failing = trace;
+    failing[0].result = Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+    let mut charger = Bq25730::new(
+        ReplayI2c::new(failing),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    assert!(matches!(
+        charger.read_charger_status(),
+        Err(Error::I2c {
+            source: ReplayError::Recorded(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_trace_entry_text_round_trip() {
+    let trace: Vec<TraceEntry> = record_session().iter().cloned().collect();
+    let lines: Vec<String> = trace.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        lines,
+        [
+            "250 0x6b write_read w=20 r=0080 ok  # ChargerStatus",
+            "500 0x6b write w=020004 - ok  # ChargeCurrent",
+        ]
+    );
+    for (line, entry) in lines.iter().zip(&trace) {
+        assert_eq!(line.parse::<TraceEntry>().as_ref(), Ok(entry));
+    }
+
+    let failed: TraceEntry = "7 0x6b read - r=00 err:nack_data".parse().unwrap();
+    assert_eq!(failed.kind, TraceKind::Read);
+    assert_eq!(
+        failed.result,
+        Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data))
+    );
+    assert!("7 0x6b write w=0 - ok".parse::<TraceEntry>().is_err());
+    assert!("7 0x6b write w=00 - maybe".parse::<TraceEntry>().is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_trace_writer_output_replays() {
+    use bq25730_async_rs::trace::{TraceParseError, TraceWriter};
+
+    let i2c = TracingI2c::new(
+        I2cMock::new(&session_expectations()),
+        TraceWriter::new(Vec::new()),
+    );
+    let mut charger = Bq25730::new(i2c, BQ25730_I2C_ADDRESS, Config::default());
+    charger.read_charger_status().unwrap();
+    charger
+        .set_charge_current_setting(charge_current())
+        .unwrap();
+    let (mut mock, writer) = charger.i2c.release();
+    mock.done();
+
+    let text = String::from_utf8(writer.into_inner()).unwrap();
+    let capture = format!("# captured on a customer board\n\n{text}");
+    let mut charger = Bq25730::new(
+        ReplayI2c::from_text(&capture).unwrap(),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    charger.read_charger_status().unwrap();
+    charger
+        .set_charge_current_setting(charge_current())
+        .unwrap();
+    assert_eq!(charger.i2c.finish(), Ok(()));
+
+    assert_eq!(
+        ReplayI2c::from_text("0 0x6b write w=00 - ok\nbogus").unwrap_err(),
+        TraceParseError { line: 2 }
+    );
+}
@@ -0,0 +1,71 @@
+use core::time::Duration;
+
+use bq25730_async_rs::{
+    Micros, MilliAmps, MilliVolts, MilliWatts,
+    data_types::{ChargeCurrentSetting, SenseResistorValue, VminActiveProtection},
+};
+
+#[test]
+fn test_unit_arithmetic() {
+    let mut v = MilliVolts(16800) - MilliVolts(800);
+    assert_eq!(v, MilliVolts(16000));
+    v += MilliVolts(8);
+    assert_eq!(v, 16008);
+    assert_eq!(MilliAmps(512) * 4, MilliAmps(2048));
+    assert_eq!(MilliAmps(2048) / 4, MilliAmps(512));
+    // Same-unit division gives a plain ratio, e.g. a register step count.
+    assert_eq!(MilliAmps(2048) / MilliAmps(64), 32);
+    assert_eq!(
+        MilliVolts(100).saturating_sub(MilliVolts(200)),
+        MilliVolts::ZERO
+    );
+    assert_eq!(MilliAmps::MAX.checked_add(MilliAmps(1)), None);
+    assert!(MilliVolts(3200) < MilliVolts(3264));
+    assert_eq!(
+        [MilliAmps(100), MilliAmps(200)]
+            .into_iter()
+            .sum::<MilliAmps>(),
+        MilliAmps(300)
+    );
+}
+
+#[test]
+fn test_unit_power_conversions() {
+    let power = MilliVolts(20000) * MilliAmps(3250);
+    assert_eq!(power, MilliWatts(65000));
+    assert_eq!(MilliAmps(3250) * MilliVolts(20000), power);
+    assert_eq!(power / MilliVolts(20000), MilliAmps(3250));
+    assert_eq!(power / MilliAmps(3250), MilliVolts(20000));
+    // Results that don't fit in u16 saturate.
+    assert_eq!(MilliWatts(100_000) / MilliVolts(1), MilliAmps::MAX);
+}
+
+#[test]
+fn test_unit_conversions_and_display() {
+    assert_eq!(u16::from(MilliVolts(4200)), 4200);
+    assert_eq!(MilliAmps::from(64), MilliAmps(64));
+    assert_eq!(Duration::from(Micros(1500)), Duration::from_micros(1500));
+    assert_eq!(Micros::try_from(Duration::from_millis(2)), Ok(Micros(2000)));
+    assert!(Micros::try_from(Duration::from_secs(5000)).is_err());
+
+    assert_eq!(format!("{}", MilliVolts(16800)), "16800 mV");
+    assert_eq!(format!("{}", MilliAmps(3200)), "3200 mA");
+    assert_eq!(format!("{}", MilliWatts(65000)), "65000 mW");
+    assert_eq!(format!("{}", Micros(10)), "10 us");
+}
+
+#[test]
+fn test_settings_use_typed_units() {
+    let setting = ChargeCurrentSetting::from_milliamps(MilliAmps(1024), SenseResistorValue::R5mOhm);
+    assert_eq!(setting.to_milliamps(), MilliAmps(1024));
+    assert_eq!(
+        ChargeCurrentSetting::from_raw(setting.to_raw(), SenseResistorValue::R5mOhm).milliamps,
+        MilliAmps(1024)
+    );
+
+    let mut vmin = VminActiveProtection::default();
+    vmin.set_vbus_vap_th_mv(MilliVolts(20000));
+    assert_eq!(vmin.vbus_vap_th_mv(), MilliVolts(15900));
+    vmin.set_vsys_th2_mv(MilliVolts(5900));
+    assert_eq!(vmin.vsys_th2_mv(), MilliVolts(5900));
+}
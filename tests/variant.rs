@@ -0,0 +1,200 @@
+#![cfg(not(feature = "async"))]
+
+use bq25730_async_rs::{
+    BQ25730_I2C_ADDRESS, BQ25731_DEVICE_ID, Bq25730, ChipVariant, MilliVolts, ProtectionSettings,
+    RegisterDump,
+    data_types::{ChargeOption0, ChargeOption2, ChargeOption4, ChargeVoltageSetting, Config},
+    errors::Error,
+    registers::Register,
+};
+
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+fn read_ids(manufacturer_id: u8, device_id: u8) -> I2cTransaction {
+    I2cTransaction::write_read(
+        BQ25730_I2C_ADDRESS,
+        vec![Register::ManufacturerID as u8],
+        vec![manufacturer_id, device_id],
+    )
+}
+
+#[test]
+fn test_variant_from_ids() {
+    assert_eq!(
+        ChipVariant::from_ids(0x40, 0xD5),
+        Some(ChipVariant::Bq25730)
+    );
+    assert_eq!(
+        ChipVariant::from_ids(0x40, BQ25731_DEVICE_ID),
+        Some(ChipVariant::Bq25731)
+    );
+    assert_eq!(ChipVariant::from_ids(0x40, 0x89), None);
+    assert_eq!(ChipVariant::from_ids(0x41, 0xD5), None);
+
+    assert_eq!(ChipVariant::default(), ChipVariant::Bq25730);
+    assert_eq!(ChipVariant::Bq25731.name(), "BQ25731");
+    for variant in ChipVariant::ALL {
+        assert_eq!(variant.cell_count(), 1..=5);
+        assert_eq!(
+            variant.charge_voltage_range(),
+            MilliVolts(1024)..=MilliVolts(23000)
+        );
+    }
+    assert!(ChipVariant::Bq25730.has_batfet_power_path());
+    assert!(ChipVariant::Bq25730.has_precharge_ldo());
+    assert!(!ChipVariant::Bq25731.has_batfet_power_path());
+    assert!(!ChipVariant::Bq25731.has_precharge_ldo());
+}
+
+#[test]
+fn test_read_device_id_selects_variant() {
+    let expectations = [
+        read_ids(0x40, 0x89),
+        read_ids(0x40, BQ25731_DEVICE_ID),
+        read_ids(0x40, BQ25731_DEVICE_ID),
+        read_ids(0x40, 0xD5),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    assert_eq!(charger.variant(), ChipVariant::Bq25730);
+
+    assert!(matches!(
+        charger.read_device_id(),
+        Err(Error::UnexpectedDevice {
+            manufacturer_id: 0x40,
+            device_id: 0x89,
+        })
+    ));
+    assert_eq!(charger.variant(), ChipVariant::Bq25730);
+
+    assert_eq!(charger.read_device_id().unwrap(), ChipVariant::Bq25731);
+    assert_eq!(charger.variant(), ChipVariant::Bq25731);
+    charger.verify_device_id().unwrap();
+    // A BQ25730 no longer matches the selected variant.
+    assert!(matches!(
+        charger.verify_device_id(),
+        Err(Error::UnexpectedDevice {
+            device_id: 0xD5,
+            ..
+        })
+    ));
+    charger.i2c.done();
+}
+
+#[test]
+fn test_charge_voltage_range_follows_variant() {
+    // 23 V (5S) is code 2875 in bits 14:3.
+    let expectations = [I2cTransaction::write(
+        BQ25730_I2C_ADDRESS,
+        vec![Register::ChargeVoltage as u8, 0xD8, 0x59],
+    )];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    charger.set_variant(ChipVariant::Bq25731);
+
+    assert!(matches!(
+        charger
+            .set_charge_voltage_setting(ChargeVoltageSetting::from_millivolts(MilliVolts(23008))),
+        Err(Error::OutOfRange {
+            field: "charge_voltage",
+            value: 23008,
+            min: 1024,
+            max: 23000,
+        })
+    ));
+    charger
+        .set_charge_voltage_setting(ChargeVoltageSetting::from_millivolts(MilliVolts(23000)))
+        .unwrap();
+    charger.i2c.done();
+
+    assert!(
+        !ChipVariant::Bq25730
+            .charge_voltage_range()
+            .contains(&MilliVolts(23008))
+    );
+}
+
+#[test]
+fn test_vsys_uvp_follows_variant() {
+    assert_eq!(
+        ProtectionSettings::vsys_uvp_range(ChipVariant::Bq25731),
+        MilliVolts(1600)..=MilliVolts(1600)
+    );
+    let defaults = ProtectionSettings::power_on(ChipVariant::Bq25731);
+    assert_eq!(defaults.vsys_uvp, MilliVolts(1600));
+
+    // The BQ25731 ignores the VSYS_UVP code, so it reads back as 1.6 V and
+    // is left alone on writes.
+    let option0 = ChargeOption0::from_u16(0xE70E);
+    let option2 = ChargeOption2::from_u16(0x02B7);
+    let mut option4 = ChargeOption4::from_u16(0x6048);
+    assert_eq!(
+        ProtectionSettings::from_registers(ChipVariant::Bq25731, &option0, &option2, &option4),
+        defaults
+    );
+    defaults.apply_to(
+        ChipVariant::Bq25731,
+        &mut option0.clone(),
+        &mut option2.clone(),
+        &mut option4,
+    );
+    assert_eq!(option4.to_u16(), 0x6048);
+
+    let expectations = [
+        read_ids(0x40, BQ25731_DEVICE_ID),
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption0 as u8],
+            vec![0x0E, 0xE7],
+        ),
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption2 as u8],
+            vec![0xB7, 0x02],
+        ),
+        I2cTransaction::write_read(
+            BQ25730_I2C_ADDRESS,
+            vec![Register::ChargeOption4 as u8],
+            vec![0x48, 0x60],
+        ),
+    ];
+    let mut charger = Bq25730::new(
+        I2cMock::new(&expectations),
+        BQ25730_I2C_ADDRESS,
+        Config::default(),
+    );
+    charger.read_device_id().unwrap();
+    assert!(matches!(
+        charger.set_protection_settings(&ProtectionSettings::default()),
+        Err(Error::OutOfRange {
+            field: "vsys_uvp",
+            value: 2400,
+            min: 1600,
+            max: 1600,
+        })
+    ));
+    assert_eq!(charger.read_protection_settings().unwrap(), defaults);
+    charger.i2c.done();
+}
+
+#[test]
+fn test_dump_reports_variant() {
+    let low = [0u8; 16];
+    let high = [0u8; 16];
+    let mut status = [0u8; 16];
+    status[14] = 0x40;
+    status[15] = BQ25731_DEVICE_ID;
+    let dump = RegisterDump::from_blocks(&low, &status, &high);
+    assert_eq!(dump.variant(), Some(ChipVariant::Bq25731));
+    assert!(dump.warnings().next().is_none());
+
+    status[15] = 0x89;
+    let dump = RegisterDump::from_blocks(&low, &status, &high);
+    assert_eq!(dump.variant(), None);
+}